@@ -122,7 +122,7 @@ impl AnimationClip {
 }
 
 /// Animation controller manages available animations and tracks current animation
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 pub struct AnimationController {
     /// Map of animation name to animation clip
     pub animations: HashMap<String, AnimationClip>,
@@ -155,6 +155,8 @@ impl AnimationController {
                 self.previous_animation = self.current_animation.clone();
                 self.current_animation = animation.to_string();
                 state.current_frame = clip.first_frame;
+                state.last_event_frame = None;
+                state.clip_finished = false;
 
                 // Update timer duration based on the clip's fps
                 let frame_duration = 1.0 / clip.fps;
@@ -169,6 +171,13 @@ impl AnimationController {
         }
     }
 
+    /// Play an animation from its first frame, even if it is already playing
+    pub fn restart(&mut self, animation: &str, state: &mut AnimationState) {
+        // A stopped state always restarts in play()
+        state.playing = false;
+        self.play(animation, state);
+    }
+
     /// Get the current animation clip, if one exists
     pub fn current_clip(&self) -> Option<&AnimationClip> {
         self.animations.get(&self.current_animation)
@@ -210,8 +219,29 @@ impl Default for AnimationController {
     }
 }
 
-/// Animation state tracks the current playback state of an animation
+/// Temporarily overrides state-driven animation selection (e.g. attack animations)
+///
+/// Removed automatically once its timer finishes.
 #[derive(Component, Debug)]
+pub struct AnimationOverride {
+    /// Name of the animation to play while the override is active
+    pub animation: String,
+    /// How long the override stays active
+    pub timer: Timer,
+}
+
+impl AnimationOverride {
+    /// Create a new override playing `animation` for `duration_secs`
+    pub fn new(animation: impl Into<String>, duration_secs: f32) -> Self {
+        Self {
+            animation: animation.into(),
+            timer: Timer::from_seconds(duration_secs, TimerMode::Once),
+        }
+    }
+}
+
+/// Animation state tracks the current playback state of an animation
+#[derive(Component, Debug, Clone)]
 pub struct AnimationState {
     /// Current frame index being displayed
     pub current_frame: usize,
@@ -223,6 +253,9 @@ pub struct AnimationState {
     pub looping: bool,
     /// Last frame on which events were processed (to prevent duplicate event firing)
     pub last_event_frame: Option<usize>,
+    /// Set when the clip played past its last frame (looped or stopped) and the
+    /// clip's end events have not been processed yet
+    pub clip_finished: bool,
 }
 
 impl AnimationState {
@@ -234,6 +267,7 @@ impl AnimationState {
             playing: false,
             looping,
             last_event_frame: None,
+            clip_finished: false,
         }
     }
 
@@ -243,6 +277,7 @@ impl AnimationState {
         self.timer.reset();
         self.playing = true;
         self.last_event_frame = None;
+        self.clip_finished = false;
     }
 }
 
@@ -257,6 +292,8 @@ impl Default for AnimationState {
 pub struct AnimationEvents {
     /// Map of frame index to events that should trigger on that frame
     pub events: HashMap<usize, Vec<AnimationEvent>>,
+    /// Events by clip name, keyed by frame relative to the clip's first frame
+    pub clip_events: HashMap<String, HashMap<usize, Vec<AnimationEvent>>>,
 }
 
 impl AnimationEvents {
@@ -274,6 +311,25 @@ impl AnimationEvents {
     pub fn get_events(&self, frame: usize) -> Option<&Vec<AnimationEvent>> {
         self.events.get(&frame)
     }
+
+    /// Replace the events of a clip (frames are relative to the clip)
+    ///
+    /// Events one past the clip's last frame (`frame_count()`) trigger when the
+    /// clip finishes or loops.
+    pub fn set_clip_events(
+        &mut self,
+        clip: impl Into<String>,
+        events: HashMap<usize, Vec<AnimationEvent>>,
+    ) {
+        self.clip_events.insert(clip.into(), events);
+    }
+
+    /// Get the events of a clip for a frame relative to the clip
+    pub fn get_clip_events(&self, clip: &str, frame: usize) -> Option<&Vec<AnimationEvent>> {
+        self.clip_events
+            .get(clip)
+            .and_then(|events| events.get(&frame))
+    }
 }
 
 /// Types of events that can be triggered by animations
@@ -285,6 +341,21 @@ pub enum AnimationEvent {
     SpawnEffect(String),
     /// Custom event with string identifier (for game-specific logic)
     Custom(String),
+    /// Activate an attack hitbox by index
+    HitboxStart(usize),
+    /// Deactivate an attack hitbox by index
+    HitboxEnd(usize),
+}
+
+/// Message fired when an animation event triggers
+#[derive(bevy::ecs::prelude::Message, Debug, Clone)]
+pub struct AnimationEventTriggered {
+    /// The animated entity
+    pub entity: Entity,
+    /// Clip that was playing
+    pub animation: String,
+    /// The triggered event
+    pub event: AnimationEvent,
 }
 
 #[cfg(test)]
@@ -324,6 +395,25 @@ mod tests {
         assert_eq!(frame_events.len(), 2);
     }
 
+    #[test]
+    fn test_restart_replays_current_animation() {
+        let mut controller = AnimationController::new();
+        let mut state = AnimationState::default();
+        controller.add_animation("attack", AnimationClip::new(4, 7, 12.0).unwrap());
+
+        controller.play("attack", &mut state);
+        state.current_frame = 6;
+        state.last_event_frame = Some(6);
+
+        // play() keeps the running animation, restart() starts it over
+        controller.play("attack", &mut state);
+        assert_eq!(state.current_frame, 6);
+
+        controller.restart("attack", &mut state);
+        assert_eq!(state.current_frame, 4);
+        assert_eq!(state.last_event_frame, None);
+    }
+
     #[test]
     fn test_animation_clip_validation() {
         // Test invalid frame range
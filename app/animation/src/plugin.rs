@@ -5,7 +5,8 @@
 use bevy::prelude::*;
 use tropical_fox_common::{CharacterAssets, CharacterTextureAtlas, GameState};
 
-use crate::systems::{process_animation_events, update_animations};
+use crate::components::AnimationEventTriggered;
+use crate::systems::{process_animation_events, update_animation_overrides, update_animations};
 
 /// Plugin that manages sprite animations and character assets
 pub struct AnimationPlugin;
@@ -15,16 +16,19 @@ impl Plugin for AnimationPlugin {
         // Load character assets at startup
         app.add_systems(Startup, load_character_assets);
 
+        app.add_message::<AnimationEventTriggered>();
+
         // Core animation systems run in FixedUpdate for smooth, frame-rate independent updates
         app.add_systems(
             FixedUpdate,
             update_animations.run_if(in_state(GameState::InGame)),
         );
 
-        // Animation events run in Update
+        // Animation events and overrides run in Update
         app.add_systems(
             Update,
-            process_animation_events.run_if(in_state(GameState::InGame)),
+            (process_animation_events, update_animation_overrides)
+                .run_if(in_state(GameState::InGame)),
         );
     }
 }
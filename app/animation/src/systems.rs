@@ -2,9 +2,12 @@
 
 use bevy::prelude::*;
 
-use tropical_fox_common::{GroundDetection, Player, Velocity};
+use tropical_fox_common::{Player, PlayerState};
 
-use crate::components::{AnimationController, AnimationEvent, AnimationEvents, AnimationState};
+use crate::components::{
+    AnimationController, AnimationEvent, AnimationEventTriggered, AnimationEvents,
+    AnimationOverride, AnimationState,
+};
 
 /// Update all active animations, advancing frames based on time
 pub fn update_animations(
@@ -38,6 +41,7 @@ pub fn update_animations(
 
             // Handle end of animation
             if state.current_frame > clip.last_frame {
+                state.clip_finished = true;
                 if state.looping {
                     // Loop back to the beginning
                     state.current_frame = clip.first_frame;
@@ -56,30 +60,52 @@ pub fn update_animations(
     }
 }
 
-/// Control player animation based on movement state
+/// Animation clip played for each player state
+pub fn player_state_animation(state: PlayerState) -> &'static str {
+    match state {
+        PlayerState::Idle => "idle",
+        PlayerState::Run => "run",
+        PlayerState::Jump => "jump",
+        PlayerState::Fall => "fall",
+        PlayerState::Dash => "roll",
+        PlayerState::WallSlide => "climb",
+        PlayerState::Attack => "attack_1",
+        PlayerState::Hurt => "hurt",
+        PlayerState::Dead => "dizzy",
+    }
+}
+
+/// Control player animation based on the player state machine
 pub fn player_animation_controller(
     mut query: Query<
         (
-            &Velocity,
-            &GroundDetection,
+            &PlayerState,
             &mut AnimationController,
             &mut AnimationState,
+            Option<&AnimationOverride>,
         ),
         With<Player>,
     >,
 ) {
-    for (velocity, ground, mut controller, mut state) in &mut query {
-        // Determine which animation to play based on player state
-        let animation = if ground.is_grounded {
-            // On ground: play idle or run based on horizontal velocity
-            if velocity.x.abs() > 10.0 {
-                "run"
-            } else {
-                "idle"
+    for (player_state, mut controller, mut state, animation_override) in &mut query {
+        // Overrides (e.g. combo attacks) take priority over state animations
+        let animation = match animation_override {
+            Some(animation_override)
+                if *player_state != PlayerState::Hurt
+                    && controller
+                        .animations
+                        .contains_key(&animation_override.animation) =>
+            {
+                animation_override.animation.as_str()
             }
+            _ => player_state_animation(*player_state),
+        };
+
+        // Fall back to idle for characters without a clip for this state
+        let animation = if controller.animations.contains_key(animation) {
+            animation
         } else {
-            // In air: play jump (ascending) or fall (descending) based on vertical velocity
-            if velocity.y > 50.0 { "jump" } else { "fall" }
+            "idle"
         };
 
         // Play the animation (will only restart if it's a different animation)
@@ -87,31 +113,92 @@ pub fn player_animation_controller(
     }
 }
 
-/// Process animation events that should trigger on specific frames
-pub fn process_animation_events(mut query: Query<(&mut AnimationState, &AnimationEvents)>) {
-    for (mut state, events) in &mut query {
-        // Only process events if we've moved to a new frame
-        if state.last_event_frame == Some(state.current_frame) {
-            continue;
+/// Tick animation overrides and remove them once finished
+pub fn update_animation_overrides(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut AnimationOverride)>,
+) {
+    for (entity, mut animation_override) in &mut query {
+        animation_override.timer.tick(time.delta());
+
+        if animation_override.timer.is_finished() {
+            commands.entity(entity).remove::<AnimationOverride>();
         }
+    }
+}
+
+/// Process animation events that should trigger on specific frames
+///
+/// Every triggered event is also sent as an `AnimationEventTriggered` message
+/// so gameplay systems (e.g. attack hitboxes) can react to it.
+pub fn process_animation_events(
+    mut query: Query<(
+        Entity,
+        &AnimationController,
+        &mut AnimationState,
+        &AnimationEvents,
+    )>,
+    mut triggered: MessageWriter<AnimationEventTriggered>,
+) {
+    for (entity, controller, mut state, events) in &mut query {
+        let clip = controller.current_clip();
+
+        // Events past the clip's last frame fire once the clip has played through
+        let end_events = if state.clip_finished {
+            state.clip_finished = false;
+            clip.and_then(|clip| {
+                events.get_clip_events(&controller.current_animation, clip.frame_count())
+            })
+        } else {
+            None
+        };
 
-        // Check if there are events for the current frame
-        if let Some(frame_events) = events.get_events(state.current_frame) {
-            for event in frame_events {
-                match event {
-                    AnimationEvent::PlaySound(sound) => {
-                        // TODO: Integrate with audio system when available
-                        debug!("Animation event: Play sound '{}'", sound);
-                    }
-                    AnimationEvent::SpawnEffect(effect) => {
-                        // TODO: Integrate with VFX system when available
-                        debug!("Animation event: Spawn effect '{}'", effect);
-                    }
-                    AnimationEvent::Custom(event_name) => {
-                        debug!("Animation event: Custom event '{}'", event_name);
-                    }
+        // Events of a new frame: keyed by absolute frame, then by the playing clip
+        let new_frame = state.last_event_frame != Some(state.current_frame);
+        let (absolute_events, clip_events) = if new_frame {
+            (
+                events.get_events(state.current_frame),
+                clip.and_then(|clip| {
+                    state
+                        .current_frame
+                        .checked_sub(clip.first_frame)
+                        .and_then(|frame| {
+                            events.get_clip_events(&controller.current_animation, frame)
+                        })
+                }),
+            )
+        } else {
+            (None, None)
+        };
+
+        let frame_events = end_events
+            .into_iter()
+            .chain(absolute_events)
+            .chain(clip_events)
+            .flatten();
+
+        for event in frame_events {
+            match event {
+                AnimationEvent::PlaySound(sound) => {
+                    // TODO: Integrate with audio system when available
+                    debug!("Animation event: Play sound '{}'", sound);
+                }
+                AnimationEvent::SpawnEffect(effect) => {
+                    // TODO: Integrate with VFX system when available
+                    debug!("Animation event: Spawn effect '{}'", effect);
                 }
+                AnimationEvent::Custom(event_name) => {
+                    debug!("Animation event: Custom event '{}'", event_name);
+                }
+                AnimationEvent::HitboxStart(_) | AnimationEvent::HitboxEnd(_) => {}
             }
+
+            triggered.write(AnimationEventTriggered {
+                entity,
+                animation: controller.current_animation.clone(),
+                event: event.clone(),
+            });
         }
 
         // Mark this frame as processed
@@ -159,4 +246,27 @@ mod tests {
         // Check that the system runs without crashing
         assert!(app.world().get::<AnimationState>(entity).is_some());
     }
+
+    #[test]
+    fn test_clip_events_are_relative_to_clip() {
+        let mut app = App::new();
+        app.add_message::<AnimationEventTriggered>();
+        app.add_systems(Update, process_animation_events);
+
+        let mut controller = AnimationController::new();
+        controller.add_animation("attack", AnimationClip::new(36, 37, 12.0).unwrap());
+        let mut state = AnimationState::default();
+        controller.play("attack", &mut state);
+
+        let mut events = AnimationEvents::new();
+        events.set_clip_events("attack", [(0, vec![AnimationEvent::HitboxStart(0)])].into());
+
+        app.world_mut().spawn((controller, state, events));
+        app.update();
+
+        let messages = app.world().resource::<Messages<AnimationEventTriggered>>();
+        let triggered: Vec<_> = messages.iter_current_update_messages().collect();
+        assert_eq!(triggered.len(), 1);
+        assert!(matches!(triggered[0].event, AnimationEvent::HitboxStart(0)));
+    }
 }
@@ -1,47 +1,125 @@
 //! Attack system components and systems
 //!
 //! Handles attack hitbox creation, collision detection, and multi-hit prevention.
+//! Hitboxes take their owner's `Faction` and hit every hostile `Hurtbox`.
+//! Hitboxes are described in `assets/config/attacks.ron` and spawned/despawned
+//! by animation frame events.
 
 use bevy::prelude::*;
+use rand::Rng;
 use std::collections::HashSet;
 
-use super::{DamageEvent, EnemyHealth, PlayerHealth};
-use tropical_fox_common::{Collider, Player};
+use super::{
+    AERIAL_ATTACK, AttackDefinition, AttacksConfig, BASIC_ATTACK, CHARGED_ATTACKS, ComboConfig,
+    ComboState, CriticalHit, DamageEvent, DamageFlash, DamageType, Faction, FactionsConfig,
+    FxAssets, HitImpact, HitboxShape, StatusEffect, StatusEffects, spawn_fx,
+};
+use tropical_fox_animation::{
+    AnimationController, AnimationEvent, AnimationEventTriggered, AnimationEvents,
+    AnimationOverride, AnimationState,
+};
+use tropical_fox_common::{
+    Collider, GroundDetection, Player, PlayerInput, PlayerState, SpatialIndex,
+};
 
 /// Attack hitbox component
 #[derive(Component, Debug)]
 pub struct Attack {
     /// Damage dealt on hit
     pub damage: f32,
-    /// Knockback force on hit
-    pub knockback: f32,
-    /// Lifetime timer (attack despawns when finished)
-    pub lifetime: Timer,
+    /// Elemental type of the damage
+    pub damage_type: DamageType,
+    /// Knockback applied on hit
+    pub knockback: Vec2,
+    /// Hit stop and screen shake on hit (None uses the combat config)
+    pub impact: Option<HitImpact>,
+    /// Status effect applied on hit
+    pub status: Option<StatusEffect>,
+    /// Critical hit chance
+    pub critical: Option<CriticalHit>,
+    /// Entity performing the attack (never hit by it)
+    pub owner: Option<Entity>,
     /// Entities already hit by this attack (prevents multi-hit)
     pub hit_entities: HashSet<Entity>,
 }
 
 impl Attack {
-    /// Create a new attack with specified damage, knockback, and duration
-    pub fn new(damage: f32, knockback: f32, duration_secs: f32) -> Self {
+    /// Create a new physical attack with specified damage, knockback, and hit feedback
+    pub fn new(damage: f32, knockback: Vec2, impact: Option<HitImpact>) -> Self {
         Self {
             damage,
+            damage_type: DamageType::Physical,
             knockback,
-            lifetime: Timer::from_seconds(duration_secs, TimerMode::Once),
+            impact,
+            status: None,
+            critical: None,
+            owner: None,
             hit_entities: HashSet::new(),
         }
     }
+
+    /// Set the elemental type of the attack
+    pub fn with_damage_type(mut self, damage_type: DamageType) -> Self {
+        self.damage_type = damage_type;
+        self
+    }
+
+    /// Set the status effect applied on hit
+    pub fn with_status(mut self, status: Option<StatusEffect>) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the critical hit chance
+    pub fn with_critical(mut self, critical: Option<CriticalHit>) -> Self {
+        self.critical = critical;
+        self
+    }
+
+    /// Set the entity performing the attack
+    pub fn with_owner(mut self, owner: Entity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Damage of a single hit, and whether it was critical
+    pub fn roll_damage(&self, rng: &mut impl Rng) -> (f32, bool) {
+        match self.critical {
+            Some(critical) if rng.random::<f32>() < critical.chance => {
+                (self.damage * critical.multiplier, true)
+            }
+            _ => (self.damage, false),
+        }
+    }
 }
 
-/// Attack type variants
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AttackType {
-    /// Basic attack: moderate damage and range
-    Basic,
-    /// Charged attack: high damage and range
-    Charged,
-    /// Aerial attack: downward attack in air
-    Aerial,
+/// Links a hitbox to the attack that spawned it
+///
+/// The hitbox is despawned by its `HitboxEnd` frame event, or when its
+/// owner's attack ends.
+#[derive(Component, Debug)]
+pub struct AttackHitbox {
+    /// The entity that performed the attack
+    pub owner: Entity,
+    /// Name of the attack (from `assets/config/attacks.ron`)
+    pub attack: String,
+    /// Index of the hitbox in the attack definition
+    pub index: usize,
+}
+
+/// Seconds of holding the attack button needed to reach each charge level
+pub const CHARGE_LEVEL_TIMES: [f32; 2] = [0.4, 0.9];
+
+/// Sprite tint shown at each charge level (from level 1)
+const CHARGE_TINTS: [Color; 2] = [Color::srgb(1.0, 1.0, 0.5), Color::srgb(1.0, 0.6, 0.2)];
+
+/// Aerial down-attack hitbox (bounces its owner upward on hit)
+#[derive(Component, Debug)]
+pub struct AerialAttack {
+    /// The entity that performed the attack
+    pub owner: Entity,
+    /// Upward velocity applied to the owner on hit
+    pub bounce_force: f32,
 }
 
 /// Attack cooldown component (prevents attack spam)
@@ -70,6 +148,13 @@ impl AttackCooldown {
     pub fn reset(&mut self) {
         self.timer.reset();
     }
+
+    /// Restart the cooldown with a new duration
+    pub fn start(&mut self, duration_secs: f32) {
+        self.timer
+            .set_duration(std::time::Duration::from_secs_f32(duration_secs));
+        self.timer.reset();
+    }
 }
 
 impl Default for AttackCooldown {
@@ -78,57 +163,529 @@ impl Default for AttackCooldown {
     }
 }
 
-/// Marker component indicating an entity is currently attacking
+/// Component present while the attack button is held to charge an attack
 #[derive(Component, Debug, Default)]
-pub struct Attacking;
+pub struct ChargingAttack {
+    /// Time spent charging (seconds)
+    pub elapsed: f32,
+}
 
-/// Marker for player-owned attacks
-#[derive(Component, Debug, Default)]
-pub struct PlayerAttack;
+impl ChargingAttack {
+    /// Current charge level (0 = not charged, falls back to the basic attack)
+    pub fn level(&self) -> usize {
+        CHARGE_LEVEL_TIMES
+            .iter()
+            .take_while(|&&time| self.elapsed >= time)
+            .count()
+    }
 
-/// Marker for enemy-owned attacks
-#[derive(Component, Debug, Default)]
-pub struct EnemyAttack;
+    /// Attack to perform when the charge is released
+    pub fn release_attack(&self) -> &'static str {
+        match self.level() {
+            0 => BASIC_ATTACK,
+            level => CHARGED_ATTACKS[level - 1],
+        }
+    }
+}
+
+/// Component indicating an entity is currently attacking
+///
+/// Removed automatically once its timer finishes.
+#[derive(Component, Debug)]
+pub struct Attacking {
+    /// Name of the attack being performed (from `assets/config/attacks.ron`)
+    pub attack: String,
+    /// How long the attack state lasts
+    pub timer: Timer,
+    /// Facing direction when the attack started (1.0 = right)
+    pub facing_dir: f32,
+    /// Whether the attack animation has been started
+    pub started: bool,
+    /// Hitboxes already spawned (a looping clip must not spawn them twice)
+    pub spawned_hitboxes: HashSet<usize>,
+}
+
+impl Attacking {
+    /// Create an attacking state for `attack` lasting `duration_secs`
+    pub fn new(attack: impl Into<String>, duration_secs: f32, facing_dir: f32) -> Self {
+        Self {
+            attack: attack.into(),
+            timer: Timer::from_seconds(duration_secs, TimerMode::Once),
+            facing_dir,
+            started: false,
+            spawned_hitboxes: HashSet::new(),
+        }
+    }
+}
 
 /// Hurtbox component - marks an entity as damageable
 #[derive(Component, Debug, Default)]
 pub struct Hurtbox;
 
 /// Handle player attack input
+///
+/// Holding the attack button charges an attack that is released when the
+/// button is let go (a short press performs the next combo step instead).
+/// Presses during a combo step's recovery are buffered and continue the chain.
+/// Attacking while airborne and holding down performs an aerial down-attack.
+/// Attacks only start from states that can transition to `PlayerState::Attack`.
+#[allow(clippy::type_complexity)]
 pub fn player_attack_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
-    mut player_query: Query<(Entity, &Transform, &mut AttackCooldown, &Player), With<Player>>,
+    combo_config: Res<ComboConfig>,
+    attacks: Res<AttacksConfig>,
+    mut player_query: Query<(
+        Entity,
+        &PlayerInput,
+        &mut AttackCooldown,
+        &Player,
+        Option<&PlayerState>,
+        Option<&GroundDetection>,
+        Option<&ChargingAttack>,
+        Option<&mut ComboState>,
+    )>,
 ) {
-    let Ok((player_entity, transform, mut cooldown, player)) = player_query.single_mut() else {
-        return;
+    for (player_entity, input, mut cooldown, player, state, ground, charging, mut combo) in
+        player_query.iter_mut()
+    {
+        // Knockback and death block attacking (and charging)
+        if state.is_some_and(|state| !state.can_enter(PlayerState::Attack)) {
+            continue;
+        }
+
+        if let Some(charging) = charging {
+            // Release the charged attack once the button is let go
+            if !input.attack.pressed {
+                commands.entity(player_entity).remove::<ChargingAttack>();
+
+                // Uncharged release starts the combo chain
+                if charging.level() == 0
+                    && let Some(combo) = combo.as_deref_mut()
+                    && perform_combo_step(
+                        &mut commands,
+                        player_entity,
+                        player,
+                        &mut cooldown,
+                        combo,
+                        &combo_config,
+                        &attacks,
+                    )
+                {
+                    continue;
+                }
+
+                start_attack(
+                    &mut commands,
+                    player_entity,
+                    player,
+                    &mut cooldown,
+                    charging.release_attack(),
+                    &attacks,
+                );
+                if let Some(combo) = combo.as_deref_mut() {
+                    combo.reset();
+                }
+            }
+            continue;
+        }
+
+        let mut attack_pressed = input.attack.just_pressed;
+        if let Some(combo) = combo.as_deref_mut() {
+            if attack_pressed && !cooldown.can_attack() {
+                // Remember presses made during recovery
+                combo.buffer_input(combo_config.input_buffer_secs);
+            } else if cooldown.can_attack() && combo.take_buffered() {
+                attack_pressed = true;
+            }
+        }
+
+        if !attack_pressed || !cooldown.can_attack() {
+            continue;
+        }
+
+        let airborne = ground.is_some_and(|g| !g.is_grounded);
+        let holding_down = input.vertical() < 0.0;
+
+        if airborne && holding_down {
+            start_attack(
+                &mut commands,
+                player_entity,
+                player,
+                &mut cooldown,
+                AERIAL_ATTACK,
+                &attacks,
+            );
+            if let Some(combo) = combo.as_deref_mut() {
+                combo.reset();
+            }
+        } else if let Some(combo) = combo.as_deref_mut()
+            && combo.in_chain()
+        {
+            // Follow-up presses continue the chain immediately
+            perform_combo_step(
+                &mut commands,
+                player_entity,
+                player,
+                &mut cooldown,
+                combo,
+                &combo_config,
+                &attacks,
+            );
+        } else {
+            // Start charging (the attack is released on button up)
+            commands
+                .entity(player_entity)
+                .insert(ChargingAttack::default());
+        }
+    }
+}
+
+/// Perform the next step of the entity's combo chain
+///
+/// Returns false if the chain or its attack is not defined.
+fn perform_combo_step(
+    commands: &mut Commands,
+    player_entity: Entity,
+    player: &Player,
+    cooldown: &mut AttackCooldown,
+    combo: &mut ComboState,
+    combo_config: &ComboConfig,
+    attacks: &AttacksConfig,
+) -> bool {
+    let Some(step) = combo_config.step(&combo.chain, combo.next_step) else {
+        return false;
     };
 
-    // X key or J key for basic attack
-    if (keyboard.just_pressed(KeyCode::KeyX) || keyboard.just_pressed(KeyCode::KeyJ))
-        && cooldown.can_attack()
+    let Some(definition) = start_attack(
+        commands,
+        player_entity,
+        player,
+        cooldown,
+        &step.attack,
+        attacks,
+    ) else {
+        return false;
+    };
+
+    combo.advance(
+        combo_config.chain_len(&combo.chain),
+        definition.recovery_secs + step.cancel_window_secs,
+    );
+    true
+}
+
+/// Start an attack by name
+///
+/// The attack's animation and hitboxes are started by `attack_start_system`.
+/// Returns None if the attack is not defined.
+fn start_attack<'a>(
+    commands: &mut Commands,
+    entity: Entity,
+    player: &Player,
+    cooldown: &mut AttackCooldown,
+    name: &str,
+    attacks: &'a AttacksConfig,
+) -> Option<&'a AttackDefinition> {
+    let Some(definition) = attacks.get(name) else {
+        warn!("Attack '{}' is not defined in attacks.ron", name);
+        return None;
+    };
+
+    let facing_dir = if player.facing_right { 1.0 } else { -1.0 };
+    cooldown.start(definition.recovery_secs);
+    commands.entity(entity).insert((
+        Attacking::new(name, definition.recovery_secs, facing_dir),
+        AnimationOverride::new(&definition.animation, definition.recovery_secs),
+    ));
+
+    Some(definition)
+}
+
+/// Start the animation of newly started attacks
+///
+/// Registers the attack's hitbox frame events on the clip. Entities without
+/// the clip get all hitboxes immediately, active until the attack ends.
+#[allow(clippy::type_complexity)]
+pub fn attack_start_system(
+    mut commands: Commands,
+    attacks: Res<AttacksConfig>,
+    fx_assets: Res<FxAssets>,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &mut Attacking,
+        Option<&Faction>,
+        Option<&mut AnimationController>,
+        Option<&mut AnimationState>,
+        Option<&mut AnimationEvents>,
+    )>,
+    hitbox_query: Query<(Entity, &AttackHitbox)>,
+) {
+    for (entity, transform, mut attacking, faction, controller, state, events) in &mut query {
+        if attacking.started {
+            continue;
+        }
+        attacking.started = true;
+
+        // A new attack replaces any hitboxes left from the previous one
+        for (hitbox_entity, hitbox) in hitbox_query.iter() {
+            if hitbox.owner == entity {
+                commands.entity(hitbox_entity).despawn();
+            }
+        }
+
+        let Some(definition) = attacks.get(&attacking.attack) else {
+            continue;
+        };
+
+        match (controller, state) {
+            (Some(mut controller), Some(mut state))
+                if controller.animations.contains_key(&definition.animation) =>
+            {
+                controller.restart(&definition.animation, &mut state);
+
+                let frame_count = controller
+                    .current_clip()
+                    .map_or(1, |clip| clip.frame_count());
+                let frame_events = definition.frame_events(frame_count);
+                match events {
+                    Some(mut events) => events.set_clip_events(&definition.animation, frame_events),
+                    None => {
+                        let mut events = AnimationEvents::new();
+                        events.set_clip_events(&definition.animation, frame_events);
+                        commands.entity(entity).insert(events);
+                    }
+                }
+            }
+            _ => {
+                // No clip to drive the hitboxes
+                for index in 0..definition.hitboxes.len() {
+                    attacking.spawned_hitboxes.insert(index);
+                    spawn_attack_hitbox(
+                        &mut commands,
+                        &fx_assets,
+                        entity,
+                        transform,
+                        &attacking,
+                        definition,
+                        index,
+                        faction.copied().unwrap_or_default(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Spawn and despawn attack hitboxes from animation frame events
+#[allow(clippy::type_complexity)]
+pub fn attack_frame_event_system(
+    mut commands: Commands,
+    mut frame_events: MessageReader<AnimationEventTriggered>,
+    attacks: Res<AttacksConfig>,
+    fx_assets: Res<FxAssets>,
+    mut attacker_query: Query<(&Transform, &mut Attacking, Option<&Faction>)>,
+    hitbox_query: Query<(Entity, &AttackHitbox)>,
+) {
+    for triggered in frame_events.read() {
+        let (AnimationEvent::HitboxStart(index) | AnimationEvent::HitboxEnd(index)) =
+            triggered.event
+        else {
+            continue;
+        };
+        let Ok((transform, mut attacking, faction)) = attacker_query.get_mut(triggered.entity)
+        else {
+            continue;
+        };
+        let Some(definition) = attacks.get(&attacking.attack) else {
+            continue;
+        };
+
+        // Ignore events left over from a previous attack's clip
+        if definition.animation != triggered.animation || index >= definition.hitboxes.len() {
+            continue;
+        }
+
+        match triggered.event {
+            AnimationEvent::HitboxStart(_) => {
+                if attacking.spawned_hitboxes.insert(index) {
+                    spawn_attack_hitbox(
+                        &mut commands,
+                        &fx_assets,
+                        triggered.entity,
+                        transform,
+                        &attacking,
+                        definition,
+                        index,
+                        faction.copied().unwrap_or_default(),
+                    );
+                }
+            }
+            _ => {
+                for (hitbox_entity, hitbox) in hitbox_query.iter() {
+                    if hitbox.owner == triggered.entity && hitbox.index == index {
+                        commands.entity(hitbox_entity).despawn();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Spawn one hitbox of an attack relative to its owner
+///
+/// The first hitbox also spawns the attack's effect.
+#[allow(clippy::too_many_arguments)]
+fn spawn_attack_hitbox(
+    commands: &mut Commands,
+    fx_assets: &FxAssets,
+    owner: Entity,
+    owner_transform: &Transform,
+    attacking: &Attacking,
+    definition: &AttackDefinition,
+    index: usize,
+    faction: Faction,
+) {
+    let hitbox = &definition.hitboxes[index];
+    let offset = definition.hitbox_offset(index, attacking.facing_dir);
+    let position = owner_transform.translation + offset.extend(0.0);
+
+    let mut attack = commands.spawn((
+        Transform::from_translation(position),
+        Visibility::default(),
+        Attack::new(
+            definition.damage,
+            definition.knockback_vector(attacking.facing_dir),
+            definition.impact,
+        )
+        .with_damage_type(definition.damage_type)
+        .with_status(definition.status)
+        .with_critical(definition.critical)
+        .with_owner(owner),
+        hitbox.shape,
+        Collider::new(hitbox.shape.size()),
+        AttackHitbox {
+            owner,
+            attack: attacking.attack.clone(),
+            index,
+        },
+    ));
+
+    attack.insert((faction, Name::new(format!("{:?}Attack", faction))));
+
+    if let Some(bounce_force) = definition.bounce_force {
+        attack.insert(AerialAttack {
+            owner,
+            bounce_force,
+        });
+    }
+
+    if index == 0
+        && let Some(effect) = &definition.effect
     {
-        cooldown.reset();
-
-        // Mark player as attacking
-        commands.entity(player_entity).insert(Attacking);
-
-        // Determine attack direction based on facing
-        let facing_dir = if player.facing_right { 1.0 } else { -1.0 };
-
-        // Spawn attack hitbox in front of player
-        let attack_offset = Vec3::new(40.0 * facing_dir, 0.0, 0.0);
-        let attack_pos = transform.translation + attack_offset;
-
-        commands.spawn((
-            Transform::from_translation(attack_pos),
-            Visibility::default(),
-            Attack::new(10.0, 150.0 * facing_dir, 0.1),
-            AttackType::Basic,
-            Collider::new(Vec2::new(40.0, 32.0)),
-            PlayerAttack,
-            Name::new("PlayerAttack"),
-        ));
+        spawn_fx(
+            commands,
+            fx_assets,
+            effect,
+            position,
+            attacking.facing_dir < 0.0,
+        );
+    }
+}
+
+/// Advance attack charge timers
+pub fn update_attack_charge(time: Res<Time>, mut query: Query<&mut ChargingAttack>) {
+    for mut charging in query.iter_mut() {
+        charging.elapsed += time.delta_secs();
+    }
+}
+
+/// Sprite color of an entity before its charge tint (restored when charging ends)
+#[derive(Component, Debug)]
+pub struct ChargeTint {
+    /// Color to restore
+    pub base_color: Color,
+}
+
+/// Tint the sprite of a charging entity to indicate its charge level
+///
+/// Damage flash and status effect tints take priority over the charge tint.
+/// Once charging ends the pre-charge color is restored (into the flash or
+/// status effects if one started meanwhile).
+#[allow(clippy::type_complexity)]
+pub fn charge_indicator_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut charging_query: Query<
+        (Entity, &ChargingAttack, &mut Sprite, Option<&ChargeTint>),
+        (Without<DamageFlash>, Without<StatusEffects>),
+    >,
+    mut released_query: Query<
+        (
+            Entity,
+            &ChargeTint,
+            &mut Sprite,
+            Option<&mut DamageFlash>,
+            Option<&mut StatusEffects>,
+        ),
+        Without<ChargingAttack>,
+    >,
+) {
+    for (entity, charging, mut sprite, charge_tint) in charging_query.iter_mut() {
+        // Not charged yet: leave the sprite alone
+        let Some(&tint) = charging
+            .level()
+            .checked_sub(1)
+            .and_then(|i| CHARGE_TINTS.get(i))
+        else {
+            continue;
+        };
+
+        let base_color = match charge_tint {
+            Some(charge_tint) => charge_tint.base_color,
+            None => {
+                commands.entity(entity).insert(ChargeTint {
+                    base_color: sprite.color,
+                });
+                sprite.color
+            }
+        };
+
+        // Pulse between the base color and the level tint
+        let pulse = 0.5 + 0.5 * (time.elapsed_secs() * 12.0).sin();
+        let alpha = sprite.color.alpha();
+        sprite.color = base_color.mix(&tint, pulse).with_alpha(alpha);
+    }
+
+    // Restore the pre-charge color when charging ends
+    for (entity, charge_tint, mut sprite, flash, effects) in released_query.iter_mut() {
+        let base_color = charge_tint.base_color;
+        if let Some(mut effects) = effects {
+            effects.base_color = Some(base_color);
+        }
+        match flash {
+            Some(mut flash) => flash.original_color = base_color,
+            None => {
+                let alpha = sprite.color.alpha();
+                sprite.color = base_color.with_alpha(alpha);
+            }
+        }
+        commands.entity(entity).remove::<ChargeTint>();
+    }
+}
+
+/// Remove the attacking state once it finishes
+pub fn update_attacking(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Attacking)>,
+) {
+    for (entity, mut attacking) in query.iter_mut() {
+        attacking.timer.tick(time.delta());
+
+        if attacking.timer.is_finished() {
+            commands.entity(entity).remove::<Attacking>();
+        }
     }
 }
 
@@ -155,89 +712,120 @@ fn check_attack_collision(
     a_min.x < b_max.x && a_max.x > b_min.x && a_min.y < b_max.y && a_max.y > b_min.y
 }
 
-/// Detect collisions between attacks and targets
+/// Check collision between an attack (using its hitbox shape, if any) and a target
+#[inline]
+fn check_hitbox_collision(
+    attack_pos: Vec2,
+    attack_collider: &Collider,
+    shape: Option<&HitboxShape>,
+    target_pos: Vec2,
+    target_collider: &Collider,
+) -> bool {
+    match shape {
+        Some(shape) => shape.overlaps(
+            attack_pos + attack_collider.offset,
+            target_pos,
+            target_collider,
+        ),
+        None => check_attack_collision(attack_pos, attack_collider, target_pos, target_collider),
+    }
+}
+
+/// Detect collisions between attacks and hostile hurtboxes
+///
+/// Hostility comes from the attack's and the target's `Faction` (untagged
+/// entities are `Neutral`). Hitboxes never hit their owner. Targets near the
+/// hitbox are found through the `SpatialIndex`.
 #[allow(clippy::type_complexity)]
 pub fn attack_collision_system(
+    factions: Res<FactionsConfig>,
+    index: Res<SpatialIndex>,
     mut attack_query: Query<(
         Entity,
         &Transform,
         &Collider,
         &mut Attack,
-        Option<&PlayerAttack>,
+        Option<&Faction>,
+        Option<&HitboxShape>,
     )>,
-    player_query: Query<(Entity, &Transform, &Collider), (With<Player>, With<PlayerHealth>)>,
-    enemy_query: Query<(Entity, &Transform, &Collider), With<EnemyHealth>>,
+    target_query: Query<(&Transform, &Collider, Option<&Faction>), With<Hurtbox>>,
     mut damage_events: MessageWriter<DamageEvent>,
 ) {
-    for (attack_entity, attack_transform, attack_collider, mut attack, player_attack) in
+    let mut rng = rand::rng();
+
+    for (attack_entity, attack_transform, attack_collider, mut attack, faction, shape) in
         attack_query.iter_mut()
     {
         let attack_pos = attack_transform.translation.truncate();
-
-        if player_attack.is_some() {
-            // Player attack -> check against enemies
-            for (enemy_entity, enemy_transform, enemy_collider) in enemy_query.iter() {
-                // Skip if already hit
-                if attack.hit_entities.contains(&enemy_entity) {
-                    continue;
-                }
-
-                let enemy_pos = enemy_transform.translation.truncate();
-                if check_attack_collision(attack_pos, attack_collider, enemy_pos, enemy_collider) {
-                    // Mark as hit to prevent multi-hit
-                    attack.hit_entities.insert(enemy_entity);
-
-                    // Fire damage event
-                    damage_events.write(DamageEvent {
-                        target: enemy_entity,
-                        damage: attack.damage,
-                        knockback: Vec2::new(attack.knockback, 50.0),
-                        attacker: Some(attack_entity),
-                    });
-
-                    info!(
-                        "Player attack hit enemy! Damage: {}, Knockback: {}",
-                        attack.damage, attack.knockback
-                    );
-                }
+        let faction = faction.copied().unwrap_or_default();
+
+        for target_entity in index.bodies.query(attack_collider.aabb(attack_pos)) {
+            let Ok((target_transform, target_collider, target_faction)) =
+                target_query.get(target_entity)
+            else {
+                continue;
+            };
+
+            // Skip the owner and targets already hit (prevents multi-hit)
+            if attack.owner == Some(target_entity)
+                || attack.hit_entities.contains(&target_entity)
+                || !factions.is_hostile(faction, target_faction.copied().unwrap_or_default())
+            {
+                continue;
             }
-        } else {
-            // Enemy attack -> check against player
-            for (player_entity, player_transform, player_collider) in player_query.iter() {
-                // Skip if already hit
-                if attack.hit_entities.contains(&player_entity) {
-                    continue;
-                }
 
-                let player_pos = player_transform.translation.truncate();
-                if check_attack_collision(attack_pos, attack_collider, player_pos, player_collider)
-                {
-                    // Mark as hit
-                    attack.hit_entities.insert(player_entity);
-
-                    // Fire damage event
-                    damage_events.write(DamageEvent {
-                        target: player_entity,
-                        damage: attack.damage,
-                        knockback: Vec2::new(attack.knockback, 50.0),
-                        attacker: Some(attack_entity),
-                    });
-                }
+            let target_pos = target_transform.translation.truncate();
+            if check_hitbox_collision(
+                attack_pos,
+                attack_collider,
+                shape,
+                target_pos,
+                target_collider,
+            ) {
+                // Mark as hit to prevent multi-hit
+                attack.hit_entities.insert(target_entity);
+
+                // Fire damage event
+                let (damage, critical) = attack.roll_damage(&mut rng);
+                damage_events.write(DamageEvent {
+                    target: target_entity,
+                    damage,
+                    damage_type: attack.damage_type,
+                    knockback: attack.knockback,
+                    attacker: Some(attack_entity),
+                    impact: attack.impact,
+                    status: attack.status,
+                    critical,
+                });
+
+                info!(
+                    "{:?} attack hit {:?}! Damage: {}{}, Knockback: {}",
+                    faction,
+                    target_entity,
+                    damage,
+                    if critical { " (critical)" } else { "" },
+                    attack.knockback
+                );
             }
         }
     }
 }
 
-/// Despawn attacks when their lifetime expires
+/// Despawn hitboxes whose owner's attack has ended
+///
+/// Covers hitboxes still active when the attack is interrupted or its clip
+/// has no frame event to end them.
 pub fn attack_lifetime_system(
-    time: Res<Time>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Attack)>,
+    hitbox_query: Query<(Entity, &AttackHitbox)>,
+    owner_query: Query<&Attacking>,
 ) {
-    for (entity, mut attack) in query.iter_mut() {
-        attack.lifetime.tick(time.delta());
+    for (entity, hitbox) in hitbox_query.iter() {
+        let attack_active = owner_query
+            .get(hitbox.owner)
+            .is_ok_and(|attacking| attacking.attack == hitbox.attack);
 
-        if attack.lifetime.is_finished() {
+        if !attack_active {
             commands.entity(entity).despawn();
         }
     }
@@ -247,12 +835,89 @@ pub fn attack_lifetime_system(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hitbox_on_last_frame_ends_with_clip() {
+        use std::time::Duration;
+        use tropical_fox_animation::AnimationClip;
+        use tropical_fox_animation::systems::{process_animation_events, update_animations};
+
+        let mut app = App::new();
+        app.add_plugins(bevy::time::TimePlugin);
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_secs_f32(0.15),
+        ));
+        app.add_message::<AnimationEventTriggered>();
+        app.init_resource::<FxAssets>();
+        // combo_2 from attacks.ron: one hitbox on frame 0 of the one-frame attack_2 clip
+        app.insert_resource(
+            ron::from_str::<AttacksConfig>(
+                r#"(attacks: {
+                    "combo_2": (
+                        animation: "attack_2",
+                        hitboxes: [(shape: Rect(width: 44.0, height: 48.0), frames: (0, 0))],
+                        damage: 12.0,
+                        knockback: (180.0, 60.0),
+                        recovery_secs: 5.0,
+                    ),
+                })"#,
+            )
+            .unwrap(),
+        );
+        app.add_systems(
+            Update,
+            (
+                update_animations,
+                attack_start_system,
+                process_animation_events,
+                attack_frame_event_system,
+            )
+                .chain(),
+        );
+
+        let mut controller = AnimationController::new();
+        controller.add_animation("attack_2", AnimationClip::new(42, 42, 10.0).unwrap());
+        let mut state = AnimationState::default();
+        controller.play("attack_2", &mut state);
+        app.world_mut().spawn((
+            Transform::default(),
+            controller,
+            state,
+            Sprite::default(),
+            Attacking::new("combo_2", 5.0, 1.0),
+        ));
+
+        let mut hitboxes = app.world_mut().query::<&AttackHitbox>();
+        app.update();
+        assert_eq!(hitboxes.iter(app.world()).count(), 1);
+
+        // The clip finishes its only frame
+        app.update();
+        assert_eq!(hitboxes.iter(app.world()).count(), 0);
+    }
+
     #[test]
     fn test_attack_creation() {
-        let attack = Attack::new(10.0, 100.0, 0.5);
+        let attack = Attack::new(10.0, Vec2::new(100.0, 50.0), None);
         assert_eq!(attack.damage, 10.0);
-        assert_eq!(attack.knockback, 100.0);
+        assert_eq!(attack.knockback, Vec2::new(100.0, 50.0));
         assert!(attack.hit_entities.is_empty());
+        assert_eq!(attack.owner, None);
+
+        let owner = Entity::from_raw_u32(7).unwrap();
+        assert_eq!(attack.with_owner(owner).owner, Some(owner));
+    }
+
+    #[test]
+    fn test_critical_damage_roll() {
+        let mut rng = rand::rng();
+        let attack = Attack::new(10.0, Vec2::ZERO, None);
+        assert_eq!(attack.roll_damage(&mut rng), (10.0, false));
+
+        let attack = attack.with_critical(Some(CriticalHit {
+            chance: 1.0,
+            multiplier: 2.0,
+        }));
+        assert_eq!(attack.roll_damage(&mut rng), (20.0, true));
     }
 
     #[test]
@@ -261,6 +926,60 @@ mod tests {
         assert!(cooldown.can_attack()); // Should be ready for first attack
     }
 
+    #[test]
+    fn test_attack_cooldown_start() {
+        let mut cooldown = AttackCooldown::new(0.3);
+        cooldown.start(0.25);
+        assert!(!cooldown.can_attack());
+        assert_eq!(cooldown.timer.duration().as_secs_f32(), 0.25);
+    }
+
+    #[test]
+    fn test_charge_levels() {
+        let mut charging = ChargingAttack::default();
+        assert_eq!(charging.level(), 0);
+        assert_eq!(charging.release_attack(), BASIC_ATTACK);
+
+        charging.elapsed = CHARGE_LEVEL_TIMES[0];
+        assert_eq!(charging.level(), 1);
+        assert_eq!(charging.release_attack(), CHARGED_ATTACKS[0]);
+
+        charging.elapsed = 10.0;
+        assert_eq!(charging.level(), CHARGE_LEVEL_TIMES.len());
+        assert_eq!(
+            charging.release_attack(),
+            CHARGED_ATTACKS[CHARGED_ATTACKS.len() - 1]
+        );
+    }
+
+    #[test]
+    fn test_charge_tint_restores_base_color() {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.add_systems(Update, charge_indicator_system);
+
+        let base_color = Color::srgb(0.5, 0.8, 1.0);
+        let entity = app
+            .world_mut()
+            .spawn((
+                Sprite::from_color(base_color, Vec2::ONE),
+                ChargingAttack { elapsed: 1.0 },
+            ))
+            .id();
+        app.update();
+        assert_eq!(
+            app.world().get::<ChargeTint>(entity).unwrap().base_color,
+            base_color
+        );
+
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<ChargingAttack>();
+        app.update();
+        assert_eq!(app.world().get::<Sprite>(entity).unwrap().color, base_color);
+        assert!(app.world().get::<ChargeTint>(entity).is_none());
+    }
+
     #[test]
     fn test_attack_collision_detection() {
         let attack_collider = Collider::new(Vec2::new(32.0, 32.0));
@@ -0,0 +1,327 @@
+//! Data-driven attack definitions
+//!
+//! Each attack describes its hitboxes, the animation frames they are active
+//! on, damage, knockback and hit feedback. Hitboxes are spawned and despawned
+//! by animation frame events. Attacks are defined in
+//! `assets/config/attacks.ron` and hot-reloaded in debug builds.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+use tropical_fox_animation::AnimationEvent;
+use tropical_fox_common::Collider;
+
+use super::{DamageType, StatusEffect};
+
+/// Shape of an attack hitbox
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Component)]
+pub enum HitboxShape {
+    /// Axis-aligned rectangle
+    Rect { width: f32, height: f32 },
+    /// Circle
+    Circle { radius: f32 },
+}
+
+impl HitboxShape {
+    /// Size of the shape's bounding box
+    pub fn size(&self) -> Vec2 {
+        match *self {
+            HitboxShape::Rect { width, height } => Vec2::new(width, height),
+            HitboxShape::Circle { radius } => Vec2::splat(radius * 2.0),
+        }
+    }
+
+    /// Check overlap between this shape at `pos` and a target collider
+    pub fn overlaps(&self, pos: Vec2, target_pos: Vec2, target_collider: &Collider) -> bool {
+        let b_min = target_pos + target_collider.offset - target_collider.size / 2.0;
+        let b_max = target_pos + target_collider.offset + target_collider.size / 2.0;
+
+        match *self {
+            HitboxShape::Rect { .. } => {
+                let half = self.size() / 2.0;
+                let a_min = pos - half;
+                let a_max = pos + half;
+                a_min.x < b_max.x && a_max.x > b_min.x && a_min.y < b_max.y && a_max.y > b_min.y
+            }
+            HitboxShape::Circle { radius } => {
+                // Distance from the circle center to the closest point of the box
+                let closest = pos.clamp(b_min, b_max);
+                pos.distance_squared(closest) < radius * radius
+            }
+        }
+    }
+}
+
+/// A hitbox of an attack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HitboxConfig {
+    /// Hitbox shape
+    pub shape: HitboxShape,
+    /// Offset from the attacker (x is mirrored by facing direction)
+    #[serde(default)]
+    pub offset: (f32, f32),
+    /// First and last active frame, inclusive (relative to the animation clip)
+    pub frames: (usize, usize),
+}
+
+/// Hit feedback applied when an attack connects
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitImpact {
+    /// Hit stop duration (seconds)
+    #[serde(default = "default_hitstop_secs")]
+    pub hitstop_secs: f32,
+    /// Screen shake intensity (max pixel offset)
+    #[serde(default = "default_shake_intensity")]
+    pub shake_intensity: f32,
+    /// Screen shake duration (seconds)
+    #[serde(default = "default_shake_duration_secs")]
+    pub shake_duration_secs: f32,
+}
+
+fn default_hitstop_secs() -> f32 {
+    0.05
+}
+
+fn default_shake_intensity() -> f32 {
+    4.0
+}
+
+fn default_shake_duration_secs() -> f32 {
+    0.1
+}
+
+impl Default for HitImpact {
+    fn default() -> Self {
+        Self {
+            hitstop_secs: default_hitstop_secs(),
+            shake_intensity: default_shake_intensity(),
+            shake_duration_secs: default_shake_duration_secs(),
+        }
+    }
+}
+
+/// A single attack definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackDefinition {
+    /// Animation clip played during the attack (hitbox frames are relative to it)
+    pub animation: String,
+    /// Hitboxes and the frames they are active on
+    pub hitboxes: Vec<HitboxConfig>,
+    /// Damage dealt on hit
+    pub damage: f32,
+    /// Elemental type of the damage
+    #[serde(default)]
+    pub damage_type: DamageType,
+    /// Knockback applied on hit (x is mirrored by facing direction)
+    pub knockback: (f32, f32),
+    /// Hit stop and screen shake on hit (None uses `assets/config/combat.ron`)
+    #[serde(default)]
+    pub impact: Option<HitImpact>,
+    /// Time before the next attack can start (seconds)
+    pub recovery_secs: f32,
+    /// Upward velocity applied to the attacker when the attack connects
+    #[serde(default)]
+    pub bounce_force: Option<f32>,
+    /// Effect spawned at the first hitbox (name from `assets/config/effects.ron`)
+    #[serde(default)]
+    pub effect: Option<String>,
+    /// Status effect applied on hit
+    #[serde(default)]
+    pub status: Option<StatusEffect>,
+    /// Chance of a critical hit and its damage multiplier
+    #[serde(default)]
+    pub critical: Option<CriticalHit>,
+}
+
+/// Critical hit chance of an attack
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CriticalHit {
+    /// Probability of a hit being critical (0.0 - 1.0)
+    pub chance: f32,
+    /// Damage multiplier of a critical hit
+    #[serde(default = "default_critical_multiplier")]
+    pub multiplier: f32,
+}
+
+fn default_critical_multiplier() -> f32 {
+    1.5
+}
+
+impl AttackDefinition {
+    /// Knockback vector for an attacker facing `facing_dir` (1.0 = right)
+    pub fn knockback_vector(&self, facing_dir: f32) -> Vec2 {
+        Vec2::new(self.knockback.0 * facing_dir, self.knockback.1)
+    }
+
+    /// Hitbox offset for an attacker facing `facing_dir` (1.0 = right)
+    pub fn hitbox_offset(&self, index: usize, facing_dir: f32) -> Vec2 {
+        self.hitboxes.get(index).map_or(Vec2::ZERO, |hitbox| {
+            Vec2::new(hitbox.offset.0 * facing_dir, hitbox.offset.1)
+        })
+    }
+
+    /// Animation frame events (relative to the clip) that start and end each hitbox
+    ///
+    /// Hitboxes lasting to the clip's last frame (of `frame_count`) end when the
+    /// clip finishes.
+    pub fn frame_events(&self, frame_count: usize) -> HashMap<usize, Vec<AnimationEvent>> {
+        let last_frame = frame_count.saturating_sub(1);
+        let mut events: HashMap<usize, Vec<AnimationEvent>> = HashMap::new();
+        for (index, hitbox) in self.hitboxes.iter().enumerate() {
+            let (start, end) = hitbox.frames;
+            events
+                .entry(start)
+                .or_default()
+                .push(AnimationEvent::HitboxStart(index));
+            events
+                .entry(end.min(last_frame).max(start) + 1)
+                .or_default()
+                .push(AnimationEvent::HitboxEnd(index));
+        }
+        events
+    }
+}
+
+/// Attack used when the attack button is tapped without a combo chain
+pub const BASIC_ATTACK: &str = "basic";
+
+/// Aerial down-attack (attack while airborne and holding down)
+pub const AERIAL_ATTACK: &str = "aerial";
+
+/// Charged attacks by charge level (level 1 and up)
+pub const CHARGED_ATTACKS: [&str; 2] = ["charged_1", "charged_2"];
+
+/// Attack definitions
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource, Asset, TypePath)]
+pub struct AttacksConfig {
+    /// Attacks by name
+    pub attacks: HashMap<String, AttackDefinition>,
+}
+
+impl AttacksConfig {
+    /// Get an attack definition by name
+    pub fn get(&self, name: &str) -> Option<&AttackDefinition> {
+        self.attacks.get(name)
+    }
+}
+
+/// Attack configuration loading errors
+#[derive(Debug, Error)]
+pub enum AttackConfigError {
+    #[error("Failed to read attack config file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON attack config: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+}
+
+/// Load attack definitions from a RON file
+pub fn load_attacks_config<P: AsRef<Path>>(path: P) -> Result<AttacksConfig, AttackConfigError> {
+    let content = fs::read_to_string(path)?;
+    let config: AttacksConfig = ron::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load attack definitions from a RON file, or return default if loading/parsing fails
+pub fn load_attacks_config_optional<P: AsRef<Path>>(path: P) -> AttacksConfig {
+    match load_attacks_config(path) {
+        Ok(config) => {
+            info!("Loaded attack config with {} attacks", config.attacks.len());
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load attack config: {}. Using default.", e);
+            AttacksConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attacks_config_parse() {
+        let ron_str = r#"(
+            attacks: {
+                "basic": (
+                    animation: "attack_1",
+                    hitboxes: [
+                        (shape: Rect(width: 40.0, height: 32.0), offset: (40.0, 0.0), frames: (0, 0)),
+                    ],
+                    damage: 10.0,
+                    knockback: (150.0, 50.0),
+                    recovery_secs: 0.3,
+                ),
+            },
+        )"#;
+
+        let config: AttacksConfig = ron::from_str(ron_str).unwrap();
+        let basic = config.get(BASIC_ATTACK).unwrap();
+        assert!(basic.impact.is_none());
+        assert_eq!(basic.damage_type, DamageType::Physical);
+        assert_eq!(basic.knockback_vector(-1.0), Vec2::new(-150.0, 50.0));
+        assert_eq!(basic.hitbox_offset(0, -1.0), Vec2::new(-40.0, 0.0));
+        assert!(basic.bounce_force.is_none());
+    }
+
+    #[test]
+    fn test_frame_events() {
+        let attack = AttackDefinition {
+            animation: "attack_3".to_string(),
+            hitboxes: vec![HitboxConfig {
+                shape: HitboxShape::Circle { radius: 30.0 },
+                offset: (44.0, 0.0),
+                frames: (1, 2),
+            }],
+            damage: 18.0,
+            damage_type: DamageType::Physical,
+            knockback: (300.0, 80.0),
+            impact: None,
+            recovery_secs: 0.45,
+            bounce_force: None,
+            effect: None,
+            status: None,
+            critical: None,
+        };
+
+        let events = attack.frame_events(4);
+        assert!(matches!(
+            events[&1].as_slice(),
+            [AnimationEvent::HitboxStart(0)]
+        ));
+        // The hitbox ends after its last active frame
+        assert!(matches!(
+            events[&3].as_slice(),
+            [AnimationEvent::HitboxEnd(0)]
+        ));
+
+        // Frames past the end of the clip end with the clip
+        let events = attack.frame_events(2);
+        assert!(matches!(
+            events[&2].as_slice(),
+            [AnimationEvent::HitboxEnd(0)]
+        ));
+    }
+
+    #[test]
+    fn test_circle_hitbox_overlap() {
+        let shape = HitboxShape::Circle { radius: 20.0 };
+        let target = Collider::new(Vec2::new(20.0, 20.0));
+
+        // Edge of the box within the radius
+        assert!(shape.overlaps(Vec2::ZERO, Vec2::new(25.0, 0.0), &target));
+        // Box corner outside the radius (a rectangle would overlap here)
+        assert!(!shape.overlaps(Vec2::ZERO, Vec2::new(26.0, 26.0), &target));
+        assert!(
+            HitboxShape::Rect {
+                width: 40.0,
+                height: 40.0
+            }
+            .overlaps(Vec2::ZERO, Vec2::new(26.0, 26.0), &target)
+        );
+    }
+}
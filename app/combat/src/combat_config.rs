@@ -0,0 +1,154 @@
+//! Combat feel configuration
+//!
+//! Hit stop, screen shake, flash, knockback and invincibility timings used by
+//! `damage_system` and `player_death_system`, loaded from
+//! `assets/config/combat.ron`. Attacks override the hit feedback with `impact`
+//! in `attacks.ron`; enemies override the feedback of their own hits with
+//! `impact` and how they react to being hit with `hit_reaction` in `enemies.ron`.
+//! Shield blocking and parrying are tuned in the `shield` section.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+use super::{HitImpact, ShieldConfig};
+
+/// Combat feel defaults
+#[derive(Debug, Clone, Serialize, Deserialize, Resource, Asset, TypePath)]
+pub struct CombatConfig {
+    /// Hit feedback when an enemy is hit (used when the hit has no `impact`)
+    #[serde(default)]
+    pub enemy_hit: HitImpact,
+    /// Hit feedback when a player is hit (used when the hit has no `impact`)
+    #[serde(default = "default_player_hit")]
+    pub player_hit: HitImpact,
+    /// Screen shake multiplier for hits against a weakness
+    #[serde(default = "default_effective_shake_multiplier")]
+    pub effective_shake_multiplier: f32,
+    /// Damage flash duration (seconds)
+    #[serde(default = "default_flash_secs")]
+    pub flash_secs: f32,
+    /// Flash duration for hits the target is immune to (seconds)
+    #[serde(default = "default_immune_flash_secs")]
+    pub immune_flash_secs: f32,
+    /// Knockback duration (seconds)
+    #[serde(default = "default_knockback_secs")]
+    pub knockback_secs: f32,
+    /// Player invincibility after being hit (seconds)
+    #[serde(default = "default_player_iframe_secs")]
+    pub player_iframe_secs: f32,
+    /// Player invincibility after respawning (seconds)
+    #[serde(default = "default_respawn_iframe_secs")]
+    pub respawn_iframe_secs: f32,
+    /// Shield and parry tuning
+    #[serde(default)]
+    pub shield: ShieldConfig,
+}
+
+fn default_player_hit() -> HitImpact {
+    HitImpact {
+        hitstop_secs: 0.08,
+        shake_intensity: 8.0,
+        shake_duration_secs: 0.2,
+    }
+}
+
+fn default_effective_shake_multiplier() -> f32 {
+    1.5
+}
+
+fn default_flash_secs() -> f32 {
+    0.15
+}
+
+fn default_immune_flash_secs() -> f32 {
+    0.1
+}
+
+fn default_knockback_secs() -> f32 {
+    0.2
+}
+
+fn default_player_iframe_secs() -> f32 {
+    1.0
+}
+
+fn default_respawn_iframe_secs() -> f32 {
+    2.0
+}
+
+impl Default for CombatConfig {
+    fn default() -> Self {
+        Self {
+            enemy_hit: HitImpact::default(),
+            player_hit: default_player_hit(),
+            effective_shake_multiplier: default_effective_shake_multiplier(),
+            flash_secs: default_flash_secs(),
+            immune_flash_secs: default_immune_flash_secs(),
+            knockback_secs: default_knockback_secs(),
+            player_iframe_secs: default_player_iframe_secs(),
+            respawn_iframe_secs: default_respawn_iframe_secs(),
+            shield: ShieldConfig::default(),
+        }
+    }
+}
+
+impl CombatConfig {
+    /// Default hit feedback for a target
+    pub fn hit_impact(&self, target_is_player: bool) -> HitImpact {
+        if target_is_player {
+            self.player_hit
+        } else {
+            self.enemy_hit
+        }
+    }
+}
+
+/// Combat config loading errors
+#[derive(Debug, Error)]
+pub enum CombatConfigError {
+    #[error("Failed to read combat config file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON combat config: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+}
+
+/// Load combat feel configuration from a RON file
+pub fn load_combat_config<P: AsRef<Path>>(path: P) -> Result<CombatConfig, CombatConfigError> {
+    let content = fs::read_to_string(path)?;
+    let config: CombatConfig = ron::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load combat feel configuration from a RON file, or return default if loading/parsing fails
+pub fn load_combat_config_optional<P: AsRef<Path>>(path: P) -> CombatConfig {
+    match load_combat_config(path) {
+        Ok(config) => {
+            info!("Loaded combat config");
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load combat config: {}. Using default.", e);
+            CombatConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combat_config_defaults() {
+        let config: CombatConfig = ron::from_str("(knockback_secs: 0.3)").unwrap();
+
+        assert_eq!(config.knockback_secs, 0.3);
+        assert_eq!(config.respawn_iframe_secs, 2.0);
+        assert_eq!(config.hit_impact(false), HitImpact::default());
+        assert_eq!(config.hit_impact(true).shake_intensity, 8.0);
+        assert_eq!(config.shield.parry_window_secs, 0.15);
+    }
+}
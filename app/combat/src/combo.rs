@@ -0,0 +1,219 @@
+//! Melee combo chains
+//!
+//! Consecutive attack presses inside a step's cancel window chain into the
+//! next step of the combo. Presses made during recovery are buffered.
+//! Chains are defined in `assets/config/combos.ron`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Chain used by the player's ground attacks
+pub const DEFAULT_COMBO_CHAIN: &str = "basic";
+
+/// A single step of a combo chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComboStepConfig {
+    /// Attack performed by this step (name from `assets/config/attacks.ron`)
+    pub attack: String,
+    /// Time after recovery during which a press continues the chain (seconds)
+    #[serde(default)]
+    pub cancel_window_secs: f32,
+}
+
+/// Combo chain definitions
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct ComboConfig {
+    /// How long an attack press made during recovery is remembered (seconds)
+    #[serde(default = "default_input_buffer_secs")]
+    pub input_buffer_secs: f32,
+    /// Combo chains by name
+    pub chains: HashMap<String, Vec<ComboStepConfig>>,
+}
+
+fn default_input_buffer_secs() -> f32 {
+    0.2
+}
+
+impl Default for ComboConfig {
+    fn default() -> Self {
+        Self {
+            input_buffer_secs: default_input_buffer_secs(),
+            chains: HashMap::new(),
+        }
+    }
+}
+
+impl ComboConfig {
+    /// Get a step of a combo chain
+    pub fn step(&self, chain: &str, index: usize) -> Option<&ComboStepConfig> {
+        self.chains.get(chain).and_then(|steps| steps.get(index))
+    }
+
+    /// Number of steps in a combo chain
+    pub fn chain_len(&self, chain: &str) -> usize {
+        self.chains.get(chain).map_or(0, |steps| steps.len())
+    }
+}
+
+/// Combo configuration loading errors
+#[derive(Debug, Error)]
+pub enum ComboConfigError {
+    #[error("Failed to read combo config file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON combo config: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+}
+
+/// Load combo configuration from a RON file
+pub fn load_combo_config<P: AsRef<Path>>(path: P) -> Result<ComboConfig, ComboConfigError> {
+    let content = fs::read_to_string(path)?;
+    let config: ComboConfig = ron::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load combo configuration from a RON file, or return default if loading/parsing fails
+pub fn load_combo_config_optional<P: AsRef<Path>>(path: P) -> ComboConfig {
+    match load_combo_config(path) {
+        Ok(config) => {
+            info!("Loaded combo config with {} chains", config.chains.len());
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load combo config: {}. Using default.", e);
+            ComboConfig::default()
+        }
+    }
+}
+
+/// Combo progress for an attacking entity
+#[derive(Component, Debug)]
+pub struct ComboState {
+    /// Name of the combo chain in use
+    pub chain: String,
+    /// Index of the step performed by the next attack
+    pub next_step: usize,
+    /// Chain resets to the first step when this timer finishes
+    pub window: Timer,
+    /// Whether an attack press is buffered
+    pub buffered: bool,
+    /// Buffered press expires when this timer finishes
+    pub buffer: Timer,
+}
+
+impl ComboState {
+    /// Create a combo state using the given chain
+    pub fn new(chain: impl Into<String>) -> Self {
+        Self {
+            chain: chain.into(),
+            next_step: 0,
+            window: Timer::from_seconds(0.0, TimerMode::Once),
+            buffered: false,
+            buffer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+
+    /// Whether the next attack continues an ongoing chain
+    pub fn in_chain(&self) -> bool {
+        self.next_step > 0
+    }
+
+    /// Remember an attack press made during recovery
+    pub fn buffer_input(&mut self, buffer_secs: f32) {
+        self.buffered = true;
+        self.buffer = Timer::from_seconds(buffer_secs, TimerMode::Once);
+    }
+
+    /// Consume the buffered attack press, if any
+    pub fn take_buffered(&mut self) -> bool {
+        std::mem::take(&mut self.buffered)
+    }
+
+    /// Move to the next step (wraps after the finisher)
+    ///
+    /// The chain continues if the next attack starts within `window_secs`.
+    pub fn advance(&mut self, chain_len: usize, window_secs: f32) {
+        self.next_step = (self.next_step + 1) % chain_len.max(1);
+        self.window = Timer::from_seconds(window_secs, TimerMode::Once);
+    }
+
+    /// Return to the first step of the chain
+    pub fn reset(&mut self) {
+        self.next_step = 0;
+        self.buffered = false;
+    }
+}
+
+impl Default for ComboState {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMBO_CHAIN)
+    }
+}
+
+/// Expire combo windows and buffered inputs
+pub fn update_combo_state(time: Res<Time>, mut query: Query<&mut ComboState>) {
+    for mut combo in query.iter_mut() {
+        combo.window.tick(time.delta());
+        if combo.window.is_finished() && combo.in_chain() {
+            combo.next_step = 0;
+        }
+
+        combo.buffer.tick(time.delta());
+        if combo.buffer.is_finished() {
+            combo.buffered = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combo_advance_wraps() {
+        let mut combo = ComboState::default();
+        assert!(!combo.in_chain());
+
+        combo.advance(3, 0.5);
+        combo.advance(3, 0.5);
+        assert_eq!(combo.next_step, 2);
+
+        // Finisher wraps back to the first step
+        combo.advance(3, 0.5);
+        assert_eq!(combo.next_step, 0);
+    }
+
+    #[test]
+    fn test_combo_input_buffer() {
+        let mut combo = ComboState::default();
+        assert!(!combo.take_buffered());
+
+        combo.buffer_input(0.2);
+        assert!(combo.take_buffered());
+        assert!(!combo.take_buffered()); // Consumed
+    }
+
+    #[test]
+    fn test_combo_config_parse() {
+        let ron_str = r#"(
+            chains: {
+                "basic": [
+                    (attack: "combo_1", cancel_window_secs: 0.3),
+                ],
+            },
+        )"#;
+
+        let config: ComboConfig = ron::from_str(ron_str).unwrap();
+        assert_eq!(config.input_buffer_secs, 0.2);
+        assert_eq!(config.chain_len(DEFAULT_COMBO_CHAIN), 1);
+
+        let step = config.step(DEFAULT_COMBO_CHAIN, 0).unwrap();
+        assert_eq!(step.attack, "combo_1");
+        assert_eq!(step.cancel_window_secs, 0.3);
+        assert!(config.step(DEFAULT_COMBO_CHAIN, 1).is_none());
+    }
+}
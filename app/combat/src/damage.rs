@@ -1,13 +1,16 @@
 //! Damage processing system
 //!
-//! Handles damage application, invincibility frames, knockback, and death.
+//! Handles damage application, invincibility frames, knockback, hitstun, and death.
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::{
-    DamageEvent, DeathEvent, Health, HitStopEvent, Lives, PlayerSpawnPoint, ScreenShakeEvent,
+    CombatConfig, DamageDealtEvent, DamageEvent, DamageResistances, DeathEvent, FxAssets, Health,
+    HitEffectiveness, HitStopEvent, Lives, PlayerSpawnPoint, ScreenShakeEvent, SharedLives, Shield,
+    ShieldEvent, ShieldOutcome, StatusEffectEvent, StatusEffects, spawn_fx,
 };
-use tropical_fox_common::{GameState, Player, Velocity};
+use tropical_fox_common::{Collider, GameState, Gravity, Player, Velocity};
 
 /// Invincibility component - entity is immune to damage while this is active
 #[derive(Component, Debug)]
@@ -25,6 +28,13 @@ impl Invincibility {
     }
 }
 
+/// Invulnerable component - hits land but deal no damage or knockback
+///
+/// Unlike `Invincibility` it has no timer; hits show the immune flash, as for
+/// a 0.0 resistance. Bosses carry it outside their vulnerability windows.
+#[derive(Component, Debug, Default)]
+pub struct Invulnerable;
+
 /// Damage flash effect - changes sprite color temporarily
 #[derive(Component, Debug)]
 pub struct DamageFlash {
@@ -45,6 +55,12 @@ impl DamageFlash {
             original_color: Color::WHITE,
         }
     }
+
+    /// Set the color to flash to
+    pub fn with_color(mut self, flash_color: Color) -> Self {
+        self.flash_color = flash_color;
+        self
+    }
 }
 
 /// Knockback component - applies force and disables control temporarily
@@ -70,67 +86,311 @@ impl Knockback {
 #[derive(Component, Debug, Default)]
 pub struct InKnockback;
 
+/// How an entity reacts to being hit
+///
+/// Enemies get theirs from `hit_reaction` in `assets/config/enemies.ron`.
+/// Entities without one use `HitReaction::player` (players, i-frames from
+/// `assets/config/combat.ron`) or the default.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitReaction {
+    /// Fraction of knockback ignored (0.0 = full knockback, 1.0 = immovable)
+    #[serde(default)]
+    pub knockback_resistance: f32,
+    /// Seconds the entity's AI is suspended after a hit
+    #[serde(default = "default_hitstun_secs")]
+    pub hitstun_secs: f32,
+    /// Seconds of invincibility after a hit (0.0 = none)
+    #[serde(default)]
+    pub iframe_secs: f32,
+    /// Takes damage without knockback or hitstun
+    #[serde(default)]
+    pub super_armor: bool,
+    /// Knockback duration override (None uses `assets/config/combat.ron`)
+    #[serde(default)]
+    pub knockback_secs: Option<f32>,
+    /// Damage flash duration override (None uses `assets/config/combat.ron`)
+    #[serde(default)]
+    pub flash_secs: Option<f32>,
+}
+
+fn default_hitstun_secs() -> f32 {
+    0.25
+}
+
+impl Default for HitReaction {
+    fn default() -> Self {
+        Self {
+            knockback_resistance: 0.0,
+            hitstun_secs: default_hitstun_secs(),
+            iframe_secs: 0.0,
+            super_armor: false,
+            knockback_secs: None,
+            flash_secs: None,
+        }
+    }
+}
+
+impl HitReaction {
+    /// Reaction of players (input is disabled by `InKnockback` rather than hitstun)
+    pub fn player(iframe_secs: f32) -> Self {
+        Self {
+            hitstun_secs: 0.0,
+            iframe_secs,
+            ..default()
+        }
+    }
+
+    /// Knockback after resistance and super-armor (None if the entity isn't moved)
+    pub fn knockback(&self, force: Vec2) -> Option<Vec2> {
+        if self.super_armor {
+            return None;
+        }
+        let force = force * (1.0 - self.knockback_resistance.clamp(0.0, 1.0));
+        (force != Vec2::ZERO).then_some(force)
+    }
+
+    /// Hitstun duration (None if the entity isn't stunned)
+    pub fn hitstun(&self) -> Option<f32> {
+        (!self.super_armor && self.hitstun_secs > 0.0).then_some(self.hitstun_secs)
+    }
+}
+
+/// Hitstun component - AI is suspended while this is active
+///
+/// Enemy AI systems skip entities with `Hitstun`, so knockback isn't
+/// overwritten by their movement. Horizontal movement stops once the
+/// knockback ends.
+#[derive(Component, Debug)]
+pub struct Hitstun {
+    /// Timer counting down the hitstun
+    pub timer: Timer,
+}
+
+impl Hitstun {
+    /// Create a new hitstun with specified duration
+    pub fn new(duration_secs: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration_secs, TimerMode::Once),
+        }
+    }
+}
+
+/// Point on the target's collider closest to the attacker
+///
+/// Falls back to the target's position when the attacker or collider is unknown.
+pub fn contact_point(
+    target_pos: Vec2,
+    collider: Option<&Collider>,
+    attacker_pos: Option<Vec2>,
+) -> Vec2 {
+    match (collider, attacker_pos) {
+        (Some(collider), Some(attacker_pos)) => {
+            let center = target_pos + collider.offset;
+            let half = collider.size / 2.0;
+            attacker_pos.clamp(center - half, center + half)
+        }
+        _ => target_pos,
+    }
+}
+
 /// Process damage events
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn damage_system(
     mut commands: Commands,
+    config: Res<CombatConfig>,
+    fx_assets: Res<FxAssets>,
     mut damage_events: MessageReader<DamageEvent>,
-    mut health_query: Query<(&mut Health, Option<&Invincibility>, Option<&Sprite>)>,
+    mut health_query: Query<(
+        &mut Health,
+        &Transform,
+        Option<&Invincibility>,
+        Option<&Sprite>,
+        Option<&DamageFlash>,
+        Option<&DamageResistances>,
+        Option<&Collider>,
+        Option<&HitReaction>,
+        Option<&Shield>,
+        Has<Invulnerable>,
+    )>,
+    attacker_query: Query<&Transform>,
     player_query: Query<Entity, With<Player>>,
     mut death_events: MessageWriter<DeathEvent>,
     mut hitstop_events: MessageWriter<HitStopEvent>,
     mut shake_events: MessageWriter<ScreenShakeEvent>,
+    mut status_events: MessageWriter<StatusEffectEvent>,
+    mut dealt_events: MessageWriter<DamageDealtEvent>,
+    mut shield_events: MessageWriter<ShieldEvent>,
 ) {
     for event in damage_events.read() {
-        let Ok((mut health, invincibility, sprite)) = health_query.get_mut(event.target) else {
+        let Ok((
+            mut health,
+            transform,
+            invincibility,
+            sprite,
+            flash,
+            resistances,
+            collider,
+            reaction,
+            shield,
+            invulnerable,
+        )) = health_query.get_mut(event.target)
+        else {
             continue;
         };
+        let is_player = player_query.contains(event.target);
+        let target_pos = transform.translation.truncate();
+        let attacker_pos = event
+            .attacker
+            .and_then(|attacker| attacker_query.get(attacker).ok())
+            .map(|attacker| attacker.translation.truncate());
+
+        // Shields are resolved first so a parry works during invincibility
+        let shield_outcome = shield.and_then(|shield| shield.outcome(target_pos, attacker_pos));
+        if let Some(outcome) = shield_outcome {
+            shield_events.write(ShieldEvent {
+                defender: event.target,
+                attacker: event.attacker,
+                outcome,
+            });
+            if outcome == ShieldOutcome::Parried {
+                continue;
+            }
+        }
+        let (block_damage, block_knockback) = if shield_outcome == Some(ShieldOutcome::Blocked) {
+            (
+                1.0 - config.shield.damage_reduction,
+                1.0 - config.shield.knockback_reduction,
+            )
+        } else {
+            (1.0, 1.0)
+        };
 
         // Skip damage if invincible
         if invincibility.is_some() {
             continue;
         }
 
-        // Apply damage
-        let is_dead = health.take_damage(event.damage);
+        // Apply resistances and weaknesses
+        let multiplier = if invulnerable {
+            0.0
+        } else {
+            resistances.map_or(1.0, |r| r.multiplier(event.damage_type))
+        };
+        let effectiveness = HitEffectiveness::from_multiplier(multiplier);
+        // A flash in progress (e.g. an attack telegraph) already knows the color to restore
+        let original_color = flash.map_or_else(
+            || sprite.map_or(Color::WHITE, |sprite| sprite.color),
+            |flash| flash.original_color,
+        );
 
-        // Check if target is player for stronger effects
-        let is_player = player_query.contains(event.target);
+        if effectiveness == HitEffectiveness::Immune {
+            // No damage or knockback, just a short flash to show the hit landed
+            let mut immune_flash =
+                DamageFlash::new(config.immune_flash_secs).with_color(effectiveness.flash_color());
+            immune_flash.original_color = original_color;
+            commands.entity(event.target).insert(immune_flash);
+
+            debug!(
+                "Entity {:?} is immune to {:?} damage",
+                event.target, event.damage_type
+            );
+            continue;
+        }
+
+        // Apply damage (reduced by a raised shield)
+        let damage = event.damage * multiplier * block_damage;
+        let is_dead = health.take_damage(damage);
+
+        // Hit feedback from the attack, or the combat config (stronger for player)
+        let mut impact = event.impact.unwrap_or_else(|| config.hit_impact(is_player));
+
+        // Effective hits hit harder and show the damage type's effect
+        if effectiveness == HitEffectiveness::Effective {
+            impact.shake_intensity *= config.effective_shake_multiplier;
+            if let Some(effect) = event.damage_type.hit_effect() {
+                spawn_fx(
+                    &mut commands,
+                    &fx_assets,
+                    effect,
+                    transform.translation + Vec3::Z,
+                    false,
+                );
+            }
+        }
 
         // Trigger hit stop effect
         hitstop_events.write(HitStopEvent {
-            duration_secs: if is_player { 0.08 } else { 0.05 },
+            duration_secs: impact.hitstop_secs,
         });
 
-        // Trigger screen shake (stronger for player)
+        // Trigger screen shake
         shake_events.write(ScreenShakeEvent {
-            intensity: if is_player { 8.0 } else { 4.0 },
-            duration_secs: if is_player { 0.2 } else { 0.1 },
+            intensity: impact.shake_intensity,
+            duration_secs: impact.shake_duration_secs,
         });
 
-        // Apply invincibility frames (player only)
-        if is_player {
+        let reaction = reaction.copied().unwrap_or_else(|| {
+            if is_player {
+                HitReaction::player(config.player_iframe_secs)
+            } else {
+                HitReaction::default()
+            }
+        });
+
+        // Apply invincibility frames
+        if reaction.iframe_secs > 0.0 {
             commands
                 .entity(event.target)
-                .insert(Invincibility::new(1.0)); // 1 second of invincibility
+                .insert(Invincibility::new(reaction.iframe_secs));
         }
 
-        // Apply damage flash effect
-        let mut damage_flash = DamageFlash::new(0.15);
-        if let Some(sprite) = sprite {
-            damage_flash.original_color = sprite.color;
-        }
+        // Apply damage flash effect (colored by effectiveness)
+        let flash_secs = reaction.flash_secs.unwrap_or(config.flash_secs);
+        let mut damage_flash = DamageFlash::new(flash_secs).with_color(effectiveness.flash_color());
+        damage_flash.original_color = original_color;
         commands.entity(event.target).insert(damage_flash);
 
-        // Apply knockback
-        commands
-            .entity(event.target)
-            .insert((Knockback::new(event.knockback, 0.2), InKnockback));
+        // Apply knockback (reduced by weight, none with super-armor)
+        if let Some(knockback) = reaction.knockback(event.knockback * block_knockback) {
+            let knockback_secs = reaction.knockback_secs.unwrap_or(config.knockback_secs);
+            commands
+                .entity(event.target)
+                .insert((Knockback::new(knockback, knockback_secs), InKnockback));
+        }
+
+        // Suspend AI while hitstunned
+        if let Some(hitstun_secs) = reaction.hitstun() {
+            commands
+                .entity(event.target)
+                .insert(Hitstun::new(hitstun_secs));
+        }
 
         info!(
-            "Entity {:?} took {} damage, health: {}/{}",
-            event.target, event.damage, health.current, health.max
+            "Entity {:?} took {} {:?} damage ({:?}), health: {}/{}",
+            event.target, damage, event.damage_type, effectiveness, health.current, health.max
         );
 
+        // Damage numbers and hit sparks
+        dealt_events.write(DamageDealtEvent {
+            target: event.target,
+            damage,
+            damage_type: event.damage_type,
+            effectiveness,
+            critical: event.critical,
+            target_is_player: is_player,
+            contact_point: contact_point(target_pos, collider, attacker_pos),
+        });
+
+        // Apply the hit's status effect
+        if let Some(effect) = event.status
+            && !is_dead
+        {
+            status_events.write(StatusEffectEvent {
+                target: event.target,
+                effect,
+            });
+        }
+
         // Fire death event if health depleted
         if is_dead {
             death_events.write(DeathEvent {
@@ -222,6 +482,33 @@ pub fn knockback_system(
     }
 }
 
+/// Update hitstun timers and hold hitstunned entities in place after knockback
+#[allow(clippy::type_complexity)]
+pub fn hitstun_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Hitstun,
+        &mut Velocity,
+        Has<Knockback>,
+        Has<Gravity>,
+    )>,
+) {
+    for (entity, mut hitstun, mut velocity, in_knockback, has_gravity) in query.iter_mut() {
+        hitstun.timer.tick(time.delta());
+
+        if hitstun.timer.is_finished() {
+            commands.entity(entity).remove::<Hitstun>();
+        } else if !in_knockback {
+            velocity.x = 0.0;
+            if !has_gravity {
+                velocity.y = 0.0;
+            }
+        }
+    }
+}
+
 /// Handle generic entity death
 pub fn death_system(
     mut commands: Commands,
@@ -247,23 +534,67 @@ pub fn death_system(
 }
 
 /// Handle player death with respawn or game over
+///
+/// Players respawn at a living partner's position (local co-op), otherwise at
+/// the spawn point. With `SharedLives` every death draws from the shared pool.
+/// With per-player `Lives`, a player who runs out leaves the game while a
+/// partner is still alive, and it is game over once nobody is left.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn player_death_system(
     mut commands: Commands,
     mut death_events: MessageReader<DeathEvent>,
-    mut player_query: Query<(&mut Health, &mut Transform, Option<&mut Lives>), With<Player>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &mut Transform,
+            Option<&mut Lives>,
+            Option<&mut StatusEffects>,
+        ),
+        With<Player>,
+    >,
+    mut shared_lives: Option<ResMut<SharedLives>>,
     spawn_point: Option<Res<PlayerSpawnPoint>>,
+    config: Res<CombatConfig>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     for event in death_events.read() {
-        let Ok((mut health, mut transform, lives)) = player_query.get_mut(event.entity) else {
+        if !player_query.contains(event.entity) {
+            continue;
+        }
+
+        // Position of a living partner, if any
+        let partner_pos = player_query
+            .iter()
+            .find(|(entity, health, _, _, _)| *entity != event.entity && health.is_alive())
+            .map(|(_, _, transform, _, _)| transform.translation);
+
+        let Ok((_, mut health, mut transform, lives, status_effects)) =
+            player_query.get_mut(event.entity)
+        else {
             continue;
         };
 
         info!("Player died!");
 
         // Check if player has lives remaining
-        if let Some(mut lives) = lives {
+        if let Some(shared) = shared_lives.as_deref_mut() {
+            if shared.0.lose_life() {
+                info!("Game Over - No shared lives remaining");
+                next_state.set(GameState::GameOver);
+                return;
+            }
+
+            info!("Shared lives remaining: {}", shared.0.current);
+        } else if let Some(mut lives) = lives {
             if lives.lose_life() {
+                if partner_pos.is_some() {
+                    // Partner keeps playing
+                    info!("Player {:?} is out of lives", event.entity);
+                    commands.entity(event.entity).despawn();
+                    continue;
+                }
+
                 // No lives remaining - game over
                 info!("Game Over - No lives remaining");
                 next_state.set(GameState::GameOver);
@@ -274,23 +605,28 @@ pub fn player_death_system(
             info!("Lives remaining: {}", lives.current);
         }
 
-        // Respawn at spawn point
-        let respawn_pos = spawn_point
-            .as_ref()
-            .map(|sp| sp.position)
-            .unwrap_or(Vec3::new(0.0, 100.0, 0.0));
+        // Respawn next to the partner, or at the spawn point
+        let respawn_pos = partner_pos.unwrap_or_else(|| {
+            spawn_point
+                .as_ref()
+                .map(|sp| sp.position)
+                .unwrap_or(Vec3::new(0.0, 100.0, 0.0))
+        });
 
         transform.translation = respawn_pos;
         let max_health = health.max;
         health.heal(max_health); // Full heal on respawn
 
-        // Remove any active damage effects
+        // Remove any active damage and status effects
+        if let Some(mut status_effects) = status_effects {
+            status_effects.clear();
+        }
         commands
             .entity(event.entity)
             .remove::<Knockback>()
             .remove::<InKnockback>()
             .remove::<DamageFlash>()
-            .insert(Invincibility::new(2.0)); // 2 seconds of invincibility after respawn
+            .insert(Invincibility::new(config.respawn_iframe_secs));
 
         info!("Player respawned at {:?}", respawn_pos);
     }
@@ -300,10 +636,51 @@ pub fn player_death_system(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hit_during_flash_restores_original_color() {
+        let mut app = App::new();
+        app.add_message::<DamageEvent>()
+            .add_message::<DeathEvent>()
+            .add_message::<HitStopEvent>()
+            .add_message::<ScreenShakeEvent>()
+            .add_message::<StatusEffectEvent>()
+            .add_message::<DamageDealtEvent>()
+            .add_message::<ShieldEvent>();
+        app.init_resource::<CombatConfig>();
+        app.init_resource::<FxAssets>();
+        app.add_systems(Update, damage_system);
+
+        // Hit in the middle of a telegraph flash
+        let telegraph = Color::srgb(1.0, 0.3, 0.3);
+        let target = app
+            .world_mut()
+            .spawn((
+                Health::new(50.0),
+                Transform::default(),
+                Sprite::from_color(telegraph, Vec2::ONE),
+                DamageFlash::new(0.5).with_color(telegraph),
+            ))
+            .id();
+        app.world_mut().write_message(DamageEvent {
+            target,
+            damage: 5.0,
+            damage_type: crate::DamageType::Physical,
+            knockback: Vec2::ZERO,
+            attacker: None,
+            impact: None,
+            status: None,
+            critical: false,
+        });
+        app.update();
+
+        let flash = app.world().get::<DamageFlash>(target).unwrap();
+        assert_eq!(flash.original_color, Color::WHITE);
+    }
+
     #[test]
     fn test_invincibility_creation() {
         let inv = Invincibility::new(1.0);
-        assert!(!inv.timer.finished());
+        assert!(!inv.timer.is_finished());
     }
 
     #[test]
@@ -311,12 +688,45 @@ mod tests {
         let kb = Knockback::new(Vec2::new(100.0, 50.0), 0.2);
         assert_eq!(kb.force.x, 100.0);
         assert_eq!(kb.force.y, 50.0);
-        assert!(!kb.duration.finished());
+        assert!(!kb.duration.is_finished());
     }
 
     #[test]
     fn test_damage_flash_creation() {
         let flash = DamageFlash::new(0.15);
-        assert!(!flash.timer.finished());
+        assert!(!flash.timer.is_finished());
+    }
+
+    #[test]
+    fn test_contact_point_clamps_to_collider() {
+        let collider = Collider::new(Vec2::new(20.0, 40.0));
+        let target = Vec2::new(100.0, 0.0);
+
+        // Attacker to the left hits the left edge at its own height
+        assert_eq!(
+            contact_point(target, Some(&collider), Some(Vec2::new(50.0, 5.0))),
+            Vec2::new(90.0, 5.0)
+        );
+        // Unknown attacker falls back to the target position
+        assert_eq!(contact_point(target, Some(&collider), None), target);
+    }
+
+    #[test]
+    fn test_hit_reaction_knockback() {
+        let force = Vec2::new(200.0, 100.0);
+        assert_eq!(HitReaction::default().knockback(force), Some(force));
+
+        let heavy = HitReaction {
+            knockback_resistance: 0.75,
+            ..default()
+        };
+        assert_eq!(heavy.knockback(force), Some(Vec2::new(50.0, 25.0)));
+
+        let armored = HitReaction {
+            super_armor: true,
+            ..default()
+        };
+        assert_eq!(armored.knockback(force), None);
+        assert_eq!(armored.hitstun(), None);
     }
 }
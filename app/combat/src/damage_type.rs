@@ -0,0 +1,149 @@
+//! Damage types, resistances and weaknesses
+//!
+//! Every `DamageEvent` carries a `DamageType`. Targets with `DamageResistances`
+//! scale incoming damage by a per-type multiplier (configured per enemy in
+//! `assets/config/enemies.ron`): above 1.0 is a weakness, below 1.0 a
+//! resistance and 0.0 an immunity.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Elemental type of a hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DamageType {
+    /// Melee and contact hits
+    #[default]
+    Physical,
+    /// Fire (e.g. fireballs)
+    Fire,
+    /// Electricity
+    Electric,
+    /// Ice
+    Ice,
+    /// Poison
+    Poison,
+}
+
+impl DamageType {
+    /// Effect played on the target when a hit of this type is effective
+    /// (name from `assets/config/effects.ron`)
+    pub fn hit_effect(&self) -> Option<&'static str> {
+        match self {
+            DamageType::Fire => Some("fireball_hit"),
+            DamageType::Electric => Some("electro_shock"),
+            DamageType::Physical | DamageType::Ice | DamageType::Poison => None,
+        }
+    }
+}
+
+/// How well a hit worked against the target's resistances
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HitEffectiveness {
+    /// No modifier
+    #[default]
+    Normal,
+    /// Target is weak to the damage type (multiplier above 1.0)
+    Effective,
+    /// Target resists the damage type (multiplier below 1.0)
+    Resisted,
+    /// Target is immune to the damage type (multiplier 0.0)
+    Immune,
+}
+
+impl HitEffectiveness {
+    /// Classify a damage multiplier
+    pub fn from_multiplier(multiplier: f32) -> Self {
+        if multiplier <= 0.0 {
+            HitEffectiveness::Immune
+        } else if multiplier > 1.0 {
+            HitEffectiveness::Effective
+        } else if multiplier < 1.0 {
+            HitEffectiveness::Resisted
+        } else {
+            HitEffectiveness::Normal
+        }
+    }
+
+    /// Damage flash color shown on the target
+    pub fn flash_color(&self) -> Color {
+        match self {
+            HitEffectiveness::Normal => Color::srgb(1.0, 0.3, 0.3),
+            HitEffectiveness::Effective => Color::srgb(1.0, 0.85, 0.2),
+            HitEffectiveness::Resisted => Color::srgb(0.6, 0.6, 0.7),
+            HitEffectiveness::Immune => Color::srgb(0.5, 0.6, 1.0),
+        }
+    }
+}
+
+/// Damage multipliers by damage type (missing types take normal damage)
+#[derive(Component, Debug, Clone, Default)]
+pub struct DamageResistances {
+    /// Multiplier applied to incoming damage of each type
+    pub multipliers: HashMap<DamageType, f32>,
+}
+
+impl DamageResistances {
+    /// Create resistances from a multiplier table
+    pub fn new(multipliers: HashMap<DamageType, f32>) -> Self {
+        Self { multipliers }
+    }
+
+    /// Damage multiplier for a damage type
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        self.multipliers
+            .get(&damage_type)
+            .copied()
+            .unwrap_or(1.0)
+            .max(0.0)
+    }
+
+    /// Effectiveness of a hit of the given type
+    pub fn effectiveness(&self, damage_type: DamageType) -> HitEffectiveness {
+        HitEffectiveness::from_multiplier(self.multiplier(damage_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resistance_multipliers() {
+        let resistances = DamageResistances::new(HashMap::from([
+            (DamageType::Fire, 2.0),
+            (DamageType::Physical, 0.5),
+            (DamageType::Electric, 0.0),
+        ]));
+
+        assert_eq!(resistances.multiplier(DamageType::Fire), 2.0);
+        assert_eq!(resistances.multiplier(DamageType::Ice), 1.0);
+        assert_eq!(
+            resistances.effectiveness(DamageType::Fire),
+            HitEffectiveness::Effective
+        );
+        assert_eq!(
+            resistances.effectiveness(DamageType::Physical),
+            HitEffectiveness::Resisted
+        );
+        assert_eq!(
+            resistances.effectiveness(DamageType::Electric),
+            HitEffectiveness::Immune
+        );
+        assert_eq!(
+            resistances.effectiveness(DamageType::Poison),
+            HitEffectiveness::Normal
+        );
+    }
+
+    #[test]
+    fn test_resistance_table_parse() {
+        let multipliers: HashMap<DamageType, f32> =
+            ron::from_str("{ Fire: 1.5, Electric: 0.0 }").unwrap();
+        let resistances = DamageResistances::new(multipliers);
+        assert_eq!(
+            resistances.effectiveness(DamageType::Electric),
+            HitEffectiveness::Immune
+        );
+    }
+}
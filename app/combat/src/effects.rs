@@ -1,11 +1,17 @@
 //! Combat visual effects
 //!
-//! Implements hit stop (brief pause on impact) and screen shake effects
-//! to provide satisfying combat feedback.
+//! Implements hit stop (brief pause on impact), screen shake effects and
+//! spritesheet effects (slashes, fireballs) to provide satisfying combat feedback.
+//! Effect spritesheets are defined in `assets/config/effects.ron`.
 
 use bevy::prelude::*;
 use bevy::time::{Real, Virtual};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
 
 use super::{HitStopEvent, ScreenShakeEvent};
 
@@ -56,6 +62,213 @@ impl ScreenShake {
     }
 }
 
+/// Spritesheet effect definition (horizontal strip of frames)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FxSheetConfig {
+    /// Path to the spritesheet (relative to assets directory)
+    pub spritesheet_path: String,
+    /// Size of each frame in pixels (width, height)
+    pub frame_size: (u32, u32),
+    /// Number of frames in the strip
+    pub frames: u32,
+    /// Playback speed
+    pub fps: f32,
+}
+
+/// Effects configuration file format
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+pub struct FxConfig {
+    /// Effect definitions by name
+    pub effects: HashMap<String, FxSheetConfig>,
+}
+
+/// Effects configuration loading errors
+#[derive(Debug, Error)]
+pub enum FxConfigError {
+    #[error("Failed to read effects config file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON effects config: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+}
+
+/// Load effects configuration from a RON file
+pub fn load_fx_config<P: AsRef<Path>>(path: P) -> Result<FxConfig, FxConfigError> {
+    let content = fs::read_to_string(path)?;
+    let config: FxConfig = ron::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load effects configuration from a RON file, or return default if loading/parsing fails
+pub fn load_fx_config_optional<P: AsRef<Path>>(path: P) -> FxConfig {
+    match load_fx_config(path) {
+        Ok(config) => {
+            info!(
+                "Loaded effects config with {} effects",
+                config.effects.len()
+            );
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load effects config: {}. Using default.", e);
+            FxConfig::default()
+        }
+    }
+}
+
+/// Loaded spritesheet for an effect
+#[derive(Debug, Clone)]
+pub struct FxSheet {
+    /// Spritesheet texture
+    pub texture: Handle<Image>,
+    /// Atlas layout (single row of frames)
+    pub layout: Handle<TextureAtlasLayout>,
+    /// Number of frames
+    pub frames: usize,
+    /// Playback speed
+    pub fps: f32,
+}
+
+impl FxSheet {
+    /// Create a sprite showing the first frame of the effect
+    pub fn sprite(&self, flip_x: bool) -> Sprite {
+        let mut sprite = Sprite::from_atlas_image(
+            self.texture.clone(),
+            TextureAtlas {
+                layout: self.layout.clone(),
+                index: 0,
+            },
+        );
+        sprite.flip_x = flip_x;
+        sprite
+    }
+
+    /// Create the animation component for the effect
+    pub fn animation(&self, looping: bool) -> FxAnimation {
+        FxAnimation {
+            frames: self.frames,
+            timer: Timer::from_seconds(1.0 / self.fps, TimerMode::Repeating),
+            looping,
+        }
+    }
+}
+
+/// Resource holding loaded effect spritesheets by name
+#[derive(Resource, Debug, Default)]
+pub struct FxAssets {
+    sheets: HashMap<String, FxSheet>,
+}
+
+impl FxAssets {
+    /// Register an effect spritesheet
+    pub fn insert(&mut self, name: impl Into<String>, sheet: FxSheet) {
+        self.sheets.insert(name.into(), sheet);
+    }
+
+    /// Get an effect spritesheet by name
+    pub fn get(&self, name: &str) -> Option<&FxSheet> {
+        self.sheets.get(name)
+    }
+}
+
+/// Load the spritesheets of all configured effects
+pub fn load_fx_assets(
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    config: Res<FxConfig>,
+    mut fx_assets: ResMut<FxAssets>,
+) {
+    for (name, effect) in &config.effects {
+        let texture = asset_server.load(&effect.spritesheet_path);
+        let layout = TextureAtlasLayout::from_grid(
+            UVec2::new(effect.frame_size.0, effect.frame_size.1),
+            effect.frames,
+            1,
+            None,
+            None,
+        );
+
+        fx_assets.insert(
+            name,
+            FxSheet {
+                texture,
+                layout: texture_atlas_layouts.add(layout),
+                frames: effect.frames as usize,
+                fps: effect.fps,
+            },
+        );
+    }
+}
+
+/// Spritesheet effect animation
+///
+/// One-shot effects despawn after their last frame; looping effects
+/// (e.g. projectiles) cycle until their entity is despawned.
+#[derive(Component, Debug)]
+pub struct FxAnimation {
+    /// Number of frames in the effect
+    pub frames: usize,
+    /// Frame timer
+    pub timer: Timer,
+    /// Whether the animation loops
+    pub looping: bool,
+}
+
+/// Spawn a one-shot effect by name
+///
+/// Returns None if the effect has not been loaded.
+pub fn spawn_fx(
+    commands: &mut Commands,
+    fx_assets: &FxAssets,
+    name: &str,
+    position: Vec3,
+    flip_x: bool,
+) -> Option<Entity> {
+    let Some(sheet) = fx_assets.get(name) else {
+        warn!("Effect '{}' is not loaded", name);
+        return None;
+    };
+
+    let entity = commands
+        .spawn((
+            sheet.sprite(flip_x),
+            Transform::from_translation(position),
+            sheet.animation(false),
+            Name::new(format!("Fx_{}", name)),
+        ))
+        .id();
+
+    Some(entity)
+}
+
+/// Advance effect animations and despawn one-shot effects when finished
+pub fn fx_animation_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut FxAnimation, &mut Sprite)>,
+) {
+    for (entity, mut fx, mut sprite) in query.iter_mut() {
+        fx.timer.tick(time.delta());
+        let frames_advanced = fx.timer.times_finished_this_tick() as usize;
+        if frames_advanced == 0 {
+            continue;
+        }
+
+        let Some(atlas) = &mut sprite.texture_atlas else {
+            continue;
+        };
+
+        let next_frame = atlas.index + frames_advanced;
+        if fx.looping {
+            atlas.index = next_frame % fx.frames.max(1);
+        } else if next_frame >= fx.frames {
+            commands.entity(entity).despawn();
+        } else {
+            atlas.index = next_frame;
+        }
+    }
+}
+
 /// Process hit stop events and pause game time
 pub fn hitstop_system(
     real_time: Res<Time<Real>>,
@@ -144,6 +357,6 @@ mod tests {
         let mut shake = ScreenShake::default();
         shake.trigger(10.0, 0.5);
         assert_eq!(shake.intensity, 10.0);
-        assert!(!shake.duration.finished());
+        assert!(!shake.duration.is_finished());
     }
 }
@@ -0,0 +1,130 @@
+//! Factions and hostility
+//!
+//! Every damageable entity and every attack belongs to a `Faction`. Whether an
+//! attack can hit a target is decided by the hostility matrix in
+//! `assets/config/factions.ron`, so friendly fire, charmed enemies (switch their
+//! `Faction`), neutral critters and environmental damage need no special cases.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Side an entity or attack belongs to
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Faction {
+    /// Players and their attacks
+    Player,
+    /// Enemies and their attacks
+    Enemy,
+    /// Critters and untagged entities
+    #[default]
+    Neutral,
+    /// Traps and hazards
+    Environment,
+}
+
+/// Hostility matrix between factions
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct FactionsConfig {
+    /// Attacks also hit other members of their own faction (never their owner)
+    #[serde(default)]
+    pub friendly_fire: bool,
+    /// Factions each faction's attacks can hit
+    #[serde(default = "default_hostility")]
+    pub hostility: HashMap<Faction, HashSet<Faction>>,
+}
+
+fn default_hostility() -> HashMap<Faction, HashSet<Faction>> {
+    use Faction::*;
+
+    HashMap::from([
+        (Player, HashSet::from([Enemy, Neutral])),
+        (Enemy, HashSet::from([Player])),
+        (Neutral, HashSet::new()),
+        (Environment, HashSet::from([Player, Enemy, Neutral])),
+    ])
+}
+
+impl Default for FactionsConfig {
+    fn default() -> Self {
+        Self {
+            friendly_fire: false,
+            hostility: default_hostility(),
+        }
+    }
+}
+
+impl FactionsConfig {
+    /// Whether an attack of `attacker` can hit a target of `target`
+    pub fn is_hostile(&self, attacker: Faction, target: Faction) -> bool {
+        if attacker == target && self.friendly_fire {
+            return true;
+        }
+        self.hostility
+            .get(&attacker)
+            .is_some_and(|targets| targets.contains(&target))
+    }
+}
+
+/// Faction config loading errors
+#[derive(Debug, Error)]
+pub enum FactionsConfigError {
+    #[error("Failed to read factions config file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON factions config: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+}
+
+/// Load the hostility matrix from a RON file
+pub fn load_factions_config<P: AsRef<Path>>(
+    path: P,
+) -> Result<FactionsConfig, FactionsConfigError> {
+    let content = fs::read_to_string(path)?;
+    let config: FactionsConfig = ron::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load the hostility matrix from a RON file, or return default if loading/parsing fails
+pub fn load_factions_config_optional<P: AsRef<Path>>(path: P) -> FactionsConfig {
+    match load_factions_config(path) {
+        Ok(config) => {
+            info!("Loaded factions config");
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load factions config: {}. Using default.", e);
+            FactionsConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_hostility() {
+        let config = FactionsConfig::default();
+
+        assert!(config.is_hostile(Faction::Player, Faction::Enemy));
+        assert!(config.is_hostile(Faction::Enemy, Faction::Player));
+        assert!(!config.is_hostile(Faction::Enemy, Faction::Neutral));
+        assert!(!config.is_hostile(Faction::Player, Faction::Player));
+        assert!(config.is_hostile(Faction::Environment, Faction::Enemy));
+    }
+
+    #[test]
+    fn test_friendly_fire() {
+        let config: FactionsConfig =
+            ron::from_str("(friendly_fire: true, hostility: { Enemy: [Player] })").unwrap();
+
+        assert!(config.is_hostile(Faction::Player, Faction::Player));
+        assert!(config.is_hostile(Faction::Enemy, Faction::Player));
+        // Factions missing from the matrix hit nobody else
+        assert!(!config.is_hostile(Faction::Player, Faction::Enemy));
+    }
+}
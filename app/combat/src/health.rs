@@ -115,6 +115,13 @@ impl Default for Lives {
     }
 }
 
+/// Lives pool shared by all players (local co-op)
+///
+/// When present, player deaths draw from this pool instead of each player's
+/// `Lives` component.
+#[derive(Resource, Debug)]
+pub struct SharedLives(pub Lives);
+
 /// Resource for tracking player spawn point (for respawn)
 #[derive(Resource, Debug)]
 pub struct PlayerSpawnPoint {
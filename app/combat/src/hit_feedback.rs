@@ -0,0 +1,392 @@
+//! Floating damage numbers and hit sparks
+//!
+//! Every hit that lands (`DamageDealtEvent`) shows a world-space number that
+//! rises and fades, and an `energy_smack` spark at the contact point. Both are
+//! toggled in the `hit_feedback` section of `assets/config/game_settings.ron`.
+//! Entities are pooled: the pools grow up to `pool_size` and then reuse the
+//! oldest entry, so heavy fights don't spawn an entity per hit.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{DamageType, FxAssets, HitEffectiveness};
+
+/// Effect played at the contact point of a hit (name from `assets/config/effects.ron`)
+pub const HIT_SPARK_EFFECT: &str = "energy_smack";
+
+/// Damage number and hit spark settings
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct HitFeedbackSettings {
+    /// Show floating damage numbers
+    #[serde(default = "default_enabled")]
+    pub damage_numbers: bool,
+    /// Show hit sparks at the contact point
+    #[serde(default = "default_enabled")]
+    pub hit_sparks: bool,
+    /// Maximum number of pooled numbers (and sparks) on screen
+    #[serde(default = "default_pool_size")]
+    pub pool_size: usize,
+    /// How long a damage number stays visible (seconds)
+    #[serde(default = "default_number_lifetime_secs")]
+    pub number_lifetime_secs: f32,
+    /// Rise speed of damage numbers (pixels per second)
+    #[serde(default = "default_number_rise_speed")]
+    pub number_rise_speed: f32,
+    /// Font size of damage numbers (critical hits are larger)
+    #[serde(default = "default_number_font_size")]
+    pub number_font_size: f32,
+    /// Scale of the hit spark sprite
+    #[serde(default = "default_spark_scale")]
+    pub spark_scale: f32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_pool_size() -> usize {
+    32
+}
+
+fn default_number_lifetime_secs() -> f32 {
+    0.8
+}
+
+fn default_number_rise_speed() -> f32 {
+    60.0
+}
+
+fn default_number_font_size() -> f32 {
+    14.0
+}
+
+fn default_spark_scale() -> f32 {
+    0.4
+}
+
+impl Default for HitFeedbackSettings {
+    fn default() -> Self {
+        Self {
+            damage_numbers: true,
+            hit_sparks: true,
+            pool_size: default_pool_size(),
+            number_lifetime_secs: default_number_lifetime_secs(),
+            number_rise_speed: default_number_rise_speed(),
+            number_font_size: default_number_font_size(),
+            spark_scale: default_spark_scale(),
+        }
+    }
+}
+
+/// Event fired by `damage_system` when a hit deals damage
+#[derive(bevy::ecs::prelude::Message, Debug, Clone)]
+pub struct DamageDealtEvent {
+    /// The entity that took damage
+    pub target: Entity,
+    /// Damage dealt (after resistances)
+    pub damage: f32,
+    /// Elemental type of the damage
+    pub damage_type: DamageType,
+    /// How well the hit worked against the target's resistances
+    pub effectiveness: HitEffectiveness,
+    /// Whether the hit was critical
+    pub critical: bool,
+    /// Whether the target is a player
+    pub target_is_player: bool,
+    /// Contact point between the attack and the target (world space)
+    pub contact_point: Vec2,
+}
+
+/// Color of a damage number
+pub fn damage_number_color(
+    target_is_player: bool,
+    damage_type: DamageType,
+    critical: bool,
+) -> Color {
+    if target_is_player {
+        return Color::srgb(1.0, 0.3, 0.3);
+    }
+    if critical {
+        return Color::srgb(1.0, 0.85, 0.2);
+    }
+
+    match damage_type {
+        DamageType::Physical => Color::WHITE,
+        DamageType::Fire => Color::srgb(1.0, 0.55, 0.2),
+        DamageType::Electric => Color::srgb(0.5, 0.9, 1.0),
+        DamageType::Ice => Color::srgb(0.65, 0.8, 1.0),
+        DamageType::Poison => Color::srgb(0.6, 1.0, 0.4),
+    }
+}
+
+/// Text of a damage number
+pub fn damage_number_text(damage: f32, critical: bool) -> String {
+    let value = damage.round().max(1.0) as u32;
+    if critical {
+        format!("{}!", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Pooled floating damage number
+#[derive(Component, Debug)]
+pub struct DamageNumber {
+    /// Remaining visible time
+    pub timer: Timer,
+    /// Movement (pixels per second)
+    pub velocity: Vec2,
+    /// Color at full opacity
+    pub color: Color,
+}
+
+/// Pooled hit spark
+#[derive(Component, Debug)]
+pub struct HitSpark {
+    /// Frame timer
+    pub timer: Timer,
+    /// Number of frames
+    pub frames: usize,
+    /// Whether the spark is playing
+    pub active: bool,
+}
+
+/// Pooled damage number and hit spark entities
+#[derive(Resource, Debug, Default)]
+pub struct HitFeedbackPool {
+    numbers: Vec<Entity>,
+    next_number: usize,
+    sparks: Vec<Entity>,
+    next_spark: usize,
+}
+
+impl HitFeedbackPool {
+    /// Entity to reuse from a pool, or None if the pool can still grow
+    fn reuse(pool: &[Entity], next: &mut usize, capacity: usize) -> Option<Entity> {
+        if pool.len() < capacity.max(1) {
+            return None;
+        }
+        let entity = pool[*next % pool.len()];
+        *next = (*next + 1) % pool.len();
+        Some(entity)
+    }
+}
+
+/// Show a damage number and a hit spark for each landed hit
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn hit_feedback_system(
+    mut commands: Commands,
+    settings: Res<HitFeedbackSettings>,
+    fx_assets: Res<FxAssets>,
+    mut pool: ResMut<HitFeedbackPool>,
+    mut dealt_events: MessageReader<DamageDealtEvent>,
+    mut number_query: Query<
+        (
+            &mut DamageNumber,
+            &mut Text2d,
+            &mut TextFont,
+            &mut TextColor,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<HitSpark>,
+    >,
+    mut spark_query: Query<(&mut HitSpark, &mut Sprite, &mut Transform, &mut Visibility)>,
+) {
+    let mut rng = rand::rng();
+    let pool = pool.as_mut();
+
+    for event in dealt_events.read() {
+        if settings.damage_numbers {
+            let color =
+                damage_number_color(event.target_is_player, event.damage_type, event.critical);
+            let font_size = if event.critical {
+                settings.number_font_size * 1.5
+            } else {
+                settings.number_font_size
+            };
+            let number = DamageNumber {
+                timer: Timer::from_seconds(settings.number_lifetime_secs, TimerMode::Once),
+                // Slight sideways drift so simultaneous numbers don't overlap
+                velocity: Vec2::new(rng.random_range(-20.0..20.0), settings.number_rise_speed),
+                color,
+            };
+            let text = damage_number_text(event.damage, event.critical);
+            let translation = (event.contact_point + Vec2::new(0.0, 16.0)).extend(50.0);
+
+            match HitFeedbackPool::reuse(&pool.numbers, &mut pool.next_number, settings.pool_size)
+                .and_then(|entity| number_query.get_mut(entity).ok())
+            {
+                Some((
+                    mut pooled,
+                    mut text2d,
+                    mut font,
+                    mut text_color,
+                    mut transform,
+                    mut visibility,
+                )) => {
+                    *pooled = number;
+                    text2d.0 = text;
+                    font.font_size = font_size;
+                    text_color.0 = color;
+                    transform.translation = translation;
+                    *visibility = Visibility::Visible;
+                }
+                None => {
+                    let entity = commands
+                        .spawn((
+                            Text2d::new(text),
+                            TextFont {
+                                font_size,
+                                ..default()
+                            },
+                            TextColor(color),
+                            Transform::from_translation(translation),
+                            Visibility::Visible,
+                            number,
+                            Name::new("DamageNumber"),
+                        ))
+                        .id();
+                    pool.numbers.push(entity);
+                }
+            }
+        }
+
+        if settings.hit_sparks
+            && let Some(sheet) = fx_assets.get(HIT_SPARK_EFFECT)
+        {
+            let spark = HitSpark {
+                timer: Timer::from_seconds(1.0 / sheet.fps, TimerMode::Repeating),
+                frames: sheet.frames,
+                active: true,
+            };
+            let flip_x = rng.random_bool(0.5);
+            let transform = Transform::from_translation(event.contact_point.extend(40.0))
+                .with_scale(Vec3::splat(settings.spark_scale));
+
+            match HitFeedbackPool::reuse(&pool.sparks, &mut pool.next_spark, settings.pool_size)
+                .and_then(|entity| spark_query.get_mut(entity).ok())
+            {
+                Some((mut pooled, mut sprite, mut spark_transform, mut visibility)) => {
+                    *pooled = spark;
+                    *sprite = sheet.sprite(flip_x);
+                    *spark_transform = transform;
+                    *visibility = Visibility::Visible;
+                }
+                None => {
+                    let entity = commands
+                        .spawn((
+                            sheet.sprite(flip_x),
+                            transform,
+                            Visibility::Visible,
+                            spark,
+                            Name::new("HitSpark"),
+                        ))
+                        .id();
+                    pool.sparks.push(entity);
+                }
+            }
+        }
+    }
+}
+
+/// Raise and fade damage numbers, hiding them when they expire
+pub fn damage_number_system(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut DamageNumber,
+        &mut TextColor,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    for (mut number, mut color, mut transform, mut visibility) in query.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        number.timer.tick(time.delta());
+        if number.timer.is_finished() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        transform.translation += (number.velocity * time.delta_secs()).extend(0.0);
+
+        // Fade out over the second half of the lifetime
+        let alpha = (2.0 * (1.0 - number.timer.fraction())).min(1.0);
+        color.0 = number.color.with_alpha(alpha);
+    }
+}
+
+/// Play hit sparks, hiding them after the last frame
+pub fn hit_spark_system(
+    time: Res<Time>,
+    mut query: Query<(&mut HitSpark, &mut Sprite, &mut Visibility)>,
+) {
+    for (mut spark, mut sprite, mut visibility) in query.iter_mut() {
+        if !spark.active {
+            continue;
+        }
+
+        spark.timer.tick(time.delta());
+        let frames_advanced = spark.timer.times_finished_this_tick() as usize;
+        let Some(atlas) = &mut sprite.texture_atlas else {
+            continue;
+        };
+
+        let next_frame = atlas.index + frames_advanced;
+        if next_frame >= spark.frames {
+            spark.active = false;
+            *visibility = Visibility::Hidden;
+        } else {
+            atlas.index = next_frame;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_number_text() {
+        assert_eq!(damage_number_text(12.4, false), "12");
+        assert_eq!(damage_number_text(30.0, true), "30!");
+        // Tiny damage still shows as 1
+        assert_eq!(damage_number_text(0.3, false), "1");
+    }
+
+    #[test]
+    fn test_damage_number_color() {
+        assert_eq!(
+            damage_number_color(true, DamageType::Fire, true),
+            Color::srgb(1.0, 0.3, 0.3)
+        );
+        assert_eq!(
+            damage_number_color(false, DamageType::Physical, false),
+            Color::WHITE
+        );
+    }
+
+    #[test]
+    fn test_pool_reuses_oldest_when_full() {
+        let pool = vec![Entity::from_bits(1), Entity::from_bits(2)];
+        let mut next = 0;
+
+        assert_eq!(HitFeedbackPool::reuse(&pool, &mut next, 3), None);
+        assert_eq!(
+            HitFeedbackPool::reuse(&pool, &mut next, 2),
+            Some(Entity::from_bits(1))
+        );
+        assert_eq!(
+            HitFeedbackPool::reuse(&pool, &mut next, 2),
+            Some(Entity::from_bits(2))
+        );
+        assert_eq!(
+            HitFeedbackPool::reuse(&pool, &mut next, 2),
+            Some(Entity::from_bits(1))
+        );
+    }
+}
@@ -1,23 +1,50 @@
 //! Combat system
 //!
 //! This module implements the combat mechanics including:
-//! - Health and damage
-//! - Attack hitboxes
-//! - Knockback
+//! - Health and damage (feel tuned in `combat.ron`)
+//! - Damage types, resistances and weaknesses
+//! - Attack hitboxes (data-driven, activated by animation frame events)
+//! - Factions and hostility between attacks and hurtboxes
+//! - Melee combo chains
+//! - Projectiles (shared by players and enemies) and player ranged attacks
+//! - Knockback, hitstun and super-armor
+//! - Shield blocking and parrying
+//! - Floating damage numbers and hit sparks
+//! - Status effects (burn, poison, freeze, stun)
 //! - Invincibility frames
 //! - Death and respawn
 
 use bevy::prelude::*;
 
 pub mod attack;
+pub mod attack_config;
+pub mod combat_config;
+pub mod combo;
 pub mod damage;
+pub mod damage_type;
 pub mod effects;
+pub mod faction;
 pub mod health;
+pub mod hit_feedback;
+pub mod projectile;
+pub mod ranged;
+pub mod shield;
+pub mod status;
 
 pub use attack::*;
+pub use attack_config::*;
+pub use combat_config::*;
+pub use combo::*;
 pub use damage::*;
+pub use damage_type::*;
 pub use effects::*;
+pub use faction::*;
 pub use health::*;
+pub use hit_feedback::*;
+pub use projectile::*;
+pub use ranged::*;
+pub use shield::*;
+pub use status::*;
 
 use tropical_fox_common::GameState;
 
@@ -26,12 +53,20 @@ use tropical_fox_common::GameState;
 pub struct DamageEvent {
     /// The entity receiving damage
     pub target: Entity,
-    /// Amount of damage dealt
+    /// Amount of damage dealt (before resistances)
     pub damage: f32,
+    /// Elemental type of the damage
+    pub damage_type: DamageType,
     /// Knockback force to apply
     pub knockback: Vec2,
     /// The entity that dealt the damage (if any)
     pub attacker: Option<Entity>,
+    /// Hit stop and screen shake override (None uses the combat config)
+    pub impact: Option<HitImpact>,
+    /// Status effect applied if the hit lands
+    pub status: Option<StatusEffect>,
+    /// Whether the hit is critical (damage already includes the multiplier)
+    pub critical: bool,
 }
 
 /// Event fired when an entity dies
@@ -65,12 +100,36 @@ impl Plugin for CombatPlugin {
         // Resources
         app.insert_resource(HitStopTimer::default());
         app.insert_resource(ScreenShake::default());
+        app.insert_resource(FxAssets::default());
+        app.insert_resource(HitFeedbackPool::default());
+        // The game inserts this from game_settings.ron
+        app.init_resource::<HitFeedbackSettings>();
+
+        // Load effect spritesheets, attack definitions, combo chains and factions
+        let fx_config = load_fx_config_optional("assets/config/effects.ron");
+        app.insert_resource(fx_config);
+        app.add_systems(Startup, load_fx_assets);
+
+        let attacks_config = load_attacks_config_optional("assets/config/attacks.ron");
+        app.insert_resource(attacks_config);
+
+        let combo_config = load_combo_config_optional("assets/config/combos.ron");
+        app.insert_resource(combo_config);
+
+        let combat_config = load_combat_config_optional("assets/config/combat.ron");
+        app.insert_resource(combat_config);
+
+        let factions_config = load_factions_config_optional("assets/config/factions.ron");
+        app.insert_resource(factions_config);
 
         // Events (Messages in Bevy 0.17)
         app.add_message::<DamageEvent>();
         app.add_message::<DeathEvent>();
         app.add_message::<HitStopEvent>();
         app.add_message::<ScreenShakeEvent>();
+        app.add_message::<StatusEffectEvent>();
+        app.add_message::<DamageDealtEvent>();
+        app.add_message::<ShieldEvent>();
 
         // Combat systems (execution order is important)
         app.add_systems(
@@ -78,7 +137,14 @@ impl Plugin for CombatPlugin {
             (
                 // Phase 1: Attack input and cooldown
                 update_attack_cooldown,
+                update_attacking,
+                update_attack_charge,
+                update_combo_state,
                 player_attack_input,
+                attack_start_system,
+                attack_frame_event_system
+                    .after(tropical_fox_animation::systems::process_animation_events),
+                charge_indicator_system,
                 // Phase 2: Hit detection
                 attack_collision_system,
                 attack_lifetime_system,
@@ -90,6 +156,8 @@ impl Plugin for CombatPlugin {
                 invincibility_system,
                 damage_flash_system,
                 knockback_system,
+                hitstun_system,
+                fx_animation_system,
                 // Phase 5: Death processing
                 death_system,
                 player_death_system,
@@ -97,5 +165,62 @@ impl Plugin for CombatPlugin {
                 .chain()
                 .run_if(in_state(GameState::InGame)),
         );
+
+        // Projectile systems (hits are processed by damage_system in the same frame)
+        app.add_systems(
+            Update,
+            (
+                update_ranged_attack,
+                player_ranged_attack_input,
+                projectile_burst_system,
+                projectile_movement_system,
+                projectile_collision_system,
+            )
+                .chain()
+                .after(attack_collision_system)
+                .before(damage_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Status effect systems (after knockback so stun can hold the target in place)
+        app.add_systems(
+            Update,
+            (
+                status_hazard_system,
+                apply_status_effects,
+                status_effect_tick_system,
+                status_tint_system,
+            )
+                .chain()
+                .after(knockback_system)
+                .before(death_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Shields are raised before hits are resolved; parries stagger attackers afterwards
+        app.add_systems(
+            Update,
+            shield_input_system
+                .after(player_attack_input)
+                .before(damage_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+        app.add_systems(
+            Update,
+            (parry_system, projectile_shield_system, shield_fx_system)
+                .chain()
+                .after(damage_system)
+                .before(hitstun_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Damage numbers and hit sparks for the hits processed this frame
+        app.add_systems(
+            Update,
+            (hit_feedback_system, damage_number_system, hit_spark_system)
+                .chain()
+                .after(damage_system)
+                .run_if(in_state(GameState::InGame)),
+        );
     }
 }
@@ -0,0 +1,755 @@
+//! Projectiles
+//!
+//! Shared projectile subsystem for player ranged attacks, enemies and bosses.
+//! A `Projectile` moves with a `ProjectileMotion` (straight, arcing, homing,
+//! sine or boomerang), hits hurtboxes hostile to its `Faction` (optionally
+//! piercing through several), and stops at or bounces off walls and ground
+//! with an impact effect. `ProjectileEmitter` fires spread volleys and bursts.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+
+use super::{
+    CombatConfig, DamageEvent, DamageType, Faction, FactionsConfig, FxAssets, HitImpact, Hurtbox,
+    Invincibility, Shield, ShieldEvent, ShieldOutcome, StatusEffect, spawn_fx,
+};
+use tropical_fox_common::{Collider, Ground, SpatialIndex, Wall};
+
+/// Upward knockback added to projectile hits so grounded targets react
+const PROJECTILE_KNOCKBACK_LIFT: f32 = 50.0;
+
+/// Distance at which a returning boomerang is caught by its owner
+const BOOMERANG_CATCH_DISTANCE: f32 = 16.0;
+
+/// How a projectile moves
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ProjectileMotion {
+    /// Constant velocity
+    #[default]
+    Straight,
+    /// Falls with gravity (pixels per second squared)
+    Arc { gravity: f32 },
+    /// Turns toward the nearest hostile target within `range` (turn rate in degrees per second)
+    Homing {
+        turn_rate: f32,
+        #[serde(default = "default_homing_range")]
+        range: f32,
+    },
+    /// Waves across its path (amplitude in pixels, frequency in waves per second)
+    Sine { amplitude: f32, frequency: f32 },
+    /// Flies out and returns to its owner after `return_after_secs`
+    Boomerang { return_after_secs: f32 },
+}
+
+fn default_homing_range() -> f32 {
+    400.0
+}
+
+/// How many projectiles a shot fires
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProjectileEmitter {
+    /// Projectiles per volley, fanned out over `spread_degrees`
+    #[serde(default = "default_emitter_count")]
+    pub count: u32,
+    /// Total angle of the fan (degrees)
+    #[serde(default)]
+    pub spread_degrees: f32,
+    /// Volleys per shot
+    #[serde(default = "default_emitter_count")]
+    pub burst: u32,
+    /// Time between volleys of a burst (seconds)
+    #[serde(default = "default_burst_interval_secs")]
+    pub burst_interval_secs: f32,
+}
+
+fn default_emitter_count() -> u32 {
+    1
+}
+
+fn default_burst_interval_secs() -> f32 {
+    0.15
+}
+
+impl Default for ProjectileEmitter {
+    fn default() -> Self {
+        Self {
+            count: default_emitter_count(),
+            spread_degrees: 0.0,
+            burst: default_emitter_count(),
+            burst_interval_secs: default_burst_interval_secs(),
+        }
+    }
+}
+
+impl ProjectileEmitter {
+    /// Directions of one volley, evenly fanned around `direction`
+    pub fn directions(&self, direction: Vec2) -> Vec<Vec2> {
+        let direction = direction.normalize_or(Vec2::X);
+        let count = self.count.max(1);
+        if count == 1 {
+            return vec![direction];
+        }
+
+        let spread = self.spread_degrees.to_radians();
+        let step = spread / (count - 1) as f32;
+        (0..count)
+            .map(|i| Vec2::from_angle(-spread / 2.0 + step * i as f32).rotate(direction))
+            .collect()
+    }
+}
+
+/// Everything needed to spawn a projectile (built from `players.ron` and `enemies.ron`)
+#[derive(Debug, Clone)]
+pub struct ProjectileSpawn {
+    /// Damage dealt on hit
+    pub damage: f32,
+    /// Elemental type of the damage
+    pub damage_type: DamageType,
+    /// Status effect applied on hit
+    pub status: Option<StatusEffect>,
+    /// Knockback force
+    pub knockback: f32,
+    /// Hit stop and screen shake on hit (None uses the combat config)
+    pub impact: Option<HitImpact>,
+    /// Launch speed (pixels per second)
+    pub speed: f32,
+    /// Lifetime (seconds)
+    pub lifetime_secs: f32,
+    /// Hitbox size
+    pub size: Vec2,
+    /// Movement pattern
+    pub motion: ProjectileMotion,
+    /// Targets passed through before stopping
+    pub pierce: u32,
+    /// Bounces off walls and ground before stopping
+    pub bounces: u32,
+    /// Whether walls and ground stop (or bounce) the projectile
+    pub wall_collision: bool,
+    /// Effect played while flying (name from `effects.ron`, plain square when None)
+    pub effect: Option<String>,
+    /// Effect played on impact
+    pub hit_effect: Option<String>,
+    /// Color of the plain square used without an effect
+    pub color: Color,
+}
+
+impl Default for ProjectileSpawn {
+    fn default() -> Self {
+        Self {
+            damage: 5.0,
+            damage_type: DamageType::Physical,
+            status: None,
+            knockback: 100.0,
+            impact: None,
+            speed: 200.0,
+            lifetime_secs: 5.0,
+            size: Vec2::splat(8.0),
+            motion: ProjectileMotion::Straight,
+            pierce: 0,
+            bounces: 0,
+            wall_collision: true,
+            effect: None,
+            hit_effect: None,
+            color: Color::srgb(1.0, 0.5, 0.0),
+        }
+    }
+}
+
+/// A projectile in flight
+#[derive(Component, Debug)]
+pub struct Projectile {
+    /// Damage dealt on hit
+    pub damage: f32,
+    /// Elemental type of the damage
+    pub damage_type: DamageType,
+    /// Status effect applied on hit
+    pub status: Option<StatusEffect>,
+    /// Knockback force
+    pub knockback: f32,
+    /// Hit stop and screen shake on hit (None uses the combat config)
+    pub impact: Option<HitImpact>,
+    /// Velocity of the projectile
+    pub velocity: Vec2,
+    /// Movement pattern
+    pub motion: ProjectileMotion,
+    /// Lifetime timer
+    pub lifetime: Timer,
+    /// Remaining targets to pass through
+    pub pierce: u32,
+    /// Remaining bounces
+    pub bounces: u32,
+    /// Whether walls and ground stop (or bounce) the projectile
+    pub wall_collision: bool,
+    /// Effect played on impact
+    pub hit_effect: Option<String>,
+    /// Entity that fired the projectile (never hit, boomerangs return to it)
+    pub owner: Option<Entity>,
+    /// Targets already hit (prevents multi-hit while piercing)
+    pub hit_entities: HashSet<Entity>,
+    /// Pushed back by a shield (no longer hits targets, still stopped by walls)
+    pub deflected: bool,
+    /// Position along the path, before the sine offset
+    pub path_position: Vec2,
+    /// Whether a boomerang is on its way back
+    pub returning: bool,
+}
+
+impl Projectile {
+    /// Create a projectile at `position` flying in `direction`
+    pub fn new(
+        spawn: &ProjectileSpawn,
+        position: Vec2,
+        direction: Vec2,
+        owner: Option<Entity>,
+    ) -> Self {
+        Self {
+            damage: spawn.damage,
+            damage_type: spawn.damage_type,
+            status: spawn.status,
+            knockback: spawn.knockback,
+            impact: spawn.impact,
+            velocity: direction.normalize_or(Vec2::X) * spawn.speed,
+            motion: spawn.motion,
+            lifetime: Timer::from_seconds(spawn.lifetime_secs, TimerMode::Once),
+            pierce: spawn.pierce,
+            bounces: spawn.bounces,
+            wall_collision: spawn.wall_collision,
+            hit_effect: spawn.hit_effect.clone(),
+            owner,
+            hit_entities: HashSet::new(),
+            deflected: false,
+            path_position: position,
+            returning: false,
+        }
+    }
+
+    /// Position after applying the motion's offset from the path
+    pub fn position(&self) -> Vec2 {
+        match self.motion {
+            ProjectileMotion::Sine {
+                amplitude,
+                frequency,
+            } => {
+                let normal = self.velocity.normalize_or_zero().perp();
+                let phase = TAU * frequency * self.lifetime.elapsed_secs();
+                self.path_position + normal * amplitude * phase.sin()
+            }
+            _ => self.path_position,
+        }
+    }
+}
+
+/// One volley of projectiles (spread), repeated `emitter.burst` times
+#[derive(Debug, Clone)]
+pub struct ProjectileVolley {
+    /// Projectile to spawn
+    pub projectile: ProjectileSpawn,
+    /// Spread and burst
+    pub emitter: ProjectileEmitter,
+    /// Aim direction
+    pub direction: Vec2,
+    /// Spawn offset from the shooter
+    pub offset: Vec2,
+    /// Side of the projectiles
+    pub faction: Faction,
+}
+
+impl ProjectileVolley {
+    /// Spawn one volley from a shooter at `shooter_pos`
+    fn spawn(
+        &self,
+        commands: &mut Commands,
+        fx_assets: &FxAssets,
+        shooter: Entity,
+        shooter_pos: Vec3,
+    ) {
+        let position = shooter_pos + self.offset.extend(0.0);
+        for direction in self.emitter.directions(self.direction) {
+            spawn_projectile(
+                commands,
+                fx_assets,
+                &self.projectile,
+                position,
+                direction,
+                self.faction,
+                Some(shooter),
+            );
+        }
+    }
+}
+
+/// Remaining volleys of a burst being fired by an entity
+#[derive(Component, Debug)]
+pub struct ProjectileBurst {
+    /// The volley to repeat
+    pub volley: ProjectileVolley,
+    /// Volleys left to fire
+    pub remaining: u32,
+    /// Time until the next volley
+    pub timer: Timer,
+}
+
+/// Spawn a single projectile
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    fx_assets: &FxAssets,
+    spawn: &ProjectileSpawn,
+    position: Vec3,
+    direction: Vec2,
+    faction: Faction,
+    owner: Option<Entity>,
+) -> Entity {
+    let projectile = Projectile::new(spawn, position.truncate(), direction, owner);
+    let flip_x = projectile.velocity.x < 0.0;
+
+    // Use the projectile effect if loaded, otherwise a plain colored square
+    let (sprite, animation) = match spawn.effect.as_deref().and_then(|name| fx_assets.get(name)) {
+        Some(sheet) => (sheet.sprite(flip_x), Some(sheet.animation(true))),
+        None => (
+            Sprite {
+                color: spawn.color,
+                custom_size: Some(spawn.size),
+                ..default()
+            },
+            None,
+        ),
+    };
+
+    let mut entity = commands.spawn((
+        Transform::from_translation(position),
+        Visibility::default(),
+        sprite,
+        projectile,
+        Collider::new(spawn.size),
+        faction,
+        Name::new(format!("{:?}Projectile", faction)),
+    ));
+
+    if let Some(animation) = animation {
+        entity.insert(animation);
+    }
+
+    entity.id()
+}
+
+/// Fire a volley now and schedule the rest of its burst
+pub fn fire_projectiles(
+    commands: &mut Commands,
+    fx_assets: &FxAssets,
+    volley: ProjectileVolley,
+    shooter: Entity,
+    shooter_pos: Vec3,
+) {
+    volley.spawn(commands, fx_assets, shooter, shooter_pos);
+
+    if volley.emitter.burst > 1 {
+        let timer = Timer::from_seconds(volley.emitter.burst_interval_secs, TimerMode::Repeating);
+        commands.entity(shooter).insert(ProjectileBurst {
+            remaining: volley.emitter.burst - 1,
+            volley,
+            timer,
+        });
+    }
+}
+
+/// Fire the remaining volleys of bursts from the shooter's current position
+pub fn projectile_burst_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    fx_assets: Res<FxAssets>,
+    mut query: Query<(Entity, &Transform, &mut ProjectileBurst)>,
+) {
+    for (entity, transform, mut burst) in query.iter_mut() {
+        burst.timer.tick(time.delta());
+        if !burst.timer.just_finished() {
+            continue;
+        }
+
+        burst
+            .volley
+            .spawn(&mut commands, &fx_assets, entity, transform.translation);
+        burst.remaining -= 1;
+        if burst.remaining == 0 {
+            commands.entity(entity).remove::<ProjectileBurst>();
+        }
+    }
+}
+
+/// Rotate `velocity` toward `desired` by at most `max_angle` radians (keeps the speed)
+pub fn turn_towards(velocity: Vec2, desired: Vec2, max_angle: f32) -> Vec2 {
+    if desired == Vec2::ZERO || velocity == Vec2::ZERO {
+        return velocity;
+    }
+    let angle = velocity.angle_to(desired).clamp(-max_angle, max_angle);
+    Vec2::from_angle(angle).rotate(velocity)
+}
+
+/// Move projectiles by their motion and despawn them when their lifetime expires
+#[allow(clippy::type_complexity)]
+pub fn projectile_movement_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    factions: Res<FactionsConfig>,
+    index: Res<SpatialIndex>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Projectile,
+        Option<&Faction>,
+        Option<&mut Sprite>,
+    )>,
+    target_query: Query<(&Transform, Option<&Faction>), (With<Hurtbox>, Without<Projectile>)>,
+    owner_query: Query<&Transform, Without<Projectile>>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut projectile, faction, sprite) in query.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        match projectile.motion {
+            ProjectileMotion::Straight | ProjectileMotion::Sine { .. } => {}
+            ProjectileMotion::Arc { gravity } => {
+                projectile.velocity.y -= gravity * dt;
+            }
+            ProjectileMotion::Homing { turn_rate, range } => {
+                let faction = faction.copied().unwrap_or_default();
+                let search = Rect::from_center_half_size(position, Vec2::splat(range));
+                let target = index
+                    .bodies
+                    .query(search)
+                    .into_iter()
+                    .filter_map(|target| target_query.get(target).ok())
+                    .filter(|(_, target_faction)| {
+                        factions.is_hostile(faction, target_faction.copied().unwrap_or_default())
+                    })
+                    .map(|(target_transform, _)| target_transform.translation.truncate())
+                    .filter(|target_pos| target_pos.distance(position) <= range)
+                    .min_by(|a, b| {
+                        a.distance_squared(position)
+                            .total_cmp(&b.distance_squared(position))
+                    });
+                if let Some(target_pos) = target {
+                    projectile.velocity = turn_towards(
+                        projectile.velocity,
+                        target_pos - position,
+                        turn_rate.to_radians() * dt,
+                    );
+                }
+            }
+            ProjectileMotion::Boomerang { return_after_secs } => {
+                if !projectile.returning && projectile.lifetime.elapsed_secs() >= return_after_secs
+                {
+                    // Can hit the same targets again on the way back
+                    projectile.returning = true;
+                    projectile.hit_entities.clear();
+                    projectile.velocity = -projectile.velocity;
+                }
+
+                if projectile.returning
+                    && let Some(owner_pos) = projectile
+                        .owner
+                        .and_then(|owner| owner_query.get(owner).ok())
+                        .map(|owner| owner.translation.truncate())
+                {
+                    let to_owner = owner_pos - position;
+                    let speed = projectile.velocity.length();
+                    if to_owner.length() <= BOOMERANG_CATCH_DISTANCE.max(speed * dt) {
+                        commands.entity(entity).despawn();
+                        continue;
+                    }
+                    projectile.velocity = to_owner.normalize() * speed;
+                }
+            }
+        }
+
+        let velocity = projectile.velocity;
+        projectile.path_position += velocity * dt;
+        let position = projectile.position();
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+
+        if let Some(mut sprite) = sprite
+            && velocity.x != 0.0
+        {
+            sprite.flip_x = velocity.x < 0.0;
+        }
+    }
+}
+
+/// Overlap between two colliders, or None if they don't touch
+///
+/// Returns the smallest push that separates `a` from `b`.
+fn collider_overlap(
+    a_pos: Vec2,
+    a_collider: &Collider,
+    b_pos: Vec2,
+    b_collider: &Collider,
+) -> Option<Vec2> {
+    let delta = (a_pos + a_collider.offset) - (b_pos + b_collider.offset);
+    let overlap = (a_collider.size + b_collider.size) / 2.0 - delta.abs();
+    if overlap.x <= 0.0 || overlap.y <= 0.0 {
+        return None;
+    }
+
+    Some(if overlap.x < overlap.y {
+        Vec2::new(overlap.x * delta.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, overlap.y * delta.y.signum())
+    })
+}
+
+/// Reflect `velocity` off a surface separated by `push`
+pub fn bounce_velocity(velocity: Vec2, push: Vec2) -> Vec2 {
+    let mut velocity = velocity;
+    if push.x * velocity.x < 0.0 {
+        velocity.x = -velocity.x;
+    }
+    if push.y * velocity.y < 0.0 {
+        velocity.y = -velocity.y;
+    }
+    velocity
+}
+
+/// Projectile collision system - hit hostile hurtboxes, stop at or bounce off walls and ground
+///
+/// Projectiles that hit a raised shield are kept alive for
+/// `projectile_shield_system` to push back or reflect. Targets and level
+/// geometry near the projectile are found through the `SpatialIndex`.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn projectile_collision_system(
+    mut commands: Commands,
+    fx_assets: Res<FxAssets>,
+    factions: Res<FactionsConfig>,
+    index: Res<SpatialIndex>,
+    mut projectile_query: Query<(
+        Entity,
+        &mut Transform,
+        &Collider,
+        &mut Projectile,
+        Option<&Faction>,
+    )>,
+    target_query: Query<
+        (&Transform, &Collider, Option<&Faction>, Option<&Shield>),
+        (With<Hurtbox>, Without<Invincibility>, Without<Projectile>),
+    >,
+    solid_query: Query<
+        (&Transform, &Collider),
+        (Or<(With<Wall>, With<Ground>)>, Without<Projectile>),
+    >,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (proj_entity, mut proj_transform, proj_collider, mut projectile, faction) in
+        projectile_query.iter_mut()
+    {
+        let proj_pos = proj_transform.translation.truncate();
+        let proj_bounds = proj_collider.aabb(proj_pos);
+        let faction = faction.copied().unwrap_or_default();
+        let mut stopped = false;
+
+        // Invincible targets are skipped by the query filter, and a deflected
+        // projectile hits no one but is still stopped by walls
+        let candidates = if projectile.deflected {
+            Vec::new()
+        } else {
+            index.bodies.query(proj_bounds)
+        };
+        for target_entity in candidates {
+            let Ok((target_transform, target_collider, target_faction, shield)) =
+                target_query.get(target_entity)
+            else {
+                continue;
+            };
+            let target_pos = target_transform.translation.truncate();
+            if projectile.owner == Some(target_entity)
+                || projectile.hit_entities.contains(&target_entity)
+                || !factions.is_hostile(faction, target_faction.copied().unwrap_or_default())
+                || collider_overlap(proj_pos, proj_collider, target_pos, target_collider).is_none()
+            {
+                continue;
+            }
+
+            let direction = projectile.velocity.normalize_or_zero();
+            damage_events.write(DamageEvent {
+                target: target_entity,
+                damage: projectile.damage,
+                damage_type: projectile.damage_type,
+                knockback: direction * projectile.knockback + Vec2::Y * PROJECTILE_KNOCKBACK_LIFT,
+                attacker: Some(proj_entity),
+                impact: projectile.impact,
+                status: projectile.status,
+                critical: false,
+            });
+            projectile.hit_entities.insert(target_entity);
+
+            // A shield will push back or reflect the projectile
+            if shield.is_some_and(|shield| shield.outcome(target_pos, Some(proj_pos)).is_some()) {
+                break;
+            }
+
+            if projectile.pierce > 0 {
+                projectile.pierce -= 1;
+            } else {
+                stopped = true;
+                break;
+            }
+        }
+
+        if !stopped && projectile.wall_collision {
+            let push = index
+                .solids
+                .query(proj_bounds)
+                .into_iter()
+                .filter_map(|solid| solid_query.get(solid).ok())
+                .find_map(|(solid_transform, solid_collider)| {
+                    collider_overlap(
+                        proj_pos,
+                        proj_collider,
+                        solid_transform.translation.truncate(),
+                        solid_collider,
+                    )
+                });
+
+            if let Some(push) = push {
+                if projectile.bounces > 0 {
+                    projectile.bounces -= 1;
+                    projectile.velocity = bounce_velocity(projectile.velocity, push);
+                    projectile.path_position += push;
+                    proj_transform.translation += push.extend(0.0);
+                } else {
+                    stopped = true;
+                }
+            }
+        }
+
+        if !stopped {
+            continue;
+        }
+
+        if let Some(effect) = &projectile.hit_effect {
+            spawn_fx(
+                &mut commands,
+                &fx_assets,
+                effect,
+                proj_transform.translation,
+                projectile.velocity.x < 0.0,
+            );
+        }
+
+        commands.entity(proj_entity).despawn();
+    }
+}
+
+/// Push back projectiles blocked by a shield and reflect parried ones to the defender's side
+pub fn projectile_shield_system(
+    mut commands: Commands,
+    config: Res<CombatConfig>,
+    mut shield_events: MessageReader<ShieldEvent>,
+    mut projectile_query: Query<(&mut Projectile, Option<&mut Sprite>)>,
+    faction_query: Query<&Faction>,
+) {
+    for event in shield_events.read() {
+        let Some(attacker) = event.attacker else {
+            continue;
+        };
+        let Ok((mut projectile, sprite)) = projectile_query.get_mut(attacker) else {
+            continue;
+        };
+
+        match event.outcome {
+            ShieldOutcome::Blocked => {
+                projectile.velocity =
+                    -projectile.velocity * config.shield.pushback_speed_multiplier;
+                projectile.deflected = true;
+                if let Some(mut sprite) = sprite {
+                    sprite.color = sprite.color.with_alpha(0.5);
+                }
+            }
+            ShieldOutcome::Parried => {
+                // The projectile switches sides and flies back at its shooter
+                projectile.velocity = -projectile.velocity * config.shield.reflect_speed_multiplier;
+                projectile.damage *= config.shield.reflect_damage_multiplier;
+                projectile.owner = Some(event.defender);
+                projectile.hit_entities.clear();
+                projectile.returning = false;
+                let faction = faction_query
+                    .get(event.defender)
+                    .copied()
+                    .unwrap_or_default();
+                commands.entity(attacker).insert(faction);
+                if let Some(mut sprite) = sprite {
+                    sprite.color = Color::srgb(0.4, 0.9, 1.0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emitter_spread_directions() {
+        let emitter = ProjectileEmitter {
+            count: 3,
+            spread_degrees: 90.0,
+            ..default()
+        };
+        let directions = emitter.directions(Vec2::X);
+
+        assert_eq!(directions.len(), 3);
+        assert!(directions[1].abs_diff_eq(Vec2::X, 1e-5));
+        assert!(directions[0].abs_diff_eq(Vec2::from_angle(-TAU / 8.0), 1e-5));
+        assert!(directions[2].abs_diff_eq(Vec2::from_angle(TAU / 8.0), 1e-5));
+    }
+
+    #[test]
+    fn test_turn_towards_limits_angle() {
+        let velocity = Vec2::new(100.0, 0.0);
+        let turned = turn_towards(velocity, Vec2::Y, 0.1);
+
+        assert!((turned.length() - 100.0).abs() < 1e-3);
+        assert!((Vec2::X.angle_to(turned) - 0.1).abs() < 1e-5);
+        // Small corrections are applied fully
+        assert!(
+            turn_towards(velocity, Vec2::new(1.0, 0.01), 0.1)
+                .normalize()
+                .abs_diff_eq(Vec2::new(1.0, 0.01).normalize(), 1e-5)
+        );
+    }
+
+    #[test]
+    fn test_bounce_off_ground() {
+        let projectile = Collider::new(Vec2::splat(8.0));
+        let ground = Collider::new(Vec2::new(200.0, 32.0));
+
+        // Falling onto the top of the ground
+        let push =
+            collider_overlap(Vec2::new(0.0, 18.0), &projectile, Vec2::ZERO, &ground).unwrap();
+        assert_eq!(push, Vec2::new(0.0, 2.0));
+        assert_eq!(
+            bounce_velocity(Vec2::new(50.0, -100.0), push),
+            Vec2::new(50.0, 100.0)
+        );
+        assert!(collider_overlap(Vec2::new(0.0, 40.0), &projectile, Vec2::ZERO, &ground).is_none());
+    }
+
+    #[test]
+    fn test_motion_parse() {
+        let motion: ProjectileMotion = ron::from_str("Homing(turn_rate: 180.0)").unwrap();
+        assert_eq!(
+            motion,
+            ProjectileMotion::Homing {
+                turn_rate: 180.0,
+                range: 400.0
+            }
+        );
+    }
+}
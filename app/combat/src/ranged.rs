@@ -0,0 +1,262 @@
+//! Player ranged attacks
+//!
+//! Ranged attacks fire `Faction::Player` projectiles (see `projectile.rs`) in
+//! the facing direction. Each shot costs ammo that regenerates over time.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    DamageType, Faction, FxAssets, ProjectileEmitter, ProjectileMotion, ProjectileSpawn,
+    ProjectileVolley, StatusEffect, fire_projectiles,
+};
+use tropical_fox_common::{Player, PlayerInput};
+
+/// Distance in front of the player where projectiles spawn
+const PROJECTILE_SPAWN_OFFSET: f32 = 20.0;
+
+/// Ranged attack definition (configured per character in `players.ron`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangedAttackConfig {
+    /// Damage dealt on hit
+    pub damage: f32,
+    /// Elemental type of the damage
+    #[serde(default)]
+    pub damage_type: DamageType,
+    /// Status effect applied on hit
+    #[serde(default)]
+    pub status: Option<StatusEffect>,
+    /// Knockback force on hit
+    pub knockback: f32,
+    /// Projectile speed (pixels per second)
+    pub speed: f32,
+    /// Projectile lifetime (seconds)
+    pub lifetime_secs: f32,
+    /// Projectile hitbox size (width, height)
+    pub hitbox_size: (f32, f32),
+    /// Cooldown between shots (seconds)
+    pub cooldown_secs: f32,
+    /// Maximum ammo
+    pub max_ammo: u32,
+    /// Ammo consumed per shot
+    #[serde(default = "default_ammo_cost")]
+    pub ammo_cost: u32,
+    /// Time to regenerate one ammo (seconds, 0 = no regeneration)
+    #[serde(default)]
+    pub ammo_regen_secs: f32,
+    /// Effect played while the projectile flies (name from `effects.ron`)
+    #[serde(default)]
+    pub projectile_effect: Option<String>,
+    /// Effect played on impact (name from `effects.ron`)
+    #[serde(default)]
+    pub hit_effect: Option<String>,
+    /// Movement pattern (straight when omitted)
+    #[serde(default)]
+    pub motion: ProjectileMotion,
+    /// Number of targets the projectile passes through before stopping
+    #[serde(default)]
+    pub pierce: u32,
+    /// Number of times the projectile bounces off walls and ground
+    #[serde(default)]
+    pub bounces: u32,
+    /// Projectiles per shot (spread and burst)
+    #[serde(default)]
+    pub emitter: ProjectileEmitter,
+}
+
+impl RangedAttackConfig {
+    /// Projectile fired by this attack
+    pub fn projectile(&self) -> ProjectileSpawn {
+        ProjectileSpawn {
+            damage: self.damage,
+            damage_type: self.damage_type,
+            status: self.status,
+            knockback: self.knockback,
+            impact: None,
+            speed: self.speed,
+            lifetime_secs: self.lifetime_secs,
+            size: Vec2::new(self.hitbox_size.0, self.hitbox_size.1),
+            motion: self.motion,
+            pierce: self.pierce,
+            bounces: self.bounces,
+            wall_collision: true,
+            effect: self.projectile_effect.clone(),
+            hit_effect: self.hit_effect.clone(),
+            color: Color::srgb(1.0, 0.6, 0.1),
+        }
+    }
+}
+
+fn default_ammo_cost() -> u32 {
+    1
+}
+
+/// Ranged attack state for an entity
+#[derive(Component, Debug)]
+pub struct RangedAttack {
+    /// Ranged attack definition
+    pub config: RangedAttackConfig,
+    /// Current ammo
+    pub ammo: u32,
+    /// Cooldown between shots
+    pub cooldown: Timer,
+    /// Ammo regeneration timer
+    pub regen: Timer,
+}
+
+impl RangedAttack {
+    /// Create a ranged attack with full ammo, ready to fire
+    pub fn new(config: RangedAttackConfig) -> Self {
+        let mut cooldown = Timer::from_seconds(config.cooldown_secs, TimerMode::Once);
+        cooldown.tick(std::time::Duration::from_secs_f32(config.cooldown_secs));
+
+        Self {
+            ammo: config.max_ammo,
+            regen: Timer::from_seconds(config.ammo_regen_secs, TimerMode::Repeating),
+            cooldown,
+            config,
+        }
+    }
+
+    /// Check if a shot can be fired (cooldown finished and enough ammo)
+    pub fn can_fire(&self) -> bool {
+        self.cooldown.is_finished() && self.ammo >= self.config.ammo_cost
+    }
+
+    /// Consume ammo and restart the cooldown
+    pub fn fire(&mut self) {
+        self.ammo -= self.config.ammo_cost;
+        self.cooldown.reset();
+    }
+
+    /// Advance cooldown and ammo regeneration
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        self.cooldown.tick(delta);
+
+        if self.config.ammo_regen_secs <= 0.0 || self.ammo >= self.config.max_ammo {
+            self.regen.reset();
+            return;
+        }
+
+        self.regen.tick(delta);
+        let regenerated = self.regen.times_finished_this_tick();
+        self.ammo = (self.ammo + regenerated).min(self.config.max_ammo);
+    }
+}
+
+/// Update ranged attack cooldowns and ammo regeneration
+pub fn update_ranged_attack(time: Res<Time>, mut query: Query<&mut RangedAttack>) {
+    for mut ranged in query.iter_mut() {
+        ranged.tick(time.delta());
+    }
+}
+
+/// Handle player ranged attack input
+#[allow(clippy::type_complexity)]
+pub fn player_ranged_attack_input(
+    mut commands: Commands,
+    fx_assets: Res<FxAssets>,
+    mut player_query: Query<(
+        Entity,
+        &Transform,
+        &Player,
+        &PlayerInput,
+        &mut RangedAttack,
+        Option<&Faction>,
+    )>,
+) {
+    for (entity, transform, player, input, mut ranged, faction) in player_query.iter_mut() {
+        if !input.ranged.just_pressed || !ranged.can_fire() {
+            continue;
+        }
+        ranged.fire();
+
+        let facing_dir = if player.facing_right { 1.0 } else { -1.0 };
+        let volley = ProjectileVolley {
+            projectile: ranged.config.projectile(),
+            emitter: ranged.config.emitter,
+            direction: Vec2::new(facing_dir, 0.0),
+            offset: Vec2::new(PROJECTILE_SPAWN_OFFSET * facing_dir, 0.0),
+            faction: faction.copied().unwrap_or(Faction::Player),
+        };
+        fire_projectiles(
+            &mut commands,
+            &fx_assets,
+            volley,
+            entity,
+            transform.translation,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_config() -> RangedAttackConfig {
+        RangedAttackConfig {
+            damage: 8.0,
+            damage_type: DamageType::Fire,
+            status: None,
+            knockback: 80.0,
+            speed: 350.0,
+            lifetime_secs: 1.5,
+            hitbox_size: (16.0, 12.0),
+            cooldown_secs: 0.4,
+            max_ammo: 3,
+            ammo_cost: 1,
+            ammo_regen_secs: 2.0,
+            projectile_effect: None,
+            hit_effect: None,
+            motion: ProjectileMotion::Straight,
+            pierce: 0,
+            bounces: 0,
+            emitter: ProjectileEmitter::default(),
+        }
+    }
+
+    #[test]
+    fn test_ranged_attack_ammo_and_cooldown() {
+        let mut ranged = RangedAttack::new(test_config());
+        assert!(ranged.can_fire());
+
+        ranged.fire();
+        assert_eq!(ranged.ammo, 2);
+        assert!(!ranged.can_fire()); // Cooldown running
+
+        ranged.tick(Duration::from_secs_f32(0.5));
+        assert!(ranged.can_fire());
+    }
+
+    #[test]
+    fn test_ranged_attack_out_of_ammo() {
+        let mut ranged = RangedAttack::new(RangedAttackConfig {
+            ammo_regen_secs: 0.0,
+            ..test_config()
+        });
+
+        for _ in 0..3 {
+            ranged.fire();
+            ranged.tick(Duration::from_secs_f32(0.5));
+        }
+
+        assert_eq!(ranged.ammo, 0);
+        assert!(!ranged.can_fire());
+    }
+
+    #[test]
+    fn test_ranged_attack_ammo_regen() {
+        let mut ranged = RangedAttack::new(test_config());
+        ranged.fire();
+        ranged.fire();
+        assert_eq!(ranged.ammo, 1);
+
+        ranged.tick(Duration::from_secs_f32(2.1));
+        assert_eq!(ranged.ammo, 2);
+
+        // Regeneration stops at max ammo
+        ranged.tick(Duration::from_secs_f32(10.0));
+        assert_eq!(ranged.ammo, 3);
+    }
+}
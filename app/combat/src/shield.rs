@@ -0,0 +1,404 @@
+//! Shield and parry
+//!
+//! Holding block raises a shield that reduces damage from the front and
+//! pushes back projectiles. Pressing block right before a hit opens a short
+//! parry window: the hit is negated, melee attackers are staggered and enemy
+//! projectiles are reflected. `damage_system` resolves shields before damage
+//! is applied and reports the outcome with a `ShieldEvent`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AttackHitbox, Attacking, ChargingAttack, CombatConfig, FxAssets, Health, HitStopEvent, Hitstun,
+    InKnockback, Knockback, spawn_fx,
+};
+use tropical_fox_common::{Player, PlayerInput};
+
+/// Effect shown while the shield is raised (name from `assets/config/effects.ron`)
+pub const SHIELD_EFFECT: &str = "energy_shield";
+
+/// Distance of the shield effect in front of its owner
+const SHIELD_FX_OFFSET: f32 = 18.0;
+
+/// Shield and parry tuning (`shield` in `assets/config/combat.ron`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShieldConfig {
+    /// Fraction of frontal damage blocked (1.0 = no damage)
+    #[serde(default = "default_damage_reduction")]
+    pub damage_reduction: f32,
+    /// Fraction of frontal knockback blocked
+    #[serde(default = "default_knockback_reduction")]
+    pub knockback_reduction: f32,
+    /// Movement speed multiplier while the shield is raised
+    #[serde(default = "default_move_speed_multiplier")]
+    pub move_speed_multiplier: f32,
+    /// Parry window after pressing block (seconds)
+    #[serde(default = "default_parry_window_secs")]
+    pub parry_window_secs: f32,
+    /// Time after a press before the next press can parry again (prevents mashing)
+    #[serde(default = "default_parry_cooldown_secs")]
+    pub parry_cooldown_secs: f32,
+    /// Hitstun applied to a parried melee attacker (seconds)
+    #[serde(default = "default_parry_stagger_secs")]
+    pub parry_stagger_secs: f32,
+    /// Knockback applied to a parried melee attacker
+    #[serde(default = "default_parry_knockback")]
+    pub parry_knockback: (f32, f32),
+    /// Hit stop on a successful parry (seconds)
+    #[serde(default = "default_parry_hitstop_secs")]
+    pub parry_hitstop_secs: f32,
+    /// Speed multiplier of projectiles pushed back by a block
+    #[serde(default = "default_pushback_speed_multiplier")]
+    pub pushback_speed_multiplier: f32,
+    /// Speed multiplier of parried projectiles
+    #[serde(default = "default_reflect_speed_multiplier")]
+    pub reflect_speed_multiplier: f32,
+    /// Damage multiplier of parried projectiles
+    #[serde(default = "default_reflect_damage_multiplier")]
+    pub reflect_damage_multiplier: f32,
+}
+
+fn default_damage_reduction() -> f32 {
+    0.75
+}
+
+fn default_knockback_reduction() -> f32 {
+    0.5
+}
+
+fn default_move_speed_multiplier() -> f32 {
+    0.3
+}
+
+fn default_parry_window_secs() -> f32 {
+    0.15
+}
+
+fn default_parry_cooldown_secs() -> f32 {
+    0.5
+}
+
+fn default_parry_stagger_secs() -> f32 {
+    0.8
+}
+
+fn default_parry_knockback() -> (f32, f32) {
+    (220.0, 80.0)
+}
+
+fn default_parry_hitstop_secs() -> f32 {
+    0.12
+}
+
+fn default_pushback_speed_multiplier() -> f32 {
+    0.5
+}
+
+fn default_reflect_speed_multiplier() -> f32 {
+    1.5
+}
+
+fn default_reflect_damage_multiplier() -> f32 {
+    2.0
+}
+
+impl Default for ShieldConfig {
+    fn default() -> Self {
+        Self {
+            damage_reduction: default_damage_reduction(),
+            knockback_reduction: default_knockback_reduction(),
+            move_speed_multiplier: default_move_speed_multiplier(),
+            parry_window_secs: default_parry_window_secs(),
+            parry_cooldown_secs: default_parry_cooldown_secs(),
+            parry_stagger_secs: default_parry_stagger_secs(),
+            parry_knockback: default_parry_knockback(),
+            parry_hitstop_secs: default_parry_hitstop_secs(),
+            pushback_speed_multiplier: default_pushback_speed_multiplier(),
+            reflect_speed_multiplier: default_reflect_speed_multiplier(),
+            reflect_damage_multiplier: default_reflect_damage_multiplier(),
+        }
+    }
+}
+
+/// Result of a hit against a raised shield
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShieldOutcome {
+    /// Hit during the parry window: negated, attacker staggered or reflected
+    Parried,
+    /// Hit from the front: damage and knockback reduced
+    Blocked,
+}
+
+/// Shield of an entity that can block (players)
+#[derive(Component, Debug)]
+pub struct Shield {
+    /// Whether the shield is raised
+    pub raised: bool,
+    /// Direction the shield faces
+    pub facing_right: bool,
+    /// Remaining parry window
+    pub parry_window: Timer,
+    /// Time until a press can parry again
+    pub parry_cooldown: Timer,
+}
+
+impl Default for Shield {
+    fn default() -> Self {
+        let mut parry_window = Timer::from_seconds(0.0, TimerMode::Once);
+        parry_window.tick(std::time::Duration::ZERO);
+        let mut parry_cooldown = Timer::from_seconds(0.0, TimerMode::Once);
+        parry_cooldown.tick(std::time::Duration::ZERO);
+
+        Self {
+            raised: false,
+            facing_right: true,
+            parry_window,
+            parry_cooldown,
+        }
+    }
+}
+
+impl Shield {
+    /// Raise the shield; a fresh press opens the parry window unless on cooldown
+    pub fn raise(&mut self, just_pressed: bool, config: &ShieldConfig) {
+        if just_pressed && self.parry_cooldown.is_finished() {
+            self.parry_window = Timer::from_seconds(config.parry_window_secs, TimerMode::Once);
+        }
+        if just_pressed {
+            self.parry_cooldown = Timer::from_seconds(config.parry_cooldown_secs, TimerMode::Once);
+        }
+        self.raised = true;
+    }
+
+    /// Lower the shield (closes the parry window)
+    pub fn lower(&mut self) {
+        self.raised = false;
+        let remaining = self.parry_window.remaining();
+        self.parry_window.tick(remaining);
+    }
+
+    /// Whether a hit now would be parried
+    pub fn is_parrying(&self) -> bool {
+        self.raised && !self.parry_window.is_finished()
+    }
+
+    /// Whether the raised shield faces an attacker at `attacker_pos`
+    pub fn covers(&self, defender_pos: Vec2, attacker_pos: Vec2) -> bool {
+        let facing_dir = if self.facing_right { 1.0 } else { -1.0 };
+        self.raised && (attacker_pos.x - defender_pos.x) * facing_dir >= 0.0
+    }
+
+    /// Outcome of a hit from an attacker at `attacker_pos` (None if the shield doesn't help)
+    pub fn outcome(&self, defender_pos: Vec2, attacker_pos: Option<Vec2>) -> Option<ShieldOutcome> {
+        let attacker_pos = attacker_pos?;
+        if !self.covers(defender_pos, attacker_pos) {
+            None
+        } else if self.is_parrying() {
+            Some(ShieldOutcome::Parried)
+        } else {
+            Some(ShieldOutcome::Blocked)
+        }
+    }
+}
+
+/// Event fired by `damage_system` when a hit is blocked or parried
+#[derive(bevy::ecs::prelude::Message, Debug, Clone)]
+pub struct ShieldEvent {
+    /// The entity holding the shield
+    pub defender: Entity,
+    /// The entity that dealt the hit (hitbox, projectile or enemy body)
+    pub attacker: Option<Entity>,
+    /// Whether the hit was blocked or parried
+    pub outcome: ShieldOutcome,
+}
+
+/// Shield effect following its owner
+#[derive(Component, Debug)]
+pub struct ShieldFx {
+    /// The entity holding the shield
+    pub owner: Entity,
+}
+
+/// Raise and lower player shields from the block button
+///
+/// Attacking, charging or being knocked back lowers the shield.
+#[allow(clippy::type_complexity)]
+pub fn shield_input_system(
+    time: Res<Time>,
+    config: Res<CombatConfig>,
+    mut query: Query<(
+        &PlayerInput,
+        &Player,
+        &mut Shield,
+        Has<Attacking>,
+        Has<ChargingAttack>,
+        Has<InKnockback>,
+    )>,
+) {
+    for (input, player, mut shield, attacking, charging, in_knockback) in query.iter_mut() {
+        shield.parry_window.tick(time.delta());
+        shield.parry_cooldown.tick(time.delta());
+        shield.facing_right = player.facing_right;
+
+        if input.block.pressed && !attacking && !charging && !in_knockback {
+            shield.raise(input.block.just_pressed, &config.shield);
+        } else if shield.raised {
+            shield.lower();
+        }
+    }
+}
+
+/// Show the shield effect in front of entities with a raised shield
+pub fn shield_fx_system(
+    mut commands: Commands,
+    fx_assets: Res<FxAssets>,
+    shield_query: Query<(Entity, &Shield, &Transform), Without<ShieldFx>>,
+    mut fx_query: Query<(Entity, &ShieldFx, &mut Transform, &mut Sprite)>,
+) {
+    // Move or remove existing effects
+    let mut shown = Vec::new();
+    for (fx_entity, fx, mut fx_transform, mut sprite) in fx_query.iter_mut() {
+        match shield_query.get(fx.owner) {
+            Ok((_, shield, owner_transform)) if shield.raised => {
+                let facing_dir = if shield.facing_right { 1.0 } else { -1.0 };
+                fx_transform.translation = owner_transform.translation
+                    + Vec3::new(SHIELD_FX_OFFSET * facing_dir, 0.0, 1.0);
+                sprite.flip_x = !shield.facing_right;
+                // Brighter during the parry window
+                sprite.color = if shield.is_parrying() {
+                    Color::srgb(1.5, 1.5, 1.5)
+                } else {
+                    Color::WHITE
+                };
+                shown.push(fx.owner);
+            }
+            _ => commands.entity(fx_entity).despawn(),
+        }
+    }
+
+    // Spawn effects for newly raised shields
+    let Some(sheet) = fx_assets.get(SHIELD_EFFECT) else {
+        return;
+    };
+    for (entity, shield, transform) in shield_query.iter() {
+        if !shield.raised || shown.contains(&entity) {
+            continue;
+        }
+        let facing_dir = if shield.facing_right { 1.0 } else { -1.0 };
+        commands.spawn((
+            sheet.sprite(!shield.facing_right),
+            Transform::from_translation(
+                transform.translation + Vec3::new(SHIELD_FX_OFFSET * facing_dir, 0.0, 1.0),
+            ),
+            sheet.animation(true),
+            ShieldFx { owner: entity },
+            Name::new("ShieldFx"),
+        ));
+    }
+}
+
+/// Stagger melee attackers whose hit was parried
+///
+/// The attacker is the owner of a parried hitbox, or the enemy itself for
+/// contact damage. Projectiles are handled by their own systems.
+pub fn parry_system(
+    mut commands: Commands,
+    config: Res<CombatConfig>,
+    fx_assets: Res<FxAssets>,
+    mut shield_events: MessageReader<ShieldEvent>,
+    hitbox_query: Query<&AttackHitbox>,
+    body_query: Query<&Transform, With<Health>>,
+    mut hitstop_events: MessageWriter<HitStopEvent>,
+) {
+    for event in shield_events.read() {
+        if event.outcome != ShieldOutcome::Parried {
+            continue;
+        }
+
+        hitstop_events.write(HitStopEvent {
+            duration_secs: config.shield.parry_hitstop_secs,
+        });
+
+        let Ok(defender_transform) = body_query.get(event.defender) else {
+            continue;
+        };
+        spawn_fx(
+            &mut commands,
+            &fx_assets,
+            SHIELD_EFFECT,
+            defender_transform.translation + Vec3::Z,
+            false,
+        );
+
+        let Some(attacker) = event.attacker.map(|attacker| {
+            hitbox_query
+                .get(attacker)
+                .map_or(attacker, |hitbox| hitbox.owner)
+        }) else {
+            continue;
+        };
+        let Ok(attacker_transform) = body_query.get(attacker) else {
+            continue;
+        };
+
+        // Knock the attacker away from the defender
+        let direction =
+            (attacker_transform.translation.x - defender_transform.translation.x).signum();
+        let (knockback_x, knockback_y) = config.shield.parry_knockback;
+        commands.entity(attacker).insert((
+            Hitstun::new(config.shield.parry_stagger_secs),
+            Knockback::new(
+                Vec2::new(knockback_x * direction, knockback_y),
+                config.knockback_secs,
+            ),
+            InKnockback,
+        ));
+
+        info!("Parried {:?}", attacker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shield_covers_front_only() {
+        let mut shield = Shield::default();
+        let config = ShieldConfig::default();
+        let defender = Vec2::ZERO;
+
+        assert_eq!(shield.outcome(defender, Some(Vec2::new(20.0, 0.0))), None);
+
+        shield.raise(false, &config);
+        assert_eq!(
+            shield.outcome(defender, Some(Vec2::new(20.0, 0.0))),
+            Some(ShieldOutcome::Blocked)
+        );
+        // Hits from behind and unknown attackers aren't blocked
+        assert_eq!(shield.outcome(defender, Some(Vec2::new(-20.0, 0.0))), None);
+        assert_eq!(shield.outcome(defender, None), None);
+    }
+
+    #[test]
+    fn test_parry_window() {
+        let mut shield = Shield::default();
+        let config = ShieldConfig::default();
+
+        shield.raise(true, &config);
+        assert!(shield.is_parrying());
+
+        // The window closes over time but the block stays up
+        shield
+            .parry_window
+            .tick(std::time::Duration::from_secs_f32(config.parry_window_secs));
+        assert!(!shield.is_parrying());
+        assert!(shield.raised);
+
+        // Pressing again during the cooldown doesn't open a new window
+        shield.lower();
+        shield.raise(true, &config);
+        assert!(!shield.is_parrying());
+    }
+}
@@ -0,0 +1,632 @@
+//! Status effects (burn, poison, freeze, stun)
+//!
+//! Timed effects applied by hits (`DamageEvent::status`), status hazards or a
+//! `StatusEffectEvent`. Burn and poison deal damage over time, freeze slows
+//! movement and stun blocks AI and player input. Each entity keeps one entry
+//! per effect kind; reapplying follows the kind's `StatusStacking` rule, which
+//! can be overridden per enemy in `assets/config/enemies.ron` along with
+//! immunities.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    DamageDealtEvent, DamageFlash, DamageResistances, DamageType, DeathEvent, Faction,
+    FactionsConfig, Health, HitEffectiveness, Invincibility, Invulnerable,
+};
+use tropical_fox_common::{Collider, Gravity, Player, SpatialIndex, Velocity};
+
+/// Seconds between damage-over-time ticks
+pub const STATUS_TICK_SECS: f32 = 0.5;
+
+/// Kind of status effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StatusEffectKind {
+    /// Fire damage over time
+    Burn,
+    /// Poison damage over time
+    Poison,
+    /// Slowed movement
+    Freeze,
+    /// No AI or player input
+    Stun,
+}
+
+impl StatusEffectKind {
+    /// Potency used when an effect does not specify one
+    pub fn default_potency(&self) -> f32 {
+        match self {
+            StatusEffectKind::Burn => 4.0,
+            StatusEffectKind::Poison => 2.0,
+            StatusEffectKind::Freeze => 0.5,
+            StatusEffectKind::Stun => 0.0,
+        }
+    }
+
+    /// Stacking rule used when an entity has no override
+    pub fn default_stacking(&self) -> StatusStacking {
+        match self {
+            StatusEffectKind::Burn | StatusEffectKind::Freeze => StatusStacking::Refresh,
+            StatusEffectKind::Poison => StatusStacking::Stack { max_stacks: 3 },
+            // Prevent stun-locking
+            StatusEffectKind::Stun => StatusStacking::Ignore,
+        }
+    }
+
+    /// Damage type of the damage-over-time ticks
+    pub fn damage_type(&self) -> Option<DamageType> {
+        match self {
+            StatusEffectKind::Burn => Some(DamageType::Fire),
+            StatusEffectKind::Poison => Some(DamageType::Poison),
+            StatusEffectKind::Freeze | StatusEffectKind::Stun => None,
+        }
+    }
+
+    /// Sprite tint while the effect is active
+    pub fn tint(&self) -> Color {
+        match self {
+            StatusEffectKind::Burn => Color::srgb(1.0, 0.55, 0.3),
+            StatusEffectKind::Poison => Color::srgb(0.6, 1.0, 0.45),
+            StatusEffectKind::Freeze => Color::srgb(0.6, 0.8, 1.0),
+            StatusEffectKind::Stun => Color::srgb(1.0, 1.0, 0.45),
+        }
+    }
+
+    /// Tint priority when several effects are active (higher wins)
+    fn tint_priority(&self) -> u8 {
+        match self {
+            StatusEffectKind::Poison => 0,
+            StatusEffectKind::Burn => 1,
+            StatusEffectKind::Freeze => 2,
+            StatusEffectKind::Stun => 3,
+        }
+    }
+}
+
+/// A status effect to apply (configured on attacks, projectiles and hazards)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    /// Kind of effect
+    pub kind: StatusEffectKind,
+    /// How long the effect lasts (seconds)
+    pub duration_secs: f32,
+    /// Damage per second (Burn, Poison) or speed multiplier (Freeze);
+    /// unused for Stun. Defaults per kind when omitted.
+    #[serde(default)]
+    pub potency: Option<f32>,
+}
+
+impl StatusEffect {
+    /// Create an effect with the kind's default potency
+    pub fn new(kind: StatusEffectKind, duration_secs: f32) -> Self {
+        Self {
+            kind,
+            duration_secs,
+            potency: None,
+        }
+    }
+
+    /// Effective potency
+    pub fn potency(&self) -> f32 {
+        self.potency.unwrap_or_else(|| self.kind.default_potency())
+    }
+}
+
+/// What happens when an effect is applied while the same kind is active
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatusStacking {
+    /// Reset the duration (keeps the stronger potency)
+    Refresh,
+    /// Add to the remaining duration, up to `max_secs`
+    Extend { max_secs: f32 },
+    /// Add a stack (damage and slow scale with stacks) and reset the duration
+    Stack { max_stacks: u32 },
+    /// No effect until the active one ends
+    Ignore,
+}
+
+/// Per-entity status effect immunities and stacking overrides
+#[derive(Component, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatusEffectRules {
+    /// Effects that are never applied
+    #[serde(default)]
+    pub immunities: HashSet<StatusEffectKind>,
+    /// Stacking rule overrides by kind
+    #[serde(default)]
+    pub stacking: HashMap<StatusEffectKind, StatusStacking>,
+}
+
+impl StatusEffectRules {
+    /// Whether the entity is immune to an effect
+    pub fn is_immune(&self, kind: StatusEffectKind) -> bool {
+        self.immunities.contains(&kind)
+    }
+
+    /// Stacking rule for an effect
+    pub fn stacking(&self, kind: StatusEffectKind) -> StatusStacking {
+        self.stacking
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| kind.default_stacking())
+    }
+}
+
+/// A status effect active on an entity
+#[derive(Debug, Clone)]
+pub struct ActiveStatusEffect {
+    /// Kind of effect
+    pub kind: StatusEffectKind,
+    /// Potency of a single stack
+    pub potency: f32,
+    /// Number of stacks
+    pub stacks: u32,
+    /// Remaining duration
+    pub timer: Timer,
+    /// Damage-over-time tick timer
+    pub tick: Timer,
+}
+
+impl ActiveStatusEffect {
+    fn new(effect: &StatusEffect) -> Self {
+        Self {
+            kind: effect.kind,
+            potency: effect.potency(),
+            stacks: 1,
+            timer: Timer::from_seconds(effect.duration_secs, TimerMode::Once),
+            tick: Timer::from_seconds(STATUS_TICK_SECS, TimerMode::Repeating),
+        }
+    }
+
+    fn restart(&mut self, duration_secs: f32) {
+        self.timer = Timer::from_seconds(duration_secs, TimerMode::Once);
+    }
+}
+
+/// Status effects active on an entity
+///
+/// Removed once all effects have ended.
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects {
+    /// Active effects (at most one entry per kind)
+    pub effects: Vec<ActiveStatusEffect>,
+    /// Sprite color before any effect was applied (restored when they end)
+    pub base_color: Option<Color>,
+}
+
+impl StatusEffects {
+    /// Apply an effect following the stacking rule
+    ///
+    /// Returns false if the effect had no impact.
+    pub fn apply(&mut self, effect: &StatusEffect, stacking: StatusStacking) -> bool {
+        let Some(active) = self.effects.iter_mut().find(|e| e.kind == effect.kind) else {
+            self.effects.push(ActiveStatusEffect::new(effect));
+            return true;
+        };
+
+        match stacking {
+            StatusStacking::Refresh => {
+                active.potency = strongest(effect.kind, active.potency, effect.potency());
+                active.restart(effect.duration_secs);
+            }
+            StatusStacking::Extend { max_secs } => {
+                let remaining = active.timer.remaining_secs() + effect.duration_secs;
+                active.potency = strongest(effect.kind, active.potency, effect.potency());
+                active.restart(remaining.min(max_secs));
+            }
+            StatusStacking::Stack { max_stacks } => {
+                active.stacks = (active.stacks + 1).min(max_stacks.max(1));
+                active.potency = strongest(effect.kind, active.potency, effect.potency());
+                active.restart(effect.duration_secs);
+            }
+            StatusStacking::Ignore => return false,
+        }
+        true
+    }
+
+    /// Whether an effect of the given kind is active
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.effects.iter().any(|e| e.kind == kind)
+    }
+
+    /// Whether the entity is stunned
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusEffectKind::Stun)
+    }
+
+    /// Movement speed multiplier from slowing effects (1.0 = normal speed)
+    pub fn speed_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter(|e| e.kind == StatusEffectKind::Freeze)
+            .map(|e| e.potency.clamp(0.0, 1.0).powi(e.stacks as i32))
+            .fold(1.0, f32::min)
+    }
+
+    /// Tint of the highest priority active effect
+    pub fn tint(&self) -> Option<Color> {
+        self.effects
+            .iter()
+            .max_by_key(|e| e.kind.tint_priority())
+            .map(|e| e.kind.tint())
+    }
+
+    /// End all effects (the component is removed on the next update)
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+}
+
+/// Stronger of two potencies (lower is stronger for slows)
+fn strongest(kind: StatusEffectKind, a: f32, b: f32) -> f32 {
+    if kind == StatusEffectKind::Freeze {
+        a.min(b)
+    } else {
+        a.max(b)
+    }
+}
+
+/// Marker present while an entity is stunned (AI and player input are blocked)
+#[derive(Component, Debug, Default)]
+pub struct Stunned;
+
+/// Area that applies a status effect to entities inside it (e.g. lava, poison swamp)
+///
+/// Affects everyone unless the hazard has a `Faction`.
+#[derive(Component, Debug)]
+pub struct StatusHazard {
+    /// Effect applied to entities inside the hazard
+    pub effect: StatusEffect,
+    /// Time between applications
+    pub interval: Timer,
+}
+
+impl StatusHazard {
+    /// Create a hazard applying `effect` every `interval_secs`
+    pub fn new(effect: StatusEffect, interval_secs: f32) -> Self {
+        let mut interval = Timer::from_seconds(interval_secs, TimerMode::Repeating);
+        // Apply immediately on the first update
+        interval.tick(std::time::Duration::from_secs_f32(interval_secs));
+        Self { effect, interval }
+    }
+}
+
+/// Event to apply a status effect to an entity
+#[derive(bevy::ecs::prelude::Message, Debug, Clone)]
+pub struct StatusEffectEvent {
+    /// The entity receiving the effect
+    pub target: Entity,
+    /// The effect to apply
+    pub effect: StatusEffect,
+}
+
+/// Apply status effects from hazards to overlapping entities with health
+#[allow(clippy::type_complexity)]
+pub fn status_hazard_system(
+    time: Res<Time>,
+    factions: Res<FactionsConfig>,
+    index: Res<SpatialIndex>,
+    mut hazard_query: Query<(&Transform, &Collider, &mut StatusHazard, Option<&Faction>)>,
+    target_query: Query<
+        (&Transform, &Collider, Option<&Faction>),
+        (With<Health>, Without<StatusHazard>),
+    >,
+    mut status_events: MessageWriter<StatusEffectEvent>,
+) {
+    for (hazard_transform, hazard_collider, mut hazard, hazard_faction) in hazard_query.iter_mut() {
+        hazard.interval.tick(time.delta());
+        if !hazard.interval.just_finished() {
+            continue;
+        }
+
+        let hazard_bounds = hazard_collider.aabb(hazard_transform.translation.truncate());
+        for target in index.bodies.query(hazard_bounds) {
+            let Ok((transform, collider, target_faction)) = target_query.get(target) else {
+                continue;
+            };
+
+            // Hazards with a faction only affect hostile targets
+            if let Some(hazard_faction) = hazard_faction
+                && !factions
+                    .is_hostile(*hazard_faction, target_faction.copied().unwrap_or_default())
+            {
+                continue;
+            }
+
+            let target_bounds = collider.aabb(transform.translation.truncate());
+            if !hazard_bounds.intersect(target_bounds).is_empty() {
+                status_events.write(StatusEffectEvent {
+                    target,
+                    effect: hazard.effect,
+                });
+            }
+        }
+    }
+}
+
+/// Apply requested status effects following each target's rules
+pub fn apply_status_effects(
+    mut commands: Commands,
+    mut status_events: MessageReader<StatusEffectEvent>,
+    mut query: Query<(Option<&mut StatusEffects>, Option<&StatusEffectRules>), With<Health>>,
+) {
+    // Targets that get their first effect this frame
+    let mut new_effects: HashMap<Entity, StatusEffects> = HashMap::new();
+
+    for event in status_events.read() {
+        let Ok((effects, rules)) = query.get_mut(event.target) else {
+            continue;
+        };
+
+        let default_rules = StatusEffectRules::default();
+        let rules = rules.unwrap_or(&default_rules);
+        if rules.is_immune(event.effect.kind) {
+            continue;
+        }
+
+        let stacking = rules.stacking(event.effect.kind);
+        let applied = match effects {
+            Some(mut effects) => effects.apply(&event.effect, stacking),
+            None => new_effects
+                .entry(event.target)
+                .or_default()
+                .apply(&event.effect, stacking),
+        };
+
+        if applied {
+            debug!(
+                "Entity {:?} is affected by {:?}",
+                event.target, event.effect.kind
+            );
+        }
+    }
+
+    for (entity, effects) in new_effects {
+        commands.entity(entity).insert(effects);
+    }
+}
+
+/// Tick status effects: damage over time, stun and expiry
+///
+/// Damage ticks skip invincible and invulnerable targets, and each tick that
+/// lands fires a `DamageDealtEvent` (damage numbers).
+#[allow(clippy::type_complexity)]
+pub fn status_effect_tick_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut StatusEffects,
+        &mut Health,
+        &Transform,
+        Option<&DamageResistances>,
+        Option<&mut Velocity>,
+        Has<Stunned>,
+        Has<Gravity>,
+        Has<Player>,
+        Has<Invincibility>,
+        Has<Invulnerable>,
+    )>,
+    mut death_events: MessageWriter<DeathEvent>,
+    mut dealt_events: MessageWriter<DamageDealtEvent>,
+) {
+    for (
+        entity,
+        mut effects,
+        mut health,
+        transform,
+        resistances,
+        velocity,
+        stunned,
+        has_gravity,
+        is_player,
+        invincible,
+        invulnerable,
+    ) in query.iter_mut()
+    {
+        let protected = invincible || invulnerable;
+
+        for effect in effects.effects.iter_mut() {
+            effect.timer.tick(time.delta());
+
+            let Some(damage_type) = effect.kind.damage_type() else {
+                continue;
+            };
+            effect.tick.tick(time.delta());
+            let ticks = effect.tick.times_finished_this_tick() as f32;
+            if ticks == 0.0 || protected || !health.is_alive() {
+                continue;
+            }
+
+            let multiplier = resistances.map_or(1.0, |r| r.multiplier(damage_type));
+            let damage =
+                effect.potency * effect.stacks as f32 * STATUS_TICK_SECS * ticks * multiplier;
+            if damage <= 0.0 {
+                continue;
+            }
+
+            if health.take_damage(damage) {
+                death_events.write(DeathEvent { entity });
+            }
+            dealt_events.write(DamageDealtEvent {
+                target: entity,
+                damage,
+                damage_type,
+                effectiveness: HitEffectiveness::from_multiplier(multiplier),
+                critical: false,
+                target_is_player: is_player,
+                contact_point: transform.translation.truncate(),
+            });
+        }
+        effects.effects.retain(|effect| !effect.timer.is_finished());
+
+        // Keep the Stunned marker in sync
+        let is_stunned = effects.is_stunned();
+        if is_stunned && !stunned {
+            commands.entity(entity).insert(Stunned);
+        } else if !is_stunned && stunned {
+            commands.entity(entity).remove::<Stunned>();
+        }
+
+        // Stunned entities stop moving (falling still applies)
+        if is_stunned && let Some(mut velocity) = velocity {
+            velocity.x = 0.0;
+            if !has_gravity {
+                velocity.y = 0.0;
+            }
+        }
+    }
+}
+
+/// Tint sprites by their strongest status effect
+///
+/// Only the RGB channels are changed so the alpha blinking of damage flash
+/// and invincibility keeps working. While a damage flash is active its
+/// restore color is updated instead.
+pub fn status_tint_system(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut StatusEffects,
+        Option<&mut Sprite>,
+        Option<&mut DamageFlash>,
+    )>,
+) {
+    for (entity, mut effects, sprite, flash) in query.iter_mut() {
+        let Some(mut sprite) = sprite else {
+            if effects.effects.is_empty() {
+                commands.entity(entity).remove::<(StatusEffects, Stunned)>();
+            }
+            continue;
+        };
+
+        // Remember the color to restore once all effects end
+        let base_color = *effects.base_color.get_or_insert_with(|| {
+            flash
+                .as_ref()
+                .map_or(sprite.color, |flash| flash.original_color)
+        });
+
+        let color = match effects.tint() {
+            Some(tint) => base_color.mix(&tint, 0.7),
+            None => base_color,
+        };
+
+        match flash {
+            Some(mut flash) => flash.original_color = color,
+            None => {
+                let alpha = sprite.color.alpha();
+                sprite.color = color.with_alpha(alpha);
+            }
+        }
+
+        if effects.effects.is_empty() {
+            commands.entity(entity).remove::<(StatusEffects, Stunned)>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stacking_rules() {
+        let burn = StatusEffect::new(StatusEffectKind::Burn, 2.0);
+        let mut effects = StatusEffects::default();
+
+        assert!(effects.apply(&burn, StatusStacking::Refresh));
+        assert!(effects.apply(&burn, StatusStacking::Refresh));
+        assert_eq!(effects.effects.len(), 1);
+        assert_eq!(effects.effects[0].stacks, 1);
+
+        let poison = StatusEffect::new(StatusEffectKind::Poison, 3.0);
+        let stacking = StatusStacking::Stack { max_stacks: 2 };
+        for _ in 0..3 {
+            effects.apply(&poison, stacking);
+        }
+        assert_eq!(effects.effects[1].stacks, 2);
+
+        let stun = StatusEffect::new(StatusEffectKind::Stun, 1.0);
+        assert!(effects.apply(&stun, StatusStacking::Ignore));
+        assert!(!effects.apply(&stun, StatusStacking::Ignore));
+        assert!(effects.is_stunned());
+    }
+
+    #[test]
+    fn test_damage_ticks_skip_invincible_targets() {
+        let mut app = App::new();
+        app.add_plugins(bevy::time::TimePlugin);
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f32(0.2),
+        ));
+        app.add_message::<DeathEvent>()
+            .add_message::<DamageDealtEvent>();
+        app.add_systems(Update, status_effect_tick_system);
+
+        let burn = StatusEffect::new(StatusEffectKind::Burn, 5.0);
+        let mut effects = StatusEffects::default();
+        effects.apply(&burn, StatusStacking::Refresh);
+
+        let burning = app
+            .world_mut()
+            .spawn((Health::new(100.0), Transform::default(), effects.clone()))
+            .id();
+        let invincible = app
+            .world_mut()
+            .spawn((
+                Health::new(100.0),
+                Transform::default(),
+                effects,
+                Invincibility::new(10.0),
+            ))
+            .id();
+
+        for _ in 0..4 {
+            app.update();
+        }
+
+        assert!(app.world().get::<Health>(burning).unwrap().current < 100.0);
+        assert_eq!(
+            app.world().get::<Health>(invincible).unwrap().current,
+            100.0
+        );
+
+        let messages = app.world().resource::<Messages<DamageDealtEvent>>();
+        assert!(!messages.is_empty());
+        assert!(
+            messages
+                .iter_current_update_messages()
+                .all(|event| event.target == burning && event.damage_type == DamageType::Fire)
+        );
+    }
+
+    #[test]
+    fn test_extend_is_capped() {
+        let freeze = StatusEffect::new(StatusEffectKind::Freeze, 2.0);
+        let mut effects = StatusEffects::default();
+        let stacking = StatusStacking::Extend { max_secs: 3.0 };
+
+        effects.apply(&freeze, stacking);
+        effects.apply(&freeze, stacking);
+        assert_eq!(effects.effects[0].timer.duration().as_secs_f32(), 3.0);
+        assert_eq!(effects.speed_multiplier(), 0.5);
+    }
+
+    #[test]
+    fn test_status_rules_parse() {
+        let rules: StatusEffectRules =
+            ron::from_str("(immunities: [Burn], stacking: { Poison: Stack(max_stacks: 5) })")
+                .unwrap();
+
+        assert!(rules.is_immune(StatusEffectKind::Burn));
+        assert_eq!(
+            rules.stacking(StatusEffectKind::Poison),
+            StatusStacking::Stack { max_stacks: 5 }
+        );
+        assert_eq!(
+            rules.stacking(StatusEffectKind::Stun),
+            StatusStacking::Ignore
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Broadphase benchmark scene
+//!
+//! Spawns a large level (ground and wall tiles, hundreds of enemies and
+//! hitboxes) in a headless world and compares brute-force overlap tests with
+//! queries through the `SpatialIndex`.
+//!
+//! Run with:
+//! ```text
+//! cargo run --release -p tropical-fox-common --example broadphase_benchmark
+//! ```
+
+use bevy::ecs::schedule::Schedule;
+use bevy::prelude::*;
+use rand::Rng;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tropical_fox_common::{
+    Collider, DEFAULT_CELL_SIZE, Ground, SpatialIndex, Wall, update_spatial_index,
+};
+
+/// Level width in tiles
+const LEVEL_TILES: u32 = 400;
+/// Tile size (pixels)
+const TILE_SIZE: f32 = 32.0;
+/// Number of bodies (enemies, players, pickups)
+const BODIES: u32 = 600;
+/// Number of attack hitboxes and projectiles
+const HITBOXES: u32 = 300;
+/// Frames simulated per run
+const FRAMES: u32 = 200;
+
+/// Marker for the entities doing overlap queries
+#[derive(Component)]
+struct Hitbox;
+
+/// Marker for the bodies being hit
+#[derive(Component)]
+struct Body;
+
+/// Overlaps found in the last frame
+#[derive(Resource, Default)]
+struct Overlaps(usize);
+
+fn overlaps(a_pos: Vec2, a: &Collider, b_pos: Vec2, b: &Collider) -> bool {
+    let delta = ((a_pos + a.offset) - (b_pos + b.offset)).abs() * 2.0;
+    delta.x < a.size.x + b.size.x && delta.y < a.size.y + b.size.y
+}
+
+fn spawn_scene(world: &mut World) {
+    let mut rng = rand::rng();
+    let level_width = LEVEL_TILES as f32 * TILE_SIZE;
+    let tile = Vec2::splat(TILE_SIZE);
+
+    // Ground floor, floating platforms and walls
+    for i in 0..LEVEL_TILES {
+        let x = i as f32 * TILE_SIZE;
+        world.spawn((
+            Transform::from_xyz(x, 0.0, 0.0),
+            Collider::new(tile),
+            Ground,
+        ));
+        if i % 5 < 3 {
+            let y = TILE_SIZE * (4 + (i / 5) % 6) as f32;
+            world.spawn((Transform::from_xyz(x, y, 0.0), Collider::new(tile), Ground));
+        }
+        if i % 20 == 0 {
+            for row in 1..8 {
+                let y = row as f32 * TILE_SIZE;
+                world.spawn((Transform::from_xyz(x, y, 0.0), Collider::new(tile), Wall));
+            }
+        }
+    }
+
+    for _ in 0..BODIES {
+        let position = Vec2::new(
+            rng.random_range(0.0..level_width),
+            rng.random_range(TILE_SIZE..TILE_SIZE * 12.0),
+        );
+        world.spawn((
+            Transform::from_translation(position.extend(0.0)),
+            Collider::new(Vec2::splat(30.0)),
+            Body,
+        ));
+    }
+
+    for _ in 0..HITBOXES {
+        let position = Vec2::new(
+            rng.random_range(0.0..level_width),
+            rng.random_range(TILE_SIZE..TILE_SIZE * 12.0),
+        );
+        world.spawn((
+            Transform::from_translation(position.extend(0.0)),
+            Collider::new(Vec2::new(40.0, 24.0)),
+            Hitbox,
+        ));
+    }
+}
+
+/// Every hitbox against every body, every body against every tile
+#[allow(clippy::type_complexity)]
+fn brute_force_system(
+    mut result: ResMut<Overlaps>,
+    hitbox_query: Query<(&Transform, &Collider), With<Hitbox>>,
+    body_query: Query<(&Transform, &Collider), With<Body>>,
+    solid_query: Query<(&Transform, &Collider), Or<(With<Ground>, With<Wall>)>>,
+) {
+    let mut count = 0;
+    for (hitbox_transform, hitbox_collider) in hitbox_query.iter() {
+        for (body_transform, body_collider) in body_query.iter() {
+            if overlaps(
+                hitbox_transform.translation.truncate(),
+                hitbox_collider,
+                body_transform.translation.truncate(),
+                body_collider,
+            ) {
+                count += 1;
+            }
+        }
+    }
+    for (body_transform, body_collider) in body_query.iter() {
+        for (solid_transform, solid_collider) in solid_query.iter() {
+            if overlaps(
+                body_transform.translation.truncate(),
+                body_collider,
+                solid_transform.translation.truncate(),
+                solid_collider,
+            ) {
+                count += 1;
+            }
+        }
+    }
+    result.0 = black_box(count);
+}
+
+/// Same overlaps, with candidates from the spatial index
+#[allow(clippy::type_complexity)]
+fn broadphase_system(
+    mut result: ResMut<Overlaps>,
+    index: Res<SpatialIndex>,
+    hitbox_query: Query<(&Transform, &Collider), With<Hitbox>>,
+    body_query: Query<(&Transform, &Collider), With<Body>>,
+    solid_query: Query<(&Transform, &Collider), Or<(With<Ground>, With<Wall>)>>,
+) {
+    let mut count = 0;
+    for (hitbox_transform, hitbox_collider) in hitbox_query.iter() {
+        let hitbox_pos = hitbox_transform.translation.truncate();
+        for body in index.bodies.query(hitbox_collider.aabb(hitbox_pos)) {
+            if let Ok((body_transform, body_collider)) = body_query.get(body)
+                && overlaps(
+                    hitbox_pos,
+                    hitbox_collider,
+                    body_transform.translation.truncate(),
+                    body_collider,
+                )
+            {
+                count += 1;
+            }
+        }
+    }
+    for (body_transform, body_collider) in body_query.iter() {
+        let body_pos = body_transform.translation.truncate();
+        for solid in index.solids.query(body_collider.aabb(body_pos)) {
+            if let Ok((solid_transform, solid_collider)) = solid_query.get(solid)
+                && overlaps(
+                    body_pos,
+                    body_collider,
+                    solid_transform.translation.truncate(),
+                    solid_collider,
+                )
+            {
+                count += 1;
+            }
+        }
+    }
+    result.0 = black_box(count);
+}
+
+/// Run a schedule for `FRAMES` frames and return the average frame time and overlap count
+fn run(world: &mut World, schedule: &mut Schedule) -> (Duration, usize) {
+    // Warm up
+    schedule.run(world);
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        schedule.run(world);
+    }
+    (start.elapsed() / FRAMES, world.resource::<Overlaps>().0)
+}
+
+fn main() {
+    let mut world = World::new();
+    world.init_resource::<Overlaps>();
+    world.insert_resource(SpatialIndex::new(DEFAULT_CELL_SIZE));
+    spawn_scene(&mut world);
+
+    let solids = world
+        .query_filtered::<(), Or<(With<Ground>, With<Wall>)>>()
+        .iter(&world)
+        .count();
+    println!(
+        "Scene: {} solids, {} bodies, {} hitboxes, {} frames",
+        solids, BODIES, HITBOXES, FRAMES
+    );
+
+    let mut brute_force = Schedule::default();
+    brute_force.add_systems(brute_force_system);
+    let (brute_time, brute_overlaps) = run(&mut world, &mut brute_force);
+
+    // The index is rebuilt every frame, as it is every fixed tick in game
+    let mut broadphase = Schedule::default();
+    broadphase.add_systems((update_spatial_index, broadphase_system).chain());
+    let (grid_time, grid_overlaps) = run(&mut world, &mut broadphase);
+
+    println!(
+        "Brute force: {:>10.3?} per frame ({} overlaps)",
+        brute_time, brute_overlaps
+    );
+    println!(
+        "Broadphase:  {:>10.3?} per frame ({} overlaps, including index rebuild)",
+        grid_time, grid_overlaps
+    );
+    println!(
+        "Speedup:     {:.1}x",
+        brute_time.as_secs_f64() / grid_time.as_secs_f64()
+    );
+
+    assert_eq!(
+        brute_overlaps, grid_overlaps,
+        "broadphase must find the same overlaps"
+    );
+}
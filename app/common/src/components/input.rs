@@ -0,0 +1,110 @@
+//! Player input components
+//!
+//! Each player entity reads its actions from a `PlayerInput` component that is
+//! filled once per frame from the player's `InputDevice`. Gameplay systems read
+//! `PlayerInput` instead of the keyboard so several players can be controlled
+//! by different devices.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Device controlling a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InputDevice {
+    /// Whole keyboard (arrows/WASD, single-player layout)
+    #[default]
+    Keyboard,
+    /// Left half of the keyboard (WASD + Space/Shift/F/G/H/R)
+    KeyboardLeft,
+    /// Right half of the keyboard (arrows + numpad)
+    KeyboardRight,
+    /// Gamepad by connection order (0 = first connected gamepad)
+    Gamepad(usize),
+}
+
+/// State of a single action button for the current frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActionState {
+    /// Button is held down
+    pub pressed: bool,
+    /// Button was pressed this frame
+    pub just_pressed: bool,
+    /// Button was released this frame
+    pub just_released: bool,
+}
+
+/// Per-player input state, updated every frame from the player's device
+#[derive(Component, Debug, Clone, Default)]
+pub struct PlayerInput {
+    /// Device controlling this player
+    pub device: InputDevice,
+    /// Movement direction (each axis in -1.0..=1.0, +y is up)
+    pub movement: Vec2,
+    /// Jump button
+    pub jump: ActionState,
+    /// Dash button
+    pub dash: ActionState,
+    /// Melee attack button
+    pub attack: ActionState,
+    /// Ground pound button
+    pub ground_pound: ActionState,
+    /// Ranged attack button
+    pub ranged: ActionState,
+    /// Block button (hold to shield, tap right before a hit to parry)
+    pub block: ActionState,
+}
+
+impl PlayerInput {
+    /// Create an input component reading from the given device
+    pub fn new(device: InputDevice) -> Self {
+        Self {
+            device,
+            ..default()
+        }
+    }
+
+    /// Horizontal movement input (-1.0, 0.0 or 1.0)
+    pub fn horizontal(&self) -> f32 {
+        digital_axis(self.movement.x)
+    }
+
+    /// Vertical movement input (-1.0, 0.0 or 1.0)
+    pub fn vertical(&self) -> f32 {
+        digital_axis(self.movement.y)
+    }
+
+    /// Clear all actions (used when the device is disconnected)
+    pub fn clear(&mut self) {
+        *self = Self::new(self.device);
+    }
+}
+
+/// Stick deflection below this is treated as no input
+const AXIS_DEADZONE: f32 = 0.3;
+
+fn digital_axis(value: f32) -> f32 {
+    if value > AXIS_DEADZONE {
+        1.0
+    } else if value < -AXIS_DEADZONE {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+/// Player number (0 = player one), used to tell co-op players apart
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PlayerSlot(pub usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_movement_deadzone() {
+        let mut input = PlayerInput::new(InputDevice::Gamepad(0));
+        input.movement = Vec2::new(0.2, -0.8);
+        assert_eq!(input.horizontal(), 0.0);
+        assert_eq!(input.vertical(), -1.0);
+    }
+}
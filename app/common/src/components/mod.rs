@@ -4,6 +4,7 @@
 
 pub mod animation;
 pub mod enemy;
+pub mod input;
 
 use bevy::prelude::*;
 
@@ -13,6 +14,8 @@ use bevy::prelude::*;
 // Note: Enemy components are now in tropical-fox-enemy crate
 // pub use enemy::*;
 
+pub use input::*;
+
 /// Velocity component for entities that move
 #[derive(Component, Debug, Default)]
 pub struct Velocity {
@@ -71,6 +74,10 @@ pub struct Player {
     pub dash_direction: Vec2,
     /// Whether player is touching a wall on the left (-1), right (1), or none (0)
     pub wall_contact: i32,
+    /// Number of mid-air jumps remaining (resets when grounded)
+    pub air_jumps_remaining: u32,
+    /// Whether the player is currently slamming downward in a ground pound
+    pub ground_pounding: bool,
 }
 
 impl Default for Player {
@@ -83,8 +90,67 @@ impl Default for Player {
             dash_timer: 0.0,
             dash_direction: Vec2::ZERO,
             wall_contact: 0,
+            air_jumps_remaining: 0,
+            ground_pounding: false,
+        }
+    }
+}
+
+/// Player behaviour state, driven by the player state machine
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PlayerState {
+    /// Standing still on the ground
+    #[default]
+    Idle,
+    /// Moving on the ground
+    Run,
+    /// Rising in the air
+    Jump,
+    /// Falling in the air
+    Fall,
+    /// Dashing
+    Dash,
+    /// Sliding down a wall
+    WallSlide,
+    /// Performing an attack
+    Attack,
+    /// Knocked back after taking damage
+    Hurt,
+    /// Out of health
+    Dead,
+}
+
+impl PlayerState {
+    /// States that can be entered from this state (transition table)
+    pub fn allowed_transitions(self) -> &'static [PlayerState] {
+        use PlayerState::*;
+
+        match self {
+            Idle => &[Run, Jump, Fall, Dash, Attack, Hurt, Dead],
+            Run => &[Idle, Jump, Fall, Dash, Attack, Hurt, Dead],
+            Jump => &[Idle, Run, Fall, Dash, WallSlide, Attack, Hurt, Dead],
+            Fall => &[Idle, Run, Jump, Dash, WallSlide, Attack, Hurt, Dead],
+            Dash => &[Idle, Run, Jump, Fall, WallSlide, Attack, Hurt, Dead],
+            WallSlide => &[Idle, Run, Jump, Fall, Dash, Attack, Hurt, Dead],
+            Attack => &[Idle, Run, Jump, Fall, Dash, WallSlide, Hurt, Dead],
+            // Knockback must end in a movement state before acting again
+            Hurt => &[Idle, Run, Jump, Fall, Dead],
+            // Respawn
+            Dead => &[Idle, Fall],
         }
     }
+
+    /// Check if the transition table allows moving to `next`
+    pub fn can_transition_to(self, next: PlayerState) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Check if `next` can be entered from this state (staying in it is always allowed)
+    ///
+    /// Used to gate actions such as attacks and dashes before they start.
+    pub fn can_enter(self, next: PlayerState) -> bool {
+        self == next || self.can_transition_to(next)
+    }
 }
 
 /// Player movement and physics statistics
@@ -126,6 +192,14 @@ pub struct PlayerStats {
     pub wall_jump_force_y: f32,
     /// Wall slide speed (pixels per second, negative = downward)
     pub wall_slide_speed: f32,
+    /// Number of mid-air jumps granted by the double jump ability
+    pub max_air_jumps: u32,
+    /// Double jump force (upward velocity in pixels per second)
+    pub double_jump_force: f32,
+    /// Ground pound slam speed (pixels per second, still capped by terminal velocity)
+    pub ground_pound_speed: f32,
+    /// Movement speed multiplier while charging an attack
+    pub charge_move_speed_multiplier: f32,
 }
 
 impl Default for PlayerStats {
@@ -145,6 +219,10 @@ impl Default for PlayerStats {
             wall_jump_force_x: 300.0,
             wall_jump_force_y: 400.0,
             wall_slide_speed: -100.0,
+            max_air_jumps: 1,
+            double_jump_force: 350.0,
+            ground_pound_speed: 500.0,
+            charge_move_speed_multiplier: 0.4, // Slow walk while charging
         }
     }
 }
@@ -157,6 +235,8 @@ impl Default for PlayerStats {
 pub struct GroundDetection {
     /// Whether the entity is currently on the ground
     pub is_grounded: bool,
+    /// Whether the entity was on the ground in the previous fixed tick
+    pub was_grounded: bool,
     /// Distance to check below entity for ground (pixels)
     #[allow(dead_code)]
     pub ground_check_distance: f32,
@@ -166,6 +246,7 @@ impl Default for GroundDetection {
     fn default() -> Self {
         Self {
             is_grounded: false,
+            was_grounded: false,
             ground_check_distance: 2.0,
         }
     }
@@ -194,6 +275,11 @@ impl Collider {
     pub fn with_offset(size: Vec2, offset: Vec2) -> Self {
         Self { size, offset }
     }
+
+    /// Bounds of the collider for an entity at `position`
+    pub fn aabb(&self, position: Vec2) -> Rect {
+        Rect::from_center_size(position + self.offset, self.size)
+    }
 }
 
 /// Ground marker component for platforms and surfaces
@@ -1,3 +1,18 @@
 //! Game events
 //!
 //! This module will contain all event types used throughout the game.
+
+use bevy::prelude::*;
+
+use crate::components::PlayerState;
+
+/// Event fired when the player state machine changes state
+#[derive(bevy::ecs::prelude::Message, Debug, Clone, Copy)]
+pub struct PlayerStateChanged {
+    /// The player entity
+    pub entity: Entity,
+    /// State that was exited
+    pub from: PlayerState,
+    /// State that was entered
+    pub to: PlayerState,
+}
@@ -5,13 +5,17 @@
 //! - Common ECS components
 //! - Common events
 //! - Common resources
+//! - Broadphase spatial index
 
 pub mod components;
 pub mod events;
 pub mod game_state;
 pub mod resources;
+pub mod spatial;
 
 // Re-export commonly used types
 pub use components::*;
+pub use events::*;
 pub use game_state::{GameState, InGameState};
 pub use resources::*;
+pub use spatial::*;
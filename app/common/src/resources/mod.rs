@@ -24,6 +24,27 @@ impl Default for PhysicsConfig {
     }
 }
 
+/// Which physics implementation moves bodies and resolves collisions
+///
+/// With `Rapier` (the binary's `rapier` feature) the built-in gravity,
+/// movement and AABB collision systems are skipped; gameplay code still reads
+/// and writes `Velocity` and `GroundDetection` either way.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PhysicsBackend {
+    /// Built-in gravity, velocity integration and AABB collision
+    #[default]
+    Custom,
+    /// Kinematic character controllers from `bevy_rapier2d`
+    Rapier,
+}
+
+/// Run condition: the built-in physics backend is active
+///
+/// True when no `PhysicsBackend` resource is present.
+pub fn custom_physics(backend: Option<Res<PhysicsBackend>>) -> bool {
+    backend.is_none_or(|backend| *backend == PhysicsBackend::Custom)
+}
+
 /// Texture atlas data for a single character
 #[derive(Debug, Clone)]
 pub struct CharacterTextureAtlas {
@@ -0,0 +1,241 @@
+//! Broadphase spatial index
+//!
+//! Overlap queries (attacks, projectiles, contact damage, ground and wall
+//! collision) look up nearby entities in a uniform grid instead of testing
+//! every pair. The `SpatialIndex` is rebuilt every fixed tick after physics,
+//! and callers run their exact collision test on the returned candidates.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::{Collider, Ground, Wall};
+
+/// Default grid cell size (pixels)
+pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+/// Extra space around indexed colliders (pixels)
+///
+/// Entities keep being found while they move between two rebuilds of the index.
+pub const SPATIAL_MARGIN: f32 = 16.0;
+
+/// Uniform grid of entities bucketed by the cells their bounds overlap
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<Entity>>,
+    len: usize,
+}
+
+impl SpatialGrid {
+    /// Create an empty grid
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Size of a grid cell (pixels)
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Number of entities in the grid
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the grid is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all entities
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.len = 0;
+    }
+
+    /// Add an entity covering `bounds`
+    pub fn insert(&mut self, entity: Entity, bounds: Rect) {
+        let (min, max) = self.cell_range(bounds);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(entity);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Entities whose cells overlap `bounds` (sorted, without duplicates)
+    ///
+    /// This is a broadphase: candidates still need an exact overlap test.
+    pub fn query(&self, bounds: Rect) -> Vec<Entity> {
+        let (min, max) = self.cell_range(bounds);
+        let mut entities = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                if let Some(cell) = self.cells.get(&IVec2::new(x, y)) {
+                    entities.extend_from_slice(cell);
+                }
+            }
+        }
+        entities.sort_unstable();
+        entities.dedup();
+        entities
+    }
+
+    /// First and last cell covered by `bounds`
+    fn cell_range(&self, bounds: Rect) -> (IVec2, IVec2) {
+        let min = (bounds.min / self.cell_size).floor().as_ivec2();
+        let max = (bounds.max / self.cell_size).floor().as_ivec2();
+        (min, max)
+    }
+}
+
+/// Broadphase for all colliders, split into level geometry and everything else
+#[derive(Resource, Debug, Clone)]
+pub struct SpatialIndex {
+    /// `Ground` and `Wall` colliders
+    pub solids: SpatialGrid,
+    /// Other colliders (players, enemies, hurtboxes, pickups)
+    pub bodies: SpatialGrid,
+}
+
+impl SpatialIndex {
+    /// Create an empty index
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            solids: SpatialGrid::new(cell_size),
+            bodies: SpatialGrid::new(cell_size),
+        }
+    }
+
+    /// Change the cell size (the index is empty until the next rebuild)
+    pub fn set_cell_size(&mut self, cell_size: f32) {
+        *self = Self::new(cell_size);
+    }
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+/// Fraction along the segment `from` -> `to` where it enters `rect`
+///
+/// Returns `None` when the segment misses the rectangle or only grazes its
+/// edge; a segment starting inside the rectangle enters it at `0.0`.
+pub fn segment_rect_intersection(from: Vec2, to: Vec2, rect: Rect) -> Option<f32> {
+    let delta = to - from;
+    let mut enter: f32 = 0.0;
+    let mut exit: f32 = 1.0;
+
+    // Slab test on each axis
+    for axis in 0..2 {
+        let (start, step, min, max) = (from[axis], delta[axis], rect.min[axis], rect.max[axis]);
+        if step.abs() < f32::EPSILON {
+            if start <= min || start >= max {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (min - start) / step;
+        let t2 = (max - start) / step;
+        enter = enter.max(t1.min(t2));
+        exit = exit.min(t1.max(t2));
+    }
+
+    (enter < exit).then_some(enter)
+}
+
+/// Rebuild the spatial index from the current collider positions
+///
+/// Runs in `FixedUpdate` after physics; collision systems run after it.
+#[allow(clippy::type_complexity)]
+pub fn update_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    solid_query: Query<(Entity, &Transform, &Collider), Or<(With<Ground>, With<Wall>)>>,
+    body_query: Query<(Entity, &Transform, &Collider), (Without<Ground>, Without<Wall>)>,
+) {
+    index.solids.clear();
+    for (entity, transform, collider) in solid_query.iter() {
+        let bounds = collider.aabb(transform.translation.truncate());
+        index.solids.insert(entity, bounds.inflate(SPATIAL_MARGIN));
+    }
+
+    index.bodies.clear();
+    for (entity, transform, collider) in body_query.iter() {
+        let bounds = collider.aabb(transform.translation.truncate());
+        index.bodies.insert(entity, bounds.inflate(SPATIAL_MARGIN));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_query_finds_nearby_entities_once() {
+        let mut grid = SpatialGrid::new(32.0);
+        let near = Entity::from_raw_u32(1).unwrap();
+        let wide = Entity::from_raw_u32(2).unwrap();
+        let far = Entity::from_raw_u32(3).unwrap();
+
+        grid.insert(near, Rect::new(0.0, 0.0, 10.0, 10.0));
+        // Spans many cells but is returned only once
+        grid.insert(wide, Rect::new(-200.0, -5.0, 200.0, 5.0));
+        grid.insert(far, Rect::new(500.0, 500.0, 510.0, 510.0));
+
+        assert_eq!(grid.len(), 3);
+        let nearby = grid.query(Rect::new(-40.0, -40.0, 40.0, 40.0));
+        assert_eq!(nearby.len(), 2);
+        assert!(nearby.contains(&near) && nearby.contains(&wide));
+        assert_eq!(grid.query(Rect::new(490.0, 490.0, 495.0, 495.0)), vec![far]);
+    }
+
+    #[test]
+    fn test_segment_rect_intersection() {
+        let rect = Rect::new(10.0, -10.0, 20.0, 10.0);
+
+        assert_eq!(
+            segment_rect_intersection(Vec2::ZERO, Vec2::new(40.0, 0.0), rect),
+            Some(0.25)
+        );
+        // Passes above, stops short, and runs along the top edge
+        assert!(
+            segment_rect_intersection(Vec2::new(0.0, 20.0), Vec2::new(40.0, 20.0), rect).is_none()
+        );
+        assert!(segment_rect_intersection(Vec2::ZERO, Vec2::new(5.0, 0.0), rect).is_none());
+        assert!(
+            segment_rect_intersection(Vec2::new(0.0, 10.0), Vec2::new(40.0, 10.0), rect).is_none()
+        );
+        // Diagonal through the corner region
+        assert!(
+            segment_rect_intersection(Vec2::new(0.0, -20.0), Vec2::new(30.0, 20.0), rect).is_some()
+        );
+        // Starting inside
+        assert_eq!(
+            segment_rect_intersection(Vec2::new(15.0, 0.0), Vec2::new(40.0, 0.0), rect),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_grid_negative_coordinates() {
+        let mut grid = SpatialGrid::new(64.0);
+        let entity = Entity::from_raw_u32(7).unwrap();
+        grid.insert(entity, Rect::new(-70.0, -70.0, -66.0, -66.0));
+
+        assert_eq!(
+            grid.query(Rect::new(-100.0, -100.0, -65.0, -65.0)),
+            vec![entity]
+        );
+        assert!(grid.query(Rect::new(0.0, 0.0, 10.0, 10.0)).is_empty());
+
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+}
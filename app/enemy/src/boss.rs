@@ -0,0 +1,783 @@
+//! Boss fights
+//!
+//! Bosses and their arenas are defined in `bosses.ron`. When a player enters
+//! an arena's trigger the game switches to `InGameState::BossRoom`: barriers
+//! lock the arena and the boss spawns. `BossController` runs the attack
+//! pattern of the current health-threshold phase; every attack has a
+//! telegraphed wind-up, an active part and a recovery. The boss is
+//! `Invulnerable` except during the recovery of attacks marked `vulnerable`.
+//! Once the boss dies a victory sequence plays and the arena unlocks for good.
+
+use bevy::prelude::*;
+use tropical_fox_combat::{
+    DamageFlash, DamageResistances, EnemyHealth, Faction, FxAssets, Health, HitReaction, Hurtbox,
+    Invulnerable, ProjectileVolley, ScreenShakeEvent, fire_projectiles,
+};
+use tropical_fox_common::{CharacterAssets, Collider, InGameState, Player, Velocity, Wall};
+
+use crate::components::{AttackStage, Boss, ContactDamage, Enemy, EnemyAI, EnemyStats, Minion};
+use crate::config::{BossAttack, BossAttackConfig, BossPhaseConfig, BossTypeConfig, BossesConfig};
+use crate::plugin::{
+    EnemyConfig, get_enemy_color, insert_character_sprite, nearest_living_player, spawn_enemy,
+};
+
+/// How quickly a hovering boss closes the distance to its hover point (per second)
+const HOVER_GAIN: f32 = 4.0;
+
+/// Period of the idle hover bob (seconds)
+const HOVER_PERIOD_SECS: f32 = 2.0;
+
+/// Blinks per second of the wind-up telegraph
+const TELEGRAPH_BLINK_RATE: f32 = 6.0;
+
+/// Horizontal spacing of summoned minions (pixels)
+const MINION_SPACING: f32 = 48.0;
+
+/// Screen shake when the boss enters a new phase
+const PHASE_SHAKE_INTENSITY: f32 = 4.0;
+const PHASE_SHAKE_SECS: f32 = 0.3;
+
+/// Screen shake when the boss is defeated
+const VICTORY_SHAKE_INTENSITY: f32 = 8.0;
+const VICTORY_SHAKE_SECS: f32 = 0.6;
+
+/// Resource holding boss configuration
+#[derive(Resource, Debug)]
+pub struct BossConfig(pub BossesConfig);
+
+/// Result of one boss controller update
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BossTick {
+    /// A new phase started this update
+    pub phase_changed: bool,
+    /// Stage started this update
+    pub started: Option<AttackStage>,
+}
+
+/// Runs a boss's phases and attack patterns
+#[derive(Component, Debug, Clone)]
+pub struct BossController {
+    /// Boss ID in `bosses.ron`
+    pub boss_id: String,
+    /// Index of the current phase
+    pub phase: usize,
+    /// Index of the current attack in the phase pattern
+    pub step: usize,
+    /// Stage of the current attack
+    pub stage: AttackStage,
+    /// Time left in the current stage (seconds)
+    pub stage_timer: f32,
+    /// Time since the boss spawned (seconds)
+    pub time: f32,
+    /// Hover position between attacks
+    pub home: Vec2,
+    /// Area the boss stays in
+    pub bounds: Option<Rect>,
+    /// Dive target, taken when the dive starts
+    pub target: Option<Vec2>,
+    /// Time until the next fire breath volley (seconds)
+    pub fire_timer: f32,
+    /// Sprite color without tints
+    pub base_color: Color,
+}
+
+impl BossController {
+    /// Create a controller starting the first attack of the first phase
+    pub fn new(boss_id: impl Into<String>, phases: &[BossPhaseConfig], home: Vec2) -> Self {
+        let mut controller = Self {
+            boss_id: boss_id.into(),
+            phase: 0,
+            step: 0,
+            stage: AttackStage::Windup,
+            stage_timer: 0.0,
+            time: 0.0,
+            home,
+            bounds: None,
+            target: None,
+            fire_timer: 0.0,
+            base_color: Color::WHITE,
+        };
+        controller.start(AttackStage::Windup, phases);
+        controller
+    }
+
+    /// Attack currently performed, if the phase has a pattern
+    pub fn current_attack<'a>(
+        &self,
+        phases: &'a [BossPhaseConfig],
+    ) -> Option<&'a BossAttackConfig> {
+        phases.get(self.phase)?.pattern.get(self.step)
+    }
+
+    /// Whether the boss can be damaged right now
+    ///
+    /// Bosses without a pattern are always vulnerable.
+    pub fn vulnerable(&self, phases: &[BossPhaseConfig]) -> bool {
+        self.current_attack(phases)
+            .is_none_or(|attack| self.stage == AttackStage::Recovery && attack.vulnerable)
+    }
+
+    /// Advance timers, switching phase and attack stage as needed
+    pub fn advance(
+        &mut self,
+        delta: f32,
+        phases: &[BossPhaseConfig],
+        health_ratio: f32,
+    ) -> BossTick {
+        self.time += delta;
+        let mut tick = BossTick::default();
+
+        // Phases never go back, even if the boss heals
+        let phase = phase_index(phases, health_ratio).max(self.phase);
+        if phase != self.phase {
+            self.phase = phase;
+            self.step = 0;
+            self.start(AttackStage::Windup, phases);
+            tick.phase_changed = true;
+            tick.started = Some(AttackStage::Windup);
+            return tick;
+        }
+
+        // The pattern may have shrunk on hot reload
+        let Some(pattern) = phases.get(self.phase).map(|phase| &phase.pattern) else {
+            return tick;
+        };
+        if pattern.is_empty() {
+            return tick;
+        }
+        if self.step >= pattern.len() {
+            self.step = 0;
+        }
+
+        self.stage_timer -= delta;
+        if self.stage_timer > 0.0 {
+            return tick;
+        }
+
+        let next = match self.stage {
+            AttackStage::Windup => AttackStage::Active,
+            AttackStage::Active => AttackStage::Recovery,
+            AttackStage::Recovery => {
+                self.step = (self.step + 1) % pattern.len();
+                AttackStage::Windup
+            }
+        };
+        self.start(next, phases);
+        tick.started = Some(next);
+        tick
+    }
+
+    /// Enter a stage of the current attack
+    fn start(&mut self, stage: AttackStage, phases: &[BossPhaseConfig]) {
+        self.stage = stage;
+        self.stage_timer = self
+            .current_attack(phases)
+            .map_or(0.0, |attack| stage_secs(attack, stage));
+    }
+}
+
+/// Duration of a stage of a boss attack (seconds)
+fn stage_secs(attack: &BossAttackConfig, stage: AttackStage) -> f32 {
+    match stage {
+        AttackStage::Windup => attack.windup_secs,
+        AttackStage::Active => attack.active_secs,
+        AttackStage::Recovery => attack.recovery_secs,
+    }
+}
+
+/// Deepest phase reached at this health ratio
+///
+/// Phases are ordered by descending `health_threshold`.
+pub fn phase_index(phases: &[BossPhaseConfig], health_ratio: f32) -> usize {
+    phases
+        .iter()
+        .rposition(|phase| health_ratio <= phase.health_threshold)
+        .unwrap_or(0)
+}
+
+/// Boss arena from `bosses.ron`
+#[derive(Component, Debug, Clone)]
+pub struct BossArena {
+    /// Boss fought in this arena
+    pub boss_id: String,
+    /// The boss was defeated; the arena no longer triggers
+    pub cleared: bool,
+}
+
+/// Wall locking an arena during a boss fight
+#[derive(Component, Debug, Default)]
+pub struct ArenaBarrier;
+
+/// Boss fight in progress (present while in `InGameState::BossRoom`)
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveBossFight {
+    /// Arena entity
+    pub arena: Entity,
+    /// Boss ID in `bosses.ron`
+    pub boss_id: String,
+    /// Boss entity, once spawned
+    pub boss: Option<Entity>,
+    /// Victory sequence timer, once the boss is defeated
+    pub victory: Option<Timer>,
+}
+
+impl ActiveBossFight {
+    /// Whether the victory sequence is playing
+    pub fn is_won(&self) -> bool {
+        self.victory.is_some()
+    }
+}
+
+fn rect(corners: ((f32, f32), (f32, f32))) -> Rect {
+    Rect::new(corners.0.0, corners.0.1, corners.1.0, corners.1.1)
+}
+
+/// Spawn an arena for each boss that has one
+pub fn spawn_boss_arenas(
+    mut commands: Commands,
+    config: Res<BossConfig>,
+    arena_query: Query<&BossArena>,
+) {
+    for (boss_id, boss) in &config.0.bosses {
+        let Some(arena) = &boss.arena else {
+            continue;
+        };
+        if arena_query.iter().any(|arena| arena.boss_id == *boss_id) {
+            continue;
+        }
+
+        let trigger = rect(arena.trigger);
+        commands.spawn((
+            Transform::from_translation(trigger.center().extend(0.0)),
+            BossArena {
+                boss_id: boss_id.clone(),
+                cleared: false,
+            },
+            Name::new(format!("BossArena_{}", boss.name)),
+        ));
+        info!("Boss arena for '{}' at {:?}", boss_id, trigger);
+    }
+}
+
+/// Start a boss fight when a living player enters an arena trigger
+pub fn boss_arena_trigger_system(
+    mut commands: Commands,
+    config: Res<BossConfig>,
+    arena_query: Query<(Entity, &BossArena)>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    mut next_state: ResMut<NextState<InGameState>>,
+) {
+    for (entity, arena) in arena_query.iter() {
+        if arena.cleared {
+            continue;
+        }
+        let Some(arena_config) = config
+            .0
+            .get_boss(&arena.boss_id)
+            .ok()
+            .and_then(|boss| boss.arena.as_ref())
+        else {
+            continue;
+        };
+
+        let trigger = rect(arena_config.trigger);
+        let entered = player_query.iter().any(|(transform, health)| {
+            health.is_alive() && trigger.contains(transform.translation.truncate())
+        });
+        if entered {
+            info!("Boss fight '{}' started", arena.boss_id);
+            commands.insert_resource(ActiveBossFight {
+                arena: entity,
+                boss_id: arena.boss_id.clone(),
+                boss: None,
+                victory: None,
+            });
+            next_state.set(InGameState::BossRoom);
+            return;
+        }
+    }
+}
+
+/// Lock the arena and spawn the boss when entering the boss room
+pub fn start_boss_fight(
+    mut commands: Commands,
+    config: Res<BossConfig>,
+    character_assets: Option<Res<CharacterAssets>>,
+    fight: Option<ResMut<ActiveBossFight>>,
+) {
+    let Some(mut fight) = fight else {
+        return;
+    };
+    let Ok(boss) = config.0.get_boss(&fight.boss_id) else {
+        warn!("Unknown boss '{}'", fight.boss_id);
+        return;
+    };
+    let Some(arena) = &boss.arena else {
+        return;
+    };
+
+    for barrier in &arena.barriers {
+        let size = Vec2::new(barrier.size.0, barrier.size.1);
+        commands.spawn((
+            Transform::from_xyz(barrier.position.0, barrier.position.1, 0.0),
+            Visibility::default(),
+            Sprite {
+                color: Color::srgb(0.5, 0.25, 0.25),
+                custom_size: Some(size),
+                ..default()
+            },
+            Wall,
+            Collider::new(size),
+            ArenaBarrier,
+            Name::new("Arena Barrier"),
+        ));
+    }
+
+    let entity = spawn_boss(
+        &mut commands,
+        &fight.boss_id,
+        Vec2::new(arena.spawn.0, arena.spawn.1),
+        Some(rect(arena.bounds)),
+        boss,
+        character_assets.as_deref(),
+    );
+    fight.boss = Some(entity);
+}
+
+/// Spawn a boss entity from configuration, hovering at `position`
+pub fn spawn_boss(
+    commands: &mut Commands,
+    boss_id: &str,
+    position: Vec2,
+    bounds: Option<Rect>,
+    config: &BossTypeConfig,
+    character_assets: Option<&CharacterAssets>,
+) -> Entity {
+    let display_size = Vec2::new(config.display_size.0, config.display_size.1);
+    let mut controller = BossController::new(boss_id, &config.phases, position);
+    controller.bounds = bounds;
+    if character_assets.is_none_or(|assets| assets.get(boss_id).is_none()) {
+        controller.base_color = get_enemy_color(boss_id);
+    }
+
+    let mut entity = commands.spawn((
+        Transform::from_translation(position.extend(0.0)),
+        Visibility::default(),
+        Enemy::new(boss_id),
+        EnemyStats {
+            move_speed: config.stats.move_speed,
+            damage: config.stats.damage,
+            knockback_force: config.stats.knockback_force,
+            score_value: config.stats.score_value,
+        },
+        Health::new(config.stats.health),
+        EnemyHealth,
+        Hurtbox,
+        Faction::Enemy,
+        Velocity::default(),
+        Collider::with_offset(
+            Vec2::new(config.collider.size.0, config.collider.size.1),
+            Vec2::new(config.collider.offset.0, config.collider.offset.1),
+        ),
+        ContactDamage::new(config.stats.damage, config.stats.knockback_force, 0.5)
+            .with_impact(config.impact),
+        Name::new(config.name.clone()),
+    ));
+
+    insert_character_sprite(
+        &mut entity,
+        boss_id,
+        &config.animation_config_path,
+        display_size,
+        character_assets,
+    );
+
+    if !config.resistances.is_empty() {
+        entity.insert(DamageResistances::new(config.resistances.clone()));
+    }
+
+    // Bosses are never knocked back or stunned, and start out of reach
+    entity.insert((
+        Boss,
+        controller,
+        EnemyAI::Idle,
+        HitReaction {
+            super_armor: true,
+            ..default()
+        },
+        Invulnerable,
+    ));
+
+    info!("Spawned boss '{}' at {:?}", boss_id, position);
+    entity.id()
+}
+
+/// Run boss phases, attack patterns, movement and telegraphs
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn boss_controller_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    fx_assets: Res<FxAssets>,
+    boss_config: Res<BossConfig>,
+    enemy_config: Res<EnemyConfig>,
+    character_assets: Option<Res<CharacterAssets>>,
+    mut shake_events: MessageWriter<ScreenShakeEvent>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    minion_query: Query<&Minion>,
+    mut boss_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Health,
+            &Collider,
+            &EnemyStats,
+            &mut BossController,
+            &mut Velocity,
+            &mut Sprite,
+            Has<Invulnerable>,
+            Has<DamageFlash>,
+        ),
+        With<Boss>,
+    >,
+) {
+    let delta = time.delta_secs();
+
+    for (
+        entity,
+        transform,
+        health,
+        collider,
+        stats,
+        mut controller,
+        mut velocity,
+        mut sprite,
+        invulnerable,
+        flashing,
+    ) in boss_query.iter_mut()
+    {
+        let Ok(config) = boss_config.0.get_boss(&controller.boss_id) else {
+            continue;
+        };
+        let phases = &config.phases;
+        let position = transform.translation.truncate();
+        let player = nearest_living_player(position, &player_query);
+
+        let tick = controller.advance(delta, phases, health.ratio());
+        if tick.phase_changed {
+            info!(
+                "Boss '{}' entered phase {} ({})",
+                controller.boss_id,
+                controller.phase + 1,
+                phases[controller.phase].name
+            );
+            shake_events.write(ScreenShakeEvent {
+                intensity: PHASE_SHAKE_INTENSITY,
+                duration_secs: PHASE_SHAKE_SECS,
+            });
+        }
+
+        let attack = controller
+            .current_attack(phases)
+            .map(|attack| &attack.attack);
+
+        // Attacks starting this update
+        if tick.started == Some(AttackStage::Active) {
+            match attack {
+                Some(BossAttack::Dive { .. }) => {
+                    // Aim where the player is now, keeping the whole body in the arena
+                    let target = player.unwrap_or(controller.home);
+                    controller.target = Some(clamp_to_bounds(target, collider, controller.bounds));
+                }
+                Some(BossAttack::FireBreath { .. }) => controller.fire_timer = 0.0,
+                Some(BossAttack::SummonMinions { enemy, count }) => {
+                    let alive = minion_query
+                        .iter()
+                        .filter(|minion| minion.summoner == entity)
+                        .count() as u32;
+                    let count = (*count).min(config.max_minions.saturating_sub(alive));
+                    match enemy_config.0.get(enemy) {
+                        Some(minion_config) => {
+                            for i in 0..count {
+                                let offset = (i as f32 - (count - 1) as f32 / 2.0) * MINION_SPACING;
+                                let minion = spawn_enemy(
+                                    &mut commands,
+                                    enemy,
+                                    position + Vec2::X * offset,
+                                    minion_config,
+                                    character_assets.as_deref(),
+                                );
+                                commands.entity(minion).insert(Minion { summoner: entity });
+                            }
+                        }
+                        None => warn!("Unknown minion enemy type '{}'", enemy),
+                    }
+                }
+                Some(BossAttack::Hover) | None => {}
+            }
+        }
+        if tick.started == Some(AttackStage::Windup) {
+            controller.target = None;
+        }
+
+        // Movement: dive at the target and rest after it, otherwise hover at home
+        let hover = controller.home
+            + Vec2::Y
+                * config.hover_amplitude
+                * (controller.time * std::f32::consts::TAU / HOVER_PERIOD_SECS).sin();
+        let diving = matches!(attack, Some(BossAttack::Dive { .. }));
+        let new_velocity = match (controller.stage, attack) {
+            (AttackStage::Active, Some(BossAttack::Dive { speed })) => {
+                let target = controller.target.unwrap_or(position);
+                let offset = target - position;
+                if offset.length() <= speed * delta {
+                    Vec2::ZERO
+                } else {
+                    offset.normalize() * *speed
+                }
+            }
+            (AttackStage::Recovery, _) if diving => Vec2::ZERO,
+            _ => ((hover - position) * HOVER_GAIN).clamp_length_max(stats.move_speed),
+        };
+        velocity.x = new_velocity.x;
+        velocity.y = new_velocity.y;
+
+        // Fire breath volleys at the nearest player
+        if controller.stage == AttackStage::Active
+            && let Some(BossAttack::FireBreath {
+                projectile,
+                interval_secs,
+            }) = attack
+        {
+            controller.fire_timer -= delta;
+            if controller.fire_timer <= 0.0 {
+                controller.fire_timer = interval_secs.max(0.05);
+                let volley = ProjectileVolley {
+                    projectile: projectile.projectile(config.impact),
+                    emitter: projectile.emitter,
+                    direction: player.map_or(Vec2::NEG_Y, |player| player - position),
+                    offset: Vec2::ZERO,
+                    faction: Faction::Enemy,
+                };
+                fire_projectiles(
+                    &mut commands,
+                    &fx_assets,
+                    volley,
+                    entity,
+                    transform.translation,
+                );
+            }
+        }
+
+        // Only open to damage in vulnerability windows
+        let vulnerable = controller.vulnerable(phases);
+        if vulnerable && invulnerable {
+            commands.entity(entity).remove::<Invulnerable>();
+        } else if !vulnerable && !invulnerable {
+            commands.entity(entity).insert(Invulnerable);
+        }
+
+        // Telegraph wind-ups and show vulnerability (damage flashes take priority)
+        if !flashing {
+            sprite.color = if controller.stage == AttackStage::Windup
+                && (controller.time * TELEGRAPH_BLINK_RATE).fract() < 0.5
+            {
+                rgb(config.telegraph_color)
+            } else if vulnerable {
+                rgb(config.vulnerable_color)
+            } else {
+                controller.base_color
+            };
+        }
+    }
+}
+
+/// Finish the fight: victory once the boss is dead, reset if every player left
+#[allow(clippy::too_many_arguments)]
+pub fn boss_fight_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<BossConfig>,
+    fight: Option<ResMut<ActiveBossFight>>,
+    mut next_state: ResMut<NextState<InGameState>>,
+    mut shake_events: MessageWriter<ScreenShakeEvent>,
+    mut arena_query: Query<&mut BossArena>,
+    boss_query: Query<&Health, With<Boss>>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    minion_query: Query<(Entity, &Minion)>,
+) {
+    let Some(mut fight) = fight else {
+        return;
+    };
+    let boss_config = config.0.get_boss(&fight.boss_id).ok();
+
+    // Victory sequence
+    if let Some(timer) = fight.victory.as_mut() {
+        timer.tick(time.delta());
+        if timer.is_finished() {
+            if let Ok(mut arena) = arena_query.get_mut(fight.arena) {
+                arena.cleared = true;
+            }
+            next_state.set(InGameState::StagePlay);
+        }
+        return;
+    }
+
+    let Some(boss) = fight.boss else {
+        return;
+    };
+    if !boss_query.get(boss).is_ok_and(Health::is_alive) {
+        info!("Boss '{}' defeated!", fight.boss_id);
+        for (entity, minion) in minion_query.iter() {
+            if minion.summoner == boss {
+                commands.entity(entity).despawn();
+            }
+        }
+        shake_events.write(ScreenShakeEvent {
+            intensity: VICTORY_SHAKE_INTENSITY,
+            duration_secs: VICTORY_SHAKE_SECS,
+        });
+        let victory_secs = boss_config.map_or(0.0, |boss| boss.victory_secs);
+        fight.victory = Some(Timer::from_seconds(victory_secs, TimerMode::Once));
+        return;
+    }
+
+    // Everyone died or left the arena: the fight starts over next time
+    let Some(bounds) = boss_config
+        .and_then(|boss| boss.arena.as_ref())
+        .map(|arena| rect(arena.bounds))
+    else {
+        return;
+    };
+    let present = player_query.iter().any(|(transform, health)| {
+        health.is_alive() && bounds.contains(transform.translation.truncate())
+    });
+    if !present {
+        info!("Boss fight '{}' reset", fight.boss_id);
+        next_state.set(InGameState::StagePlay);
+    }
+}
+
+/// Unlock the arena and remove what is left of the fight when leaving the boss room
+pub fn end_boss_fight(
+    mut commands: Commands,
+    fight: Option<Res<ActiveBossFight>>,
+    barrier_query: Query<Entity, With<ArenaBarrier>>,
+    minion_query: Query<(Entity, &Minion)>,
+) {
+    for barrier in barrier_query.iter() {
+        commands.entity(barrier).despawn();
+    }
+
+    if let Some(boss) = fight.and_then(|fight| fight.boss) {
+        for (entity, minion) in minion_query.iter() {
+            if minion.summoner == boss {
+                commands.entity(entity).despawn();
+            }
+        }
+        if let Ok(mut entity) = commands.get_entity(boss) {
+            entity.despawn();
+        }
+    }
+
+    commands.remove_resource::<ActiveBossFight>();
+}
+
+/// Clamp a position so a collider stays inside the bounds
+fn clamp_to_bounds(position: Vec2, collider: &Collider, bounds: Option<Rect>) -> Vec2 {
+    let Some(bounds) = bounds else {
+        return position;
+    };
+    let half = collider.size / 2.0;
+    let min = bounds.min + half - collider.offset;
+    let max = (bounds.max - half - collider.offset).max(min);
+    position.clamp(min, max)
+}
+
+fn rgb((r, g, b): (f32, f32, f32)) -> Color {
+    Color::srgb(r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attack(attack: BossAttack, vulnerable: bool) -> BossAttackConfig {
+        BossAttackConfig {
+            attack,
+            windup_secs: 0.5,
+            active_secs: 1.0,
+            recovery_secs: 1.0,
+            vulnerable,
+        }
+    }
+
+    fn phases() -> Vec<BossPhaseConfig> {
+        vec![
+            BossPhaseConfig {
+                name: "calm".to_string(),
+                health_threshold: 1.0,
+                pattern: vec![
+                    attack(BossAttack::Dive { speed: 400.0 }, true),
+                    attack(BossAttack::Hover, false),
+                ],
+            },
+            BossPhaseConfig {
+                name: "angry".to_string(),
+                health_threshold: 0.5,
+                pattern: vec![attack(BossAttack::Hover, true)],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_attack_stages_cycle() {
+        let phases = phases();
+        let mut boss = BossController::new("dragon", &phases, Vec2::ZERO);
+        assert_eq!(boss.stage, AttackStage::Windup);
+        assert!(!boss.vulnerable(&phases));
+
+        assert_eq!(boss.advance(0.3, &phases, 1.0).started, None);
+        assert_eq!(
+            boss.advance(0.3, &phases, 1.0).started,
+            Some(AttackStage::Active)
+        );
+        assert_eq!(
+            boss.advance(1.0, &phases, 1.0).started,
+            Some(AttackStage::Recovery)
+        );
+        // Recovery after the dive is the vulnerability window
+        assert!(boss.vulnerable(&phases));
+
+        assert_eq!(
+            boss.advance(1.0, &phases, 1.0).started,
+            Some(AttackStage::Windup)
+        );
+        assert_eq!(boss.step, 1);
+        assert!(!boss.vulnerable(&phases));
+    }
+
+    #[test]
+    fn test_phases_follow_health() {
+        let phases = phases();
+        assert_eq!(phase_index(&phases, 1.0), 0);
+        assert_eq!(phase_index(&phases, 0.6), 0);
+        assert_eq!(phase_index(&phases, 0.5), 1);
+        assert_eq!(phase_index(&phases, 0.1), 1);
+
+        let mut boss = BossController::new("dragon", &phases, Vec2::ZERO);
+        boss.advance(0.6, &phases, 1.0);
+        let tick = boss.advance(0.1, &phases, 0.4);
+        assert!(tick.phase_changed);
+        assert_eq!(
+            (boss.phase, boss.step, boss.stage),
+            (1, 0, AttackStage::Windup)
+        );
+
+        // Healing does not bring the previous phase back
+        assert!(!boss.advance(0.1, &phases, 1.0).phase_changed);
+        assert_eq!(boss.phase, 1);
+
+        // Without a pattern the boss is always open to damage
+        assert!(BossController::new("dragon", &[], Vec2::ZERO).vulnerable(&[]));
+    }
+}
@@ -3,6 +3,9 @@
 //! This module contains all ECS components used for enemy entities.
 
 use bevy::prelude::*;
+use tropical_fox_combat::{HitImpact, ProjectileEmitter, ProjectileSpawn};
+
+use crate::navigation::NavWaypoint;
 
 /// Marker component for enemy entities
 #[derive(Component, Debug, Clone)]
@@ -29,6 +32,29 @@ impl Enemy {
     }
 }
 
+/// Marker component for boss enemies (shown on the HUD boss health bar)
+#[derive(Component, Debug, Default)]
+pub struct Boss;
+
+/// Part of a telegraphed attack (boss attacks and enemy melee attacks)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AttackStage {
+    /// Telegraph before the attack
+    #[default]
+    Windup,
+    /// The attack itself
+    Active,
+    /// Pause after the attack, open to punishment
+    Recovery,
+}
+
+/// Enemy spawned by another enemy's `SpawnMinion` action
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Minion {
+    /// Enemy that spawned this minion
+    pub summoner: Entity,
+}
+
 /// Enemy statistics component
 #[derive(Component, Debug, Clone)]
 pub struct EnemyStats {
@@ -134,6 +160,18 @@ pub struct ChaseAI {
     pub lost_sight_timer: f32,
     /// Time to wait before losing aggro (seconds)
     pub lose_aggro_time: f32,
+    /// Full width of the view cone in front of the enemy (degrees, 360 = all around)
+    pub view_angle: f32,
+    /// Whether `Ground` and `Wall` colliders block sight
+    pub line_of_sight: bool,
+    /// Where the player was last seen (searched after losing sight)
+    pub last_known_position: Option<Vec2>,
+    /// Time until the enemy looks the other way while searching
+    pub search_turn_timer: f32,
+    /// Remaining waypoints towards a target on another platform
+    pub path: Vec<NavWaypoint>,
+    /// Time until the path is planned again
+    pub repath_timer: f32,
 }
 
 impl ChaseAI {
@@ -145,8 +183,44 @@ impl ChaseAI {
             is_chasing: false,
             lost_sight_timer: 0.0,
             lose_aggro_time: 2.0,
+            view_angle: 120.0,
+            line_of_sight: true,
+            last_known_position: None,
+            search_turn_timer: 0.0,
+            path: Vec::new(),
+            repath_timer: 0.0,
         }
     }
+
+    /// Set the view cone, line of sight and how long to search after losing sight
+    pub fn with_perception(
+        mut self,
+        view_angle: f32,
+        line_of_sight: bool,
+        lose_aggro_time: f32,
+    ) -> Self {
+        self.view_angle = view_angle;
+        self.line_of_sight = line_of_sight;
+        self.lose_aggro_time = lose_aggro_time;
+        self
+    }
+
+    /// Remember the player's position after seeing them
+    pub fn spot(&mut self, position: Vec2) {
+        self.is_chasing = true;
+        self.lost_sight_timer = 0.0;
+        self.last_known_position = Some(position);
+    }
+
+    /// Count time out of sight; returns false once the enemy gives up
+    pub fn lose_sight(&mut self, delta: f32) -> bool {
+        self.lost_sight_timer += delta;
+        if self.lost_sight_timer >= self.lose_aggro_time {
+            self.is_chasing = false;
+            self.last_known_position = None;
+        }
+        self.is_chasing
+    }
 }
 
 impl Default for ChaseAI {
@@ -234,6 +308,8 @@ pub struct ContactDamage {
     pub cooldown: f32,
     /// Current cooldown timer
     pub cooldown_timer: f32,
+    /// Hit stop and screen shake on hit (None uses the combat config)
+    pub impact: Option<HitImpact>,
 }
 
 impl ContactDamage {
@@ -244,9 +320,16 @@ impl ContactDamage {
             knockback_force,
             cooldown,
             cooldown_timer: 0.0,
+            impact: None,
         }
     }
 
+    /// Set the hit stop and screen shake on hit
+    pub fn with_impact(mut self, impact: Option<HitImpact>) -> Self {
+        self.impact = impact;
+        self
+    }
+
     /// Check if contact damage can be applied
     pub fn can_damage(&self) -> bool {
         self.cooldown_timer <= 0.0
@@ -274,10 +357,10 @@ impl Default for ContactDamage {
 /// Projectile shooter component - enemy can fire projectiles
 #[derive(Component, Debug, Clone)]
 pub struct ProjectileShooter {
-    /// Damage per projectile
-    pub damage: f32,
-    /// Projectile speed (pixels per second)
-    pub projectile_speed: f32,
+    /// Projectile fired at the player
+    pub projectile: ProjectileSpawn,
+    /// Spread and burst of each shot
+    pub emitter: ProjectileEmitter,
     /// Time between shots (seconds)
     pub fire_rate: f32,
     /// Current fire cooldown timer
@@ -288,10 +371,15 @@ pub struct ProjectileShooter {
 
 impl ProjectileShooter {
     /// Create a new projectile shooter
-    pub fn new(damage: f32, projectile_speed: f32, fire_rate: f32, range: f32) -> Self {
+    pub fn new(
+        projectile: ProjectileSpawn,
+        emitter: ProjectileEmitter,
+        fire_rate: f32,
+        range: f32,
+    ) -> Self {
         Self {
-            damage,
-            projectile_speed,
+            projectile,
+            emitter,
             fire_rate,
             fire_timer: 0.0,
             range,
@@ -318,32 +406,12 @@ impl ProjectileShooter {
 
 impl Default for ProjectileShooter {
     fn default() -> Self {
-        Self::new(5.0, 200.0, 2.0, 300.0)
-    }
-}
-
-/// Enemy projectile component
-#[derive(Component, Debug)]
-pub struct EnemyProjectile {
-    /// Damage dealt on hit
-    pub damage: f32,
-    /// Knockback force
-    pub knockback: f32,
-    /// Velocity of the projectile
-    pub velocity: Vec2,
-    /// Lifetime timer
-    pub lifetime: Timer,
-}
-
-impl EnemyProjectile {
-    /// Create a new enemy projectile
-    pub fn new(damage: f32, knockback: f32, velocity: Vec2, lifetime_secs: f32) -> Self {
-        Self {
-            damage,
-            knockback,
-            velocity,
-            lifetime: Timer::from_seconds(lifetime_secs, TimerMode::Once),
-        }
+        Self::new(
+            ProjectileSpawn::default(),
+            ProjectileEmitter::default(),
+            2.0,
+            300.0,
+        )
     }
 }
 
@@ -390,7 +458,12 @@ mod tests {
 
     #[test]
     fn test_projectile_shooter() {
-        let mut shooter = ProjectileShooter::new(5.0, 200.0, 1.0, 300.0);
+        let mut shooter = ProjectileShooter::new(
+            ProjectileSpawn::default(),
+            ProjectileEmitter::default(),
+            1.0,
+            300.0,
+        );
         assert!(shooter.can_fire());
 
         shooter.reset_timer();
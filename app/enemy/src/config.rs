@@ -1,7 +1,9 @@
 //! Enemy and boss configuration loading
 
+pub mod behavior;
 pub mod bosses;
 pub mod enemies;
 
+pub use behavior::*;
 pub use bosses::*;
 pub use enemies::*;
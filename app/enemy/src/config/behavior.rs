@@ -0,0 +1,246 @@
+//! Data-driven enemy AI state machines
+//!
+//! A state machine is a set of named states. Each state runs one built-in
+//! movement node (the `EnemyAI` behaviours), fires actions when it is entered
+//! and optionally on a repeating timer, and lists transitions that are checked
+//! in order every frame.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::enemies::AIBehaviorType;
+
+/// Built-in movement node a state runs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum AINode {
+    /// Stand still
+    #[default]
+    Idle,
+    /// Patrol around the spawn point (`patrol_distance`, `wait_time`)
+    Patrol,
+    /// Chase players in sight (`detection_range`, `attack_range`, view settings)
+    Chase,
+    /// Fly the configured `flying_pattern`
+    Flying,
+    /// Walk to a point relative to the spawn position and stay there
+    MoveTo(f32, f32),
+}
+
+impl From<&AIBehaviorType> for AINode {
+    fn from(behavior: &AIBehaviorType) -> Self {
+        match behavior {
+            AIBehaviorType::Idle => Self::Idle,
+            AIBehaviorType::Patrol => Self::Patrol,
+            AIBehaviorType::Chase => Self::Chase,
+            AIBehaviorType::Flying => Self::Flying,
+        }
+    }
+}
+
+/// Condition for leaving a state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AICondition {
+    /// A living player is within this distance (pixels)
+    PlayerInRange(f32),
+    /// A living player within this distance is in the view cone and line of sight
+    PlayerVisible(f32),
+    /// Health is below this fraction of maximum (0.0 to 1.0)
+    HealthBelow(f32),
+    /// The current state has lasted this long (seconds)
+    Timer(f32),
+    /// The inner condition is false
+    Not(Box<AICondition>),
+    /// Every inner condition is true
+    All(Vec<AICondition>),
+    /// At least one inner condition is true
+    Any(Vec<AICondition>),
+}
+
+/// What the enemy knows when conditions are checked
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AIConditionInput {
+    /// Distance to the nearest living player
+    pub player_distance: Option<f32>,
+    /// Distance to the nearest player in sight
+    pub visible_player_distance: Option<f32>,
+    /// Current health as a fraction of maximum
+    pub health_ratio: f32,
+    /// Time spent in the current state (seconds)
+    pub time_in_state: f32,
+}
+
+impl AICondition {
+    /// Whether the condition holds
+    pub fn evaluate(&self, input: &AIConditionInput) -> bool {
+        match self {
+            Self::PlayerInRange(range) => input
+                .player_distance
+                .is_some_and(|distance| distance <= *range),
+            Self::PlayerVisible(range) => input
+                .visible_player_distance
+                .is_some_and(|distance| distance <= *range),
+            Self::HealthBelow(fraction) => input.health_ratio < *fraction,
+            Self::Timer(secs) => input.time_in_state >= *secs,
+            Self::Not(condition) => !condition.evaluate(input),
+            Self::All(conditions) => conditions.iter().all(|c| c.evaluate(input)),
+            Self::Any(conditions) => conditions.iter().any(|c| c.evaluate(input)),
+        }
+    }
+
+    /// Longest `PlayerVisible` range this condition checks, if any
+    ///
+    /// Sight needs raycasts, so it is only computed when a transition asks for it.
+    pub fn sight_range(&self) -> Option<f32> {
+        match self {
+            Self::PlayerVisible(range) => Some(*range),
+            Self::Not(condition) => condition.sight_range(),
+            Self::All(conditions) | Self::Any(conditions) => conditions
+                .iter()
+                .filter_map(AICondition::sight_range)
+                .reduce(f32::max),
+            _ => None,
+        }
+    }
+}
+
+/// One-shot action run by a state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AIAction {
+    /// Fire the enemy's projectile volley at the nearest player (needs `projectile.enabled`)
+    Shoot,
+    /// Jump with this launch speed when standing on ground
+    Jump(f32),
+    /// Play an animation until the state changes
+    PlayAnimation(String),
+    /// Spawn an enemy of this type next to the enemy (up to `max_minions` alive)
+    SpawnMinion(String),
+}
+
+/// Transition to another state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AITransition {
+    /// Name of the target state
+    pub to: String,
+    /// Condition that triggers the transition
+    pub when: AICondition,
+}
+
+/// One state of an AI state machine
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct AIStateConfig {
+    /// Built-in movement node run while in this state
+    #[serde(default)]
+    pub node: AINode,
+    /// Actions run when the state is entered
+    #[serde(default)]
+    pub on_enter: Vec<AIAction>,
+    /// Actions run every `repeat_secs` while in the state
+    #[serde(default)]
+    pub repeat: Vec<AIAction>,
+    /// Interval of the repeated actions (seconds)
+    #[serde(default = "default_repeat_secs")]
+    pub repeat_secs: f32,
+    /// Transitions checked in order; the first that holds is taken
+    #[serde(default)]
+    pub transitions: Vec<AITransition>,
+}
+
+fn default_repeat_secs() -> f32 {
+    1.0
+}
+
+/// AI state machine for an enemy type
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct AIStateMachineConfig {
+    /// State the enemy spawns in
+    pub initial: String,
+    /// States by name
+    pub states: HashMap<String, AIStateConfig>,
+    /// Most minions alive at once from `SpawnMinion`
+    #[serde(default = "default_max_minions")]
+    pub max_minions: u32,
+}
+
+fn default_max_minions() -> u32 {
+    3
+}
+
+impl AIStateMachineConfig {
+    /// Get a state by name
+    pub fn state(&self, name: &str) -> Option<&AIStateConfig> {
+        self.states.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_machine_parse() {
+        let config: AIStateMachineConfig = ron::from_str(
+            r#"(
+                initial: "patrol",
+                states: {
+                    "patrol": (
+                        node: Patrol,
+                        transitions: [(to: "chase", when: PlayerVisible(150.0))],
+                    ),
+                    "chase": (
+                        node: Chase,
+                        on_enter: [PlayAnimation("run"), Jump(200.0)],
+                        repeat: [Shoot],
+                        repeat_secs: 2.0,
+                        transitions: [
+                            (to: "summon", when: All([HealthBelow(0.5), Timer(3.0)])),
+                            (to: "patrol", when: Not(PlayerInRange(300.0))),
+                        ],
+                    ),
+                    "summon": (node: MoveTo(0.0, 0.0), on_enter: [SpawnMinion("ant")]),
+                },
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.initial, "patrol");
+        assert_eq!(config.max_minions, 3);
+        let chase = config.state("chase").unwrap();
+        assert_eq!(chase.node, AINode::Chase);
+        assert_eq!(chase.repeat, vec![AIAction::Shoot]);
+        assert_eq!(chase.transitions.len(), 2);
+        assert_eq!(config.state("summon").unwrap().repeat_secs, 1.0);
+    }
+
+    #[test]
+    fn test_condition_evaluate() {
+        let input = AIConditionInput {
+            player_distance: Some(120.0),
+            visible_player_distance: None,
+            health_ratio: 0.4,
+            time_in_state: 1.5,
+        };
+
+        assert!(AICondition::PlayerInRange(150.0).evaluate(&input));
+        assert!(!AICondition::PlayerInRange(100.0).evaluate(&input));
+        assert!(!AICondition::PlayerVisible(150.0).evaluate(&input));
+        assert!(AICondition::HealthBelow(0.5).evaluate(&input));
+        assert!(
+            AICondition::All(vec![
+                AICondition::Timer(1.0),
+                AICondition::Not(Box::new(AICondition::PlayerVisible(500.0))),
+            ])
+            .evaluate(&input)
+        );
+        assert!(
+            !AICondition::Any(vec![AICondition::Timer(2.0), AICondition::HealthBelow(0.2)])
+                .evaluate(&input)
+        );
+
+        let condition = AICondition::Any(vec![
+            AICondition::PlayerVisible(100.0),
+            AICondition::Not(Box::new(AICondition::PlayerVisible(250.0))),
+        ]);
+        assert_eq!(condition.sight_range(), Some(250.0));
+        assert_eq!(AICondition::Timer(1.0).sight_range(), None);
+    }
+}
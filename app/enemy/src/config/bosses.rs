@@ -1,6 +1,7 @@
 //! Boss configuration and management
 //!
-//! Handles boss character definitions
+//! Handles boss character definitions: stats, health-threshold phases with
+//! timed attack patterns, and the arena the fight is locked into.
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -8,6 +9,158 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
+use tropical_fox_combat::{DamageType, HitImpact};
+
+use super::enemies::{ColliderConfig, ProjectileConfig};
+
+/// Boss statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossStatsConfig {
+    /// Maximum health
+    #[serde(default = "default_boss_health")]
+    pub health: f32,
+    /// Flying speed between attacks (pixels per second)
+    #[serde(default = "default_boss_move_speed")]
+    pub move_speed: f32,
+    /// Contact damage dealt to players (dives hit with this)
+    #[serde(default = "default_boss_damage")]
+    pub damage: f32,
+    /// Knockback force of contact damage
+    #[serde(default = "default_boss_knockback")]
+    pub knockback_force: f32,
+    /// Score value when defeated
+    #[serde(default = "default_boss_score")]
+    pub score_value: u32,
+}
+
+fn default_boss_health() -> f32 {
+    300.0
+}
+fn default_boss_move_speed() -> f32 {
+    120.0
+}
+fn default_boss_damage() -> f32 {
+    15.0
+}
+fn default_boss_knockback() -> f32 {
+    250.0
+}
+fn default_boss_score() -> u32 {
+    1000
+}
+
+impl Default for BossStatsConfig {
+    fn default() -> Self {
+        Self {
+            health: default_boss_health(),
+            move_speed: default_boss_move_speed(),
+            damage: default_boss_damage(),
+            knockback_force: default_boss_knockback(),
+            score_value: default_boss_score(),
+        }
+    }
+}
+
+/// What a boss does during the active part of an attack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BossAttack {
+    /// Keep hovering over the home position (rests and vulnerability windows)
+    Hover,
+    /// Fly at the player's position taken at the end of the wind-up, then return home
+    Dive {
+        /// Dive speed (pixels per second)
+        speed: f32,
+    },
+    /// Fire projectile volleys at the nearest player
+    FireBreath {
+        /// Projectile fired (the `enabled` flag is ignored)
+        projectile: ProjectileConfig,
+        /// Time between volleys (seconds)
+        #[serde(default = "default_breath_interval_secs")]
+        interval_secs: f32,
+    },
+    /// Spawn enemies from `enemies.ron` next to the boss
+    SummonMinions {
+        /// Enemy type ID
+        enemy: String,
+        /// Minions per summon (capped by `max_minions` alive)
+        count: u32,
+    },
+}
+
+fn default_breath_interval_secs() -> f32 {
+    0.25
+}
+
+/// One attack of a phase pattern: wind-up (telegraph), active, recovery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossAttackConfig {
+    /// Attack performed in the active part
+    pub attack: BossAttack,
+    /// Telegraph before the attack (seconds)
+    #[serde(default = "default_windup_secs")]
+    pub windup_secs: f32,
+    /// Duration of the attack (seconds)
+    #[serde(default = "default_active_secs")]
+    pub active_secs: f32,
+    /// Pause after the attack (seconds)
+    #[serde(default = "default_recovery_secs")]
+    pub recovery_secs: f32,
+    /// Whether the boss can be damaged during the recovery
+    #[serde(default)]
+    pub vulnerable: bool,
+}
+
+fn default_windup_secs() -> f32 {
+    0.6
+}
+fn default_active_secs() -> f32 {
+    1.0
+}
+fn default_recovery_secs() -> f32 {
+    1.0
+}
+
+/// Health-threshold phase with its attack pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossPhaseConfig {
+    /// Name shown in logs
+    #[serde(default)]
+    pub name: String,
+    /// Phase starts once health falls to this fraction of maximum (first phase: 1.0)
+    #[serde(default = "default_health_threshold")]
+    pub health_threshold: f32,
+    /// Attacks performed in order, looping
+    #[serde(default)]
+    pub pattern: Vec<BossAttackConfig>,
+}
+
+fn default_health_threshold() -> f32 {
+    1.0
+}
+
+/// Wall segment closing the arena during the fight
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArenaBarrierConfig {
+    /// Center position
+    pub position: (f32, f32),
+    /// Size (width, height)
+    pub size: (f32, f32),
+}
+
+/// Boss arena: where the fight starts and what it locks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BossArenaConfig {
+    /// Area a player enters to start the fight (min corner, max corner)
+    pub trigger: ((f32, f32), (f32, f32)),
+    /// Area of the fight; it resets when no living player is inside (min corner, max corner)
+    pub bounds: ((f32, f32), (f32, f32)),
+    /// Boss spawn and home position
+    pub spawn: (f32, f32),
+    /// Walls spawned while the arena is locked
+    #[serde(default)]
+    pub barriers: Vec<ArenaBarrierConfig>,
+}
 
 /// Boss character definition with asset paths
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +174,84 @@ pub struct BossTypeConfig {
     /// Description of the boss
     #[serde(default)]
     pub description: String,
+    /// Boss statistics
+    #[serde(default)]
+    pub stats: BossStatsConfig,
+    /// Collider configuration
+    #[serde(default)]
+    pub collider: ColliderConfig,
+    /// Sprite display size (width, height)
+    #[serde(default = "default_display_size")]
+    pub display_size: (f32, f32),
+    /// Damage multipliers by damage type while vulnerable
+    #[serde(default)]
+    pub resistances: HashMap<DamageType, f32>,
+    /// Hit stop and screen shake when the boss hits a player (None uses `combat.ron`)
+    #[serde(default)]
+    pub impact: Option<HitImpact>,
+    /// Phases ordered by descending `health_threshold`
+    #[serde(default)]
+    pub phases: Vec<BossPhaseConfig>,
+    /// Most summoned minions alive at once
+    #[serde(default = "default_boss_max_minions")]
+    pub max_minions: u32,
+    /// Height of the idle hover bob (pixels)
+    #[serde(default = "default_hover_amplitude")]
+    pub hover_amplitude: f32,
+    /// Sprite tint blinking during wind-ups (RGB)
+    #[serde(default = "default_telegraph_color")]
+    pub telegraph_color: (f32, f32, f32),
+    /// Sprite tint during vulnerability windows (RGB)
+    #[serde(default = "default_vulnerable_color")]
+    pub vulnerable_color: (f32, f32, f32),
+    /// Length of the victory sequence before the arena unlocks (seconds)
+    #[serde(default = "default_victory_secs")]
+    pub victory_secs: f32,
+    /// Arena the boss is fought in (None = never spawned)
+    #[serde(default)]
+    pub arena: Option<BossArenaConfig>,
+}
+
+fn default_display_size() -> (f32, f32) {
+    (160.0, 128.0)
+}
+fn default_boss_max_minions() -> u32 {
+    4
+}
+fn default_hover_amplitude() -> f32 {
+    12.0
+}
+fn default_telegraph_color() -> (f32, f32, f32) {
+    (1.0, 0.45, 0.2)
+}
+fn default_vulnerable_color() -> (f32, f32, f32) {
+    (0.6, 0.8, 1.0)
+}
+fn default_victory_secs() -> f32 {
+    3.0
+}
+
+impl Default for BossTypeConfig {
+    fn default() -> Self {
+        Self {
+            id: "unknown".to_string(),
+            name: "Unknown Boss".to_string(),
+            animation_config_path: String::new(),
+            description: String::new(),
+            stats: BossStatsConfig::default(),
+            collider: ColliderConfig::default(),
+            display_size: default_display_size(),
+            resistances: HashMap::new(),
+            impact: None,
+            phases: Vec::new(),
+            max_minions: default_boss_max_minions(),
+            hover_amplitude: default_hover_amplitude(),
+            telegraph_color: default_telegraph_color(),
+            vulnerable_color: default_vulnerable_color(),
+            victory_secs: default_victory_secs(),
+            arena: None,
+        }
+    }
 }
 
 /// Bosses configuration file format
@@ -90,6 +321,7 @@ mod tests {
             animation_config_path:
                 "graphics/characters/bosses/sunny_dragon/sunny_dragon_animations.ron".to_string(),
             description: "A fearsome dragon".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(boss.id, "sunny_dragon");
@@ -108,6 +340,7 @@ mod tests {
                     "graphics/characters/bosses/sunny_dragon/sunny_dragon_animations.ron"
                         .to_string(),
                 description: "A fearsome dragon".to_string(),
+                ..Default::default()
             },
         );
 
@@ -123,4 +356,51 @@ mod tests {
         let config = BossesConfig::default();
         assert!(config.bosses.is_empty());
     }
+
+    #[test]
+    fn test_boss_phases_parse() {
+        let boss: BossTypeConfig = ron::from_str(
+            r#"(
+                id: "sunny_dragon",
+                name: "Sunny Dragon",
+                animation_config_path: "",
+                stats: (health: 400.0),
+                phases: [
+                    (name: "calm", pattern: [
+                        (attack: Dive(speed: 420.0), recovery_secs: 1.5, vulnerable: true),
+                        (attack: FireBreath(projectile: (damage: 6.0, damage_type: Fire))),
+                    ]),
+                    (health_threshold: 0.5, pattern: [
+                        (attack: SummonMinions(enemy: "ant", count: 2), windup_secs: 1.0),
+                    ]),
+                ],
+                arena: Some((
+                    trigger: ((-10.0, 0.0), (10.0, 20.0)),
+                    bounds: ((-300.0, 0.0), (300.0, 300.0)),
+                    spawn: (0.0, 150.0),
+                    barriers: [(position: (0.0, 316.0), size: (600.0, 32.0))],
+                )),
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(boss.stats.health, 400.0);
+        assert_eq!(boss.stats.move_speed, 120.0);
+        assert_eq!(boss.phases.len(), 2);
+        assert_eq!(boss.phases[0].health_threshold, 1.0);
+        let dive = &boss.phases[0].pattern[0];
+        assert!(matches!(dive.attack, BossAttack::Dive { speed } if speed == 420.0));
+        assert!(dive.vulnerable);
+        assert_eq!(dive.windup_secs, 0.6);
+        let BossAttack::FireBreath {
+            projectile,
+            interval_secs,
+        } = &boss.phases[0].pattern[1].attack
+        else {
+            panic!("expected fire breath");
+        };
+        assert_eq!(projectile.damage_type, DamageType::Fire);
+        assert_eq!(*interval_secs, 0.25);
+        assert_eq!(boss.arena.unwrap().barriers.len(), 1);
+    }
 }
@@ -8,6 +8,12 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
+use tropical_fox_combat::{
+    DamageType, Faction, HitImpact, HitReaction, ProjectileEmitter, ProjectileMotion,
+    ProjectileSpawn, StatusEffect, StatusEffectRules,
+};
+
+use super::behavior::AIStateMachineConfig;
 
 /// Enemy AI behavior type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -23,6 +29,18 @@ pub enum AIBehaviorType {
     Flying,
 }
 
+/// What a walking enemy does at a ledge it cannot jump
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum EdgeBehavior {
+    /// Turn around (chasers hold at the edge instead)
+    #[default]
+    Turn,
+    /// Stand still at the edge (patrols wait `wait_time`, then turn)
+    Stop,
+    /// Walk off if there is ground within `max_drop`, otherwise turn
+    JumpDown,
+}
+
 /// Flying pattern configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FlyingPatternConfig {
@@ -69,6 +87,27 @@ pub struct AIConfig {
     /// Flying pattern configuration
     #[serde(default)]
     pub flying_pattern: Option<FlyingPatternConfig>,
+    /// What walking enemies do at ledges (walls always turn or stop them)
+    #[serde(default)]
+    pub edge_behavior: EdgeBehavior,
+    /// Upward launch speed chasers use to jump steps and gaps (0 = never jumps)
+    #[serde(default)]
+    pub jump_force: f32,
+    /// Deepest drop `JumpDown` walks off (pixels)
+    #[serde(default = "default_max_drop")]
+    pub max_drop: f32,
+    /// Full width of the chase view cone (degrees, 360 = all around)
+    #[serde(default = "default_view_angle")]
+    pub view_angle: f32,
+    /// Whether ground and walls block a chaser's sight
+    #[serde(default = "default_line_of_sight")]
+    pub line_of_sight: bool,
+    /// Time a chaser searches the last known position before giving up (seconds)
+    #[serde(default = "default_lose_aggro_time")]
+    pub lose_aggro_time: f32,
+    /// Data-driven state machine; replaces `behavior` when set
+    #[serde(default)]
+    pub state_machine: Option<AIStateMachineConfig>,
 }
 
 fn default_detection_range() -> f32 {
@@ -83,6 +122,18 @@ fn default_patrol_distance() -> f32 {
 fn default_wait_time() -> f32 {
     1.0
 }
+fn default_max_drop() -> f32 {
+    96.0
+}
+fn default_view_angle() -> f32 {
+    120.0
+}
+fn default_line_of_sight() -> bool {
+    true
+}
+fn default_lose_aggro_time() -> f32 {
+    2.0
+}
 
 impl Default for AIConfig {
     fn default() -> Self {
@@ -93,6 +144,13 @@ impl Default for AIConfig {
             patrol_distance: 100.0,
             wait_time: 1.0,
             flying_pattern: None,
+            edge_behavior: EdgeBehavior::Turn,
+            jump_force: 0.0,
+            max_drop: 96.0,
+            view_angle: 120.0,
+            line_of_sight: true,
+            lose_aggro_time: 2.0,
+            state_machine: None,
         }
     }
 }
@@ -182,6 +240,65 @@ pub struct ProjectileConfig {
     /// Range to start shooting
     #[serde(default = "default_shoot_range")]
     pub range: f32,
+    /// Elemental type of the damage
+    #[serde(default)]
+    pub damage_type: DamageType,
+    /// Status effect applied on hit
+    #[serde(default)]
+    pub status: Option<StatusEffect>,
+    /// Knockback force on hit
+    #[serde(default = "default_projectile_knockback")]
+    pub knockback: f32,
+    /// Projectile lifetime (seconds)
+    #[serde(default = "default_projectile_lifetime_secs")]
+    pub lifetime_secs: f32,
+    /// Projectile hitbox size (width, height)
+    #[serde(default = "default_projectile_size")]
+    pub size: (f32, f32),
+    /// Movement pattern (Straight, Arc, Homing, Sine, Boomerang)
+    #[serde(default)]
+    pub motion: ProjectileMotion,
+    /// Number of targets the projectile passes through before stopping
+    #[serde(default)]
+    pub pierce: u32,
+    /// Number of times the projectile bounces off walls and ground
+    #[serde(default)]
+    pub bounces: u32,
+    /// Whether walls and ground stop (or bounce) the projectile
+    #[serde(default = "default_wall_collision")]
+    pub wall_collision: bool,
+    /// Projectiles per shot (spread and burst)
+    #[serde(default)]
+    pub emitter: ProjectileEmitter,
+    /// Effect played while the projectile flies (name from `effects.ron`)
+    #[serde(default)]
+    pub projectile_effect: Option<String>,
+    /// Effect played on impact (name from `effects.ron`)
+    #[serde(default)]
+    pub hit_effect: Option<String>,
+}
+
+impl ProjectileConfig {
+    /// Projectile fired by this enemy
+    pub fn projectile(&self, impact: Option<HitImpact>) -> ProjectileSpawn {
+        ProjectileSpawn {
+            damage: self.damage,
+            damage_type: self.damage_type,
+            status: self.status,
+            knockback: self.knockback,
+            impact,
+            speed: self.speed,
+            lifetime_secs: self.lifetime_secs,
+            size: Vec2::new(self.size.0, self.size.1),
+            motion: self.motion,
+            pierce: self.pierce,
+            bounces: self.bounces,
+            wall_collision: self.wall_collision,
+            effect: self.projectile_effect.clone(),
+            hit_effect: self.hit_effect.clone(),
+            ..default()
+        }
+    }
 }
 
 fn default_projectile_damage() -> f32 {
@@ -196,6 +313,18 @@ fn default_fire_rate() -> f32 {
 fn default_shoot_range() -> f32 {
     300.0
 }
+fn default_projectile_knockback() -> f32 {
+    100.0
+}
+fn default_projectile_lifetime_secs() -> f32 {
+    5.0
+}
+fn default_projectile_size() -> (f32, f32) {
+    (8.0, 8.0)
+}
+fn default_wall_collision() -> bool {
+    true
+}
 
 impl Default for ProjectileConfig {
     fn default() -> Self {
@@ -205,6 +334,130 @@ impl Default for ProjectileConfig {
             speed: 200.0,
             fire_rate: 2.0,
             range: 300.0,
+            damage_type: DamageType::Physical,
+            status: None,
+            knockback: default_projectile_knockback(),
+            lifetime_secs: default_projectile_lifetime_secs(),
+            size: default_projectile_size(),
+            motion: ProjectileMotion::Straight,
+            pierce: 0,
+            bounces: 0,
+            wall_collision: true,
+            emitter: ProjectileEmitter::default(),
+            projectile_effect: None,
+            hit_effect: None,
+        }
+    }
+}
+
+/// Melee attack configuration
+///
+/// The enemy stops and telegraphs the attack (wind-up), then its hitbox is
+/// active in front of it, then it stands still and can be punished (recovery).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeleeAttackConfig {
+    /// Distance to a player at which the attack starts
+    #[serde(default = "default_melee_range")]
+    pub range: f32,
+    /// Damage dealt by the hitbox
+    #[serde(default = "default_melee_damage")]
+    pub damage: f32,
+    /// Elemental type of the damage
+    #[serde(default)]
+    pub damage_type: DamageType,
+    /// Status effect applied on hit
+    #[serde(default)]
+    pub status: Option<StatusEffect>,
+    /// Knockback (x away from the enemy, y up)
+    #[serde(default = "default_melee_knockback")]
+    pub knockback: (f32, f32),
+    /// Hitbox size (width, height)
+    #[serde(default = "default_melee_hitbox_size")]
+    pub hitbox_size: (f32, f32),
+    /// Hitbox offset from the enemy center (x towards the facing direction)
+    #[serde(default = "default_melee_hitbox_offset")]
+    pub hitbox_offset: (f32, f32),
+    /// Telegraph before the hitbox appears (seconds)
+    #[serde(default = "default_melee_windup_secs")]
+    pub windup_secs: f32,
+    /// Time the hitbox is active (seconds)
+    #[serde(default = "default_melee_active_secs")]
+    pub active_secs: f32,
+    /// Time the enemy stays open after the attack (seconds)
+    #[serde(default = "default_melee_recovery_secs")]
+    pub recovery_secs: f32,
+    /// Time before the next attack can start (seconds, counted after the recovery)
+    #[serde(default = "default_melee_cooldown_secs")]
+    pub cooldown_secs: f32,
+    /// Sprite flash color during the wind-up (RGB)
+    #[serde(default = "default_melee_telegraph_color")]
+    pub telegraph_color: (f32, f32, f32),
+    /// Animation played during the wind-up (if the enemy has it)
+    #[serde(default = "default_melee_windup_animation")]
+    pub windup_animation: String,
+    /// Animation played during the active part and recovery (if the enemy has it)
+    #[serde(default = "default_melee_attack_animation")]
+    pub attack_animation: String,
+    /// Effect played when the hitbox appears (name from `effects.ron`)
+    #[serde(default)]
+    pub effect: Option<String>,
+}
+
+fn default_melee_range() -> f32 {
+    48.0
+}
+fn default_melee_damage() -> f32 {
+    15.0
+}
+fn default_melee_knockback() -> (f32, f32) {
+    (220.0, 120.0)
+}
+fn default_melee_hitbox_size() -> (f32, f32) {
+    (36.0, 32.0)
+}
+fn default_melee_hitbox_offset() -> (f32, f32) {
+    (30.0, 0.0)
+}
+fn default_melee_windup_secs() -> f32 {
+    0.5
+}
+fn default_melee_active_secs() -> f32 {
+    0.15
+}
+fn default_melee_recovery_secs() -> f32 {
+    0.6
+}
+fn default_melee_cooldown_secs() -> f32 {
+    0.8
+}
+fn default_melee_telegraph_color() -> (f32, f32, f32) {
+    (1.0, 0.85, 0.3)
+}
+fn default_melee_windup_animation() -> String {
+    "windup".to_string()
+}
+fn default_melee_attack_animation() -> String {
+    "attack".to_string()
+}
+
+impl Default for MeleeAttackConfig {
+    fn default() -> Self {
+        Self {
+            range: default_melee_range(),
+            damage: default_melee_damage(),
+            damage_type: DamageType::Physical,
+            status: None,
+            knockback: default_melee_knockback(),
+            hitbox_size: default_melee_hitbox_size(),
+            hitbox_offset: default_melee_hitbox_offset(),
+            windup_secs: default_melee_windup_secs(),
+            active_secs: default_melee_active_secs(),
+            recovery_secs: default_melee_recovery_secs(),
+            cooldown_secs: default_melee_cooldown_secs(),
+            telegraph_color: default_melee_telegraph_color(),
+            windup_animation: default_melee_windup_animation(),
+            attack_animation: default_melee_attack_animation(),
+            effect: None,
         }
     }
 }
@@ -262,9 +515,27 @@ pub struct EnemyTypeConfig {
     /// Projectile configuration
     #[serde(default)]
     pub projectile: ProjectileConfig,
+    /// Melee attack (None = contact damage only)
+    #[serde(default)]
+    pub melee: Option<MeleeAttackConfig>,
     /// Drop configuration
     #[serde(default)]
     pub drop: DropConfig,
+    /// Damage multipliers by damage type (>1.0 weakness, <1.0 resistance, 0.0 immunity)
+    #[serde(default)]
+    pub resistances: HashMap<DamageType, f32>,
+    /// Status effect immunities and stacking overrides
+    #[serde(default)]
+    pub status_rules: StatusEffectRules,
+    /// Knockback resistance, hitstun, i-frames and super-armor when hit
+    #[serde(default)]
+    pub hit_reaction: HitReaction,
+    /// Hit stop and screen shake when this enemy hits a player (None uses `combat.ron`)
+    #[serde(default)]
+    pub impact: Option<HitImpact>,
+    /// Side in the hostility matrix (`factions.ron`); `Neutral` for harmless critters
+    #[serde(default = "default_faction")]
+    pub faction: Faction,
     /// Whether enemy is affected by gravity
     #[serde(default = "default_gravity")]
     pub has_gravity: bool,
@@ -274,6 +545,10 @@ fn default_gravity() -> bool {
     true
 }
 
+fn default_faction() -> Faction {
+    Faction::Enemy
+}
+
 impl Default for EnemyTypeConfig {
     fn default() -> Self {
         Self {
@@ -285,7 +560,13 @@ impl Default for EnemyTypeConfig {
             ai: AIConfig::default(),
             collider: ColliderConfig::default(),
             projectile: ProjectileConfig::default(),
+            melee: None,
             drop: DropConfig::default(),
+            resistances: HashMap::new(),
+            status_rules: StatusEffectRules::default(),
+            hit_reaction: HitReaction::default(),
+            impact: None,
+            faction: default_faction(),
             has_gravity: true,
         }
     }
@@ -352,6 +633,7 @@ pub fn load_enemies_config_optional<P: AsRef<Path>>(path: P) -> EnemiesConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tropical_fox_combat::{StatusEffectKind, StatusStacking};
 
     #[test]
     fn test_enemy_stats_config_default() {
@@ -403,4 +685,92 @@ mod tests {
 
         assert!(deserialized.get("slime").is_some());
     }
+
+    #[test]
+    fn test_resistances_parse() {
+        let config: EnemyTypeConfig = ron::from_str(
+            r#"(id: "piranha", name: "Piranha", resistances: { Fire: 0.0, Electric: 2.0 })"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.resistances.get(&DamageType::Fire), Some(&0.0));
+        assert_eq!(config.resistances.get(&DamageType::Electric), Some(&2.0));
+        assert!(EnemyTypeConfig::default().resistances.is_empty());
+    }
+
+    #[test]
+    fn test_status_rules_parse() {
+        let config: EnemyTypeConfig = ron::from_str(
+            r#"(id: "bear", name: "Bear", status_rules: (immunities: [Stun], stacking: { Burn: Extend(max_secs: 6.0) }))"#,
+        )
+        .unwrap();
+
+        assert!(config.status_rules.is_immune(StatusEffectKind::Stun));
+        assert_eq!(
+            config.status_rules.stacking(StatusEffectKind::Burn),
+            StatusStacking::Extend { max_secs: 6.0 }
+        );
+    }
+
+    #[test]
+    fn test_hit_reaction_parse() {
+        let config: EnemyTypeConfig = ron::from_str(
+            r#"(id: "bear", name: "Bear", hit_reaction: (knockback_resistance: 0.7, super_armor: true))"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.hit_reaction.knockback_resistance, 0.7);
+        assert!(config.hit_reaction.super_armor);
+        // Unset fields use the defaults
+        assert_eq!(
+            config.hit_reaction.hitstun_secs,
+            HitReaction::default().hitstun_secs
+        );
+        assert_eq!(config.faction, Faction::Enemy);
+    }
+
+    #[test]
+    fn test_projectile_pattern_parse() {
+        let config: ProjectileConfig = ron::from_str(
+            r#"(enabled: true, motion: Arc(gravity: 300.0), bounces: 2, emitter: (count: 3, spread_degrees: 30.0, burst: 2))"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.motion, ProjectileMotion::Arc { gravity: 300.0 });
+        assert_eq!(config.emitter.count, 3);
+        assert_eq!(config.emitter.burst, 2);
+
+        let projectile = config.projectile(None);
+        assert_eq!(projectile.bounces, 2);
+        assert_eq!(projectile.speed, 200.0);
+        assert!(projectile.wall_collision);
+    }
+
+    #[test]
+    fn test_edge_behavior_parse() {
+        let config: AIConfig =
+            ron::from_str(r#"(behavior: Chase, edge_behavior: JumpDown, jump_force: 320.0)"#)
+                .unwrap();
+
+        assert_eq!(config.edge_behavior, EdgeBehavior::JumpDown);
+        assert_eq!(config.jump_force, 320.0);
+        assert_eq!(config.max_drop, 96.0);
+
+        let config: AIConfig = ron::from_str(r#"(behavior: Patrol)"#).unwrap();
+        assert_eq!(config.edge_behavior, EdgeBehavior::Turn);
+        assert_eq!(config.jump_force, 0.0);
+    }
+
+    #[test]
+    fn test_perception_parse() {
+        let config: AIConfig =
+            ron::from_str(r#"(behavior: Chase, view_angle: 360.0, line_of_sight: false)"#).unwrap();
+        assert_eq!(config.view_angle, 360.0);
+        assert!(!config.line_of_sight);
+        assert_eq!(config.lose_aggro_time, 2.0);
+
+        let config = AIConfig::default();
+        assert_eq!(config.view_angle, 120.0);
+        assert!(config.line_of_sight);
+    }
 }
@@ -2,11 +2,23 @@
 //!
 //! This crate handles enemy AI, behaviors, and boss mechanics.
 
+pub mod boss;
 pub mod components;
 pub mod config;
+pub mod melee;
+pub mod navigation;
+pub mod perception;
 pub mod plugin;
+pub mod probes;
+pub mod state_machine;
 
 // Re-export commonly used types
+pub use boss::*;
 pub use components::*;
 pub use config::*;
-pub use plugin::EnemyPlugin;
+pub use melee::*;
+pub use navigation::*;
+pub use perception::*;
+pub use plugin::{EnemyPlugin, Score};
+pub use probes::*;
+pub use state_machine::*;
@@ -0,0 +1,331 @@
+//! Enemy melee attacks
+//!
+//! Enemies with `melee` in `enemies.ron` attack players in range in three
+//! stages: a wind-up telegraphed by a flash and an animation, a short active
+//! part where an `EnemyAttack` hitbox hits in front of them, and a recovery in
+//! which they stand still and can be punished. Hitstun and stun cancel the
+//! attack.
+
+use bevy::prelude::*;
+use tropical_fox_combat::{
+    Attack, DamageFlash, Faction, FxAssets, Health, HitImpact, Hitstun, Stunned, spawn_fx,
+};
+use tropical_fox_common::{Collider, Player, Velocity};
+
+use crate::components::{AttackStage, Enemy};
+use crate::config::MeleeAttackConfig;
+use crate::plugin::nearest_living_player;
+
+/// Melee attack state of an enemy
+#[derive(Component, Debug, Clone)]
+pub struct MeleeAttacker {
+    /// Attack timings, hitbox and damage
+    pub config: MeleeAttackConfig,
+    /// Hit stop and screen shake on hit (None uses the combat config)
+    pub impact: Option<HitImpact>,
+    /// Stage of the attack in progress
+    pub stage: Option<AttackStage>,
+    /// Time left in the current stage (seconds)
+    pub stage_timer: f32,
+    /// Time until the next attack can start (seconds)
+    pub cooldown_timer: f32,
+    /// Direction the attack was started in
+    pub facing_right: bool,
+    /// Hitbox entity while the attack is active
+    pub hitbox: Option<Entity>,
+}
+
+impl MeleeAttacker {
+    /// Create an idle melee attacker
+    pub fn new(config: MeleeAttackConfig, impact: Option<HitImpact>) -> Self {
+        Self {
+            config,
+            impact,
+            stage: None,
+            stage_timer: 0.0,
+            cooldown_timer: 0.0,
+            facing_right: false,
+            hitbox: None,
+        }
+    }
+
+    /// Whether an attack is in progress
+    pub fn is_attacking(&self) -> bool {
+        self.stage.is_some()
+    }
+
+    /// Whether a new attack can start
+    pub fn can_start(&self) -> bool {
+        self.stage.is_none() && self.cooldown_timer <= 0.0
+    }
+
+    /// Start the wind-up of an attack
+    pub fn start(&mut self, facing_right: bool) {
+        self.facing_right = facing_right;
+        self.stage = Some(AttackStage::Windup);
+        self.stage_timer = self.config.windup_secs;
+    }
+
+    /// Advance timers, returning the stage entered this update
+    ///
+    /// The cooldown starts once the recovery ends.
+    pub fn advance(&mut self, delta: f32) -> Option<AttackStage> {
+        let Some(stage) = self.stage else {
+            self.cooldown_timer = (self.cooldown_timer - delta).max(0.0);
+            return None;
+        };
+
+        self.stage_timer -= delta;
+        if self.stage_timer > 0.0 {
+            return None;
+        }
+
+        self.stage = match stage {
+            AttackStage::Windup => Some(AttackStage::Active),
+            AttackStage::Active => Some(AttackStage::Recovery),
+            AttackStage::Recovery => None,
+        };
+        match self.stage {
+            Some(next) => {
+                self.stage_timer = match next {
+                    AttackStage::Windup => self.config.windup_secs,
+                    AttackStage::Active => self.config.active_secs,
+                    AttackStage::Recovery => self.config.recovery_secs,
+                };
+            }
+            None => self.finish(),
+        }
+        self.stage
+    }
+
+    /// Abort the attack in progress
+    pub fn cancel(&mut self) {
+        if self.is_attacking() {
+            self.stage = None;
+            self.finish();
+        }
+    }
+
+    fn finish(&mut self) {
+        self.stage_timer = 0.0;
+        self.cooldown_timer = self.config.cooldown_secs;
+        self.hitbox = None;
+    }
+
+    /// Animation for the current stage
+    pub fn animation(&self) -> Option<&str> {
+        match self.stage? {
+            AttackStage::Windup => Some(&self.config.windup_animation),
+            AttackStage::Active | AttackStage::Recovery => Some(&self.config.attack_animation),
+        }
+    }
+
+    /// Hitbox offset from the enemy center
+    pub fn hitbox_offset(&self) -> Vec2 {
+        let (x, y) = self.config.hitbox_offset;
+        let dir = if self.facing_right { 1.0 } else { -1.0 };
+        Vec2::new(x * dir, y)
+    }
+}
+
+/// Active melee hitbox of an enemy
+///
+/// Carries a combat `Attack` owned by the enemy (so it never hits its owner,
+/// even with friendly fire) and the owner's `Faction`; despawned when the
+/// owner's active stage ends.
+#[derive(Component, Debug)]
+pub struct EnemyAttack {
+    /// Enemy performing the attack
+    pub owner: Entity,
+}
+
+/// Start melee attacks on players in range and run their stages
+#[allow(clippy::type_complexity)]
+pub fn melee_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    fx_assets: Res<FxAssets>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    mut hitbox_query: Query<&mut Transform, (With<EnemyAttack>, Without<Enemy>, Without<Player>)>,
+    mut enemy_query: Query<(
+        Entity,
+        &Transform,
+        &Faction,
+        &mut Enemy,
+        &mut MeleeAttacker,
+        &mut Velocity,
+        Option<&Sprite>,
+        Option<&DamageFlash>,
+        Has<Hitstun>,
+        Has<Stunned>,
+    )>,
+) {
+    for (
+        entity,
+        transform,
+        faction,
+        mut enemy,
+        mut melee,
+        mut velocity,
+        sprite,
+        flash,
+        hitstun,
+        stunned,
+    ) in enemy_query.iter_mut()
+    {
+        // Getting hit out of the attack cancels it
+        if hitstun || stunned {
+            melee.cancel();
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        if melee.can_start()
+            && let Some(player) = nearest_living_player(position, &player_query)
+            && player.distance(position) <= melee.config.range
+        {
+            let facing_right = if player.x == position.x {
+                enemy.facing_right
+            } else {
+                player.x > position.x
+            };
+            melee.start(facing_right);
+
+            // Telegraph: flash towards the warning color through the wind-up
+            let (r, g, b) = melee.config.telegraph_color;
+            let original_color = flash.map_or_else(
+                || sprite.map_or(Color::WHITE, |sprite| sprite.color),
+                |flash| flash.original_color,
+            );
+            commands.entity(entity).insert(
+                DamageFlash {
+                    original_color,
+                    ..DamageFlash::new(melee.config.windup_secs)
+                }
+                .with_color(Color::srgb(r, g, b)),
+            );
+        }
+
+        let started = melee.advance(time.delta_secs());
+
+        // Rooted and facing the target for the whole attack
+        if melee.is_attacking() {
+            velocity.x = 0.0;
+            enemy.facing_right = melee.facing_right;
+        }
+
+        if started == Some(AttackStage::Active) {
+            let config = &melee.config;
+            let dir = if melee.facing_right { 1.0 } else { -1.0 };
+            let hitbox_position = (position + melee.hitbox_offset()).extend(0.0);
+            let knockback = Vec2::new(config.knockback.0 * dir, config.knockback.1);
+            let size = Vec2::new(config.hitbox_size.0, config.hitbox_size.1);
+
+            let hitbox = commands
+                .spawn((
+                    Transform::from_translation(hitbox_position),
+                    Visibility::default(),
+                    Attack::new(config.damage, knockback, melee.impact)
+                        .with_damage_type(config.damage_type)
+                        .with_status(config.status)
+                        .with_owner(entity),
+                    Collider::new(size),
+                    *faction,
+                    EnemyAttack { owner: entity },
+                    Name::new(format!("EnemyAttack_{}", enemy.enemy_type)),
+                ))
+                .id();
+
+            if let Some(effect) = &config.effect {
+                spawn_fx(
+                    &mut commands,
+                    &fx_assets,
+                    effect,
+                    hitbox_position,
+                    !melee.facing_right,
+                );
+            }
+            melee.hitbox = Some(hitbox);
+        }
+
+        // The hitbox follows the enemy (knocked-back attackers carry it along)
+        if let Some(hitbox) = melee.hitbox
+            && let Ok(mut hitbox_transform) = hitbox_query.get_mut(hitbox)
+        {
+            hitbox_transform.translation = (position + melee.hitbox_offset()).extend(0.0);
+        }
+    }
+}
+
+/// Despawn enemy hitboxes whose owner's active stage has ended
+///
+/// Covers recoveries, cancelled attacks and owners that died mid-attack.
+pub fn enemy_attack_lifetime_system(
+    mut commands: Commands,
+    hitbox_query: Query<(Entity, &EnemyAttack)>,
+    owner_query: Query<&MeleeAttacker>,
+) {
+    for (entity, attack) in hitbox_query.iter() {
+        let active = owner_query.get(attack.owner).is_ok_and(|melee| {
+            melee.stage == Some(AttackStage::Active) && melee.hitbox == Some(entity)
+        });
+
+        if !active {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attacker() -> MeleeAttacker {
+        MeleeAttacker::new(
+            MeleeAttackConfig {
+                windup_secs: 0.5,
+                active_secs: 0.2,
+                recovery_secs: 0.6,
+                cooldown_secs: 1.0,
+                ..default()
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn test_melee_stages() {
+        let mut melee = attacker();
+        assert!(melee.can_start());
+        assert_eq!(melee.animation(), None);
+
+        melee.start(true);
+        assert_eq!(melee.animation(), Some("windup"));
+        assert_eq!(melee.hitbox_offset(), Vec2::new(30.0, 0.0));
+        assert_eq!(melee.advance(0.3), None);
+        assert_eq!(melee.advance(0.3), Some(AttackStage::Active));
+        assert_eq!(melee.animation(), Some("attack"));
+        assert_eq!(melee.advance(0.2), Some(AttackStage::Recovery));
+        assert_eq!(melee.advance(0.6), None);
+        assert!(!melee.is_attacking());
+
+        // Cooldown after the recovery
+        assert!(!melee.can_start());
+        melee.advance(1.0);
+        assert!(melee.can_start());
+    }
+
+    #[test]
+    fn test_melee_cancel() {
+        let mut melee = attacker();
+        melee.start(false);
+        assert_eq!(melee.hitbox_offset(), Vec2::new(-30.0, 0.0));
+        melee.advance(0.5);
+        melee.hitbox = Some(Entity::PLACEHOLDER);
+
+        melee.cancel();
+        assert!(!melee.is_attacking());
+        assert_eq!(melee.hitbox, None);
+        assert!(!melee.can_start());
+    }
+}
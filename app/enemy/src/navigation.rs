@@ -0,0 +1,529 @@
+//! Navigation graph for walking enemies
+//!
+//! The graph is sampled from the level geometry: nodes sit on the exposed
+//! tops of `Ground` and `Wall` colliders, walk links join neighbouring nodes
+//! on one surface, and jump and drop links join different platforms. Links
+//! record how high and how far they go, so a single graph serves every enemy
+//! and `NavGraph::find_path` only follows the links a given enemy can manage.
+//!
+//! Jumps are only blocked by walls: like the built-in physics, enemies jump up
+//! through `Ground` platforms and land on top of them.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+use tropical_fox_common::{Collider, Ground, Wall, segment_rect_intersection};
+
+use crate::config::EdgeBehavior;
+use crate::probes::{GroundMovement, JumpReach};
+
+/// Distance between nodes along a surface (pixels)
+pub const NAV_NODE_SPACING: f32 = 32.0;
+
+/// Free space above a surface for it to be walkable (pixels)
+pub const NAV_CLEARANCE: f32 = 24.0;
+
+/// Longest horizontal distance of a jump or drop link (pixels)
+pub const NAV_MAX_LINK_DISTANCE: f32 = 320.0;
+
+/// Largest height difference of a jump or drop link (pixels)
+pub const NAV_MAX_LINK_HEIGHT: f32 = 320.0;
+
+/// Horizontal distance at which a waypoint counts as reached (pixels)
+pub const NAV_WAYPOINT_RADIUS: f32 = 8.0;
+
+/// Vertical distance at which a waypoint counts as reached (pixels)
+pub const NAV_WAYPOINT_HEIGHT: f32 = 4.0;
+
+/// Extra cost of a jump over walking the same distance
+const JUMP_COST: f32 = 32.0;
+
+/// Extra cost of a drop over walking the same distance
+const DROP_COST: f32 = 16.0;
+
+/// How a link is traversed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NavLinkKind {
+    /// Walk along the surface
+    Walk,
+    /// Jump up a step or across a gap
+    Jump {
+        /// Height gained (0 for jumps that land lower)
+        height: f32,
+        /// Horizontal distance covered
+        distance: f32,
+    },
+    /// Walk off a ledge and fall
+    Drop {
+        /// Height of the fall
+        height: f32,
+    },
+}
+
+/// Directed edge between two nodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavLink {
+    /// Index of the destination node
+    pub to: usize,
+    /// How the link is traversed
+    pub kind: NavLinkKind,
+    /// Path cost (distance plus a penalty for jumps and drops)
+    pub cost: f32,
+}
+
+/// A standing spot on top of a surface
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavNode {
+    /// Point on the surface (feet position)
+    pub position: Vec2,
+    /// Connected walkable surface this node belongs to
+    pub platform: usize,
+    /// Outgoing links
+    pub links: Vec<NavLink>,
+}
+
+/// What an enemy can traverse
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NavAbility {
+    /// Jump height and distance
+    pub jump: JumpReach,
+    /// Deepest drop the enemy walks off (0 = never drops)
+    pub max_drop: f32,
+}
+
+impl NavAbility {
+    /// Ability of an enemy with the given ground movement and jump reach
+    ///
+    /// Only `EdgeBehavior::JumpDown` enemies take drop links.
+    pub fn new(movement: &GroundMovement, jump: JumpReach) -> Self {
+        Self {
+            jump,
+            max_drop: if movement.edge_behavior == EdgeBehavior::JumpDown {
+                movement.max_drop
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Whether a link of this kind can be traversed
+    pub fn allows(&self, kind: NavLinkKind) -> bool {
+        match kind {
+            NavLinkKind::Walk => true,
+            NavLinkKind::Jump { height, distance } => {
+                self.jump.height >= height && self.jump.distance >= distance
+            }
+            NavLinkKind::Drop { height } => self.max_drop >= height,
+        }
+    }
+}
+
+/// One step of a path: where to go and how to get there
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavWaypoint {
+    /// Point on the surface to reach (feet position)
+    pub position: Vec2,
+    /// How to get there from the previous waypoint
+    pub link: NavLinkKind,
+}
+
+impl NavWaypoint {
+    /// Whether feet at `feet` stand on this waypoint
+    pub fn reached(&self, feet: Vec2) -> bool {
+        (self.position.x - feet.x).abs() <= NAV_WAYPOINT_RADIUS
+            && (self.position.y - feet.y).abs() <= NAV_WAYPOINT_HEIGHT
+    }
+}
+
+/// Navigation graph over the level geometry
+///
+/// Rebuilt by `rebuild_nav_graph` whenever `Ground` or `Wall` colliders are
+/// added, moved, resized or removed.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct NavGraph {
+    nodes: Vec<NavNode>,
+}
+
+/// Whether a point lies inside any solid
+fn inside_any(point: Vec2, solids: &[Rect]) -> bool {
+    solids.iter().any(|solid| solid.contains(point))
+}
+
+/// Whether a segment crosses any solid
+fn blocked(from: Vec2, to: Vec2, solids: &[Rect]) -> bool {
+    solids
+        .iter()
+        .any(|solid| segment_rect_intersection(from, to, *solid).is_some())
+}
+
+/// Whether there is ground directly under every point from `from_x` to `to_x` at height `y`
+fn supported(from_x: f32, to_x: f32, y: f32, solids: &[Rect]) -> bool {
+    let steps = ((to_x - from_x).abs() / 4.0).ceil().max(1.0) as usize;
+    (0..=steps).all(|step| {
+        let x = from_x + (to_x - from_x) * step as f32 / steps as f32;
+        inside_any(Vec2::new(x, y - 0.5), solids)
+    })
+}
+
+/// Open set entry for A*, ordered so the cheapest estimate pops first
+struct Open {
+    estimate: f32,
+    node: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl NavGraph {
+    /// Build the graph from level geometry
+    ///
+    /// `solids` are all `Ground` and `Wall` bounds; `walls` are the `Wall`
+    /// bounds, the only geometry that blocks jumps.
+    pub fn build(solids: &[Rect], walls: &[Rect]) -> Self {
+        // Nodes along every exposed surface with room to stand
+        let mut nodes = Vec::new();
+        for solid in solids {
+            let count = (solid.width() / NAV_NODE_SPACING).floor().max(1.0) as usize;
+            for i in 0..count {
+                let x = solid.min.x + solid.width() * (i as f32 + 0.5) / count as f32;
+                let position = Vec2::new(x, solid.max.y);
+                let covered = inside_any(position + Vec2::Y, solids)
+                    || inside_any(position + Vec2::Y * NAV_CLEARANCE, solids);
+                if !covered {
+                    nodes.push(NavNode {
+                        position,
+                        platform: 0,
+                        links: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        // Walk links between neighbours at the same height with ground between them
+        let walk_height = Vec2::Y * (NAV_CLEARANCE / 2.0);
+        for a in 0..nodes.len() {
+            for b in 0..nodes.len() {
+                let (from, to) = (nodes[a].position, nodes[b].position);
+                let neighbours = a != b
+                    && (from.y - to.y).abs() < 0.5
+                    && (from.x - to.x).abs() <= NAV_NODE_SPACING * 1.5;
+                if neighbours
+                    && supported(from.x, to.x, from.y, solids)
+                    && !blocked(from + walk_height, to + walk_height, solids)
+                {
+                    nodes[a].links.push(NavLink {
+                        to: b,
+                        kind: NavLinkKind::Walk,
+                        cost: from.distance(to),
+                    });
+                }
+            }
+        }
+
+        // Platforms: nodes connected by walk links
+        let mut platform = vec![usize::MAX; nodes.len()];
+        let mut platforms = 0;
+        for start in 0..nodes.len() {
+            if platform[start] != usize::MAX {
+                continue;
+            }
+            let mut stack = vec![start];
+            platform[start] = platforms;
+            while let Some(node) = stack.pop() {
+                for link in &nodes[node].links {
+                    if platform[link.to] == usize::MAX {
+                        platform[link.to] = platforms;
+                        stack.push(link.to);
+                    }
+                }
+            }
+            platforms += 1;
+        }
+        for (node, platform) in nodes.iter_mut().zip(platform) {
+            node.platform = platform;
+        }
+
+        // Jump and drop links between platforms
+        for a in 0..nodes.len() {
+            let from = nodes[a].position;
+            for b in 0..nodes.len() {
+                let to = nodes[b].position;
+                let rise = to.y - from.y;
+                let distance = (to.x - from.x).abs();
+                if nodes[a].platform == nodes[b].platform
+                    || distance > NAV_MAX_LINK_DISTANCE
+                    || rise.abs() > NAV_MAX_LINK_HEIGHT
+                {
+                    continue;
+                }
+
+                // Landing lower: nothing between the takeoff height and the landing spot
+                let column_clear = rise > 0.0
+                    || !blocked(
+                        Vec2::new(to.x, from.y - 0.5),
+                        Vec2::new(to.x, to.y + 0.5),
+                        solids,
+                    );
+                if !column_clear {
+                    continue;
+                }
+
+                let direction = (to.x - from.x).signum();
+                let ledge = !inside_any(
+                    Vec2::new(from.x + direction * NAV_NODE_SPACING, from.y - 0.5),
+                    solids,
+                );
+                let kind = if rise < 0.0
+                    && ledge
+                    && distance <= NAV_NODE_SPACING * 1.5
+                    && !blocked(from + Vec2::Y, Vec2::new(to.x, from.y + 1.0), solids)
+                {
+                    NavLinkKind::Drop { height: -rise }
+                } else if !blocked(from + Vec2::Y, to + Vec2::Y, walls) {
+                    NavLinkKind::Jump {
+                        height: rise.max(0.0),
+                        distance,
+                    }
+                } else {
+                    continue;
+                };
+
+                let penalty = match kind {
+                    NavLinkKind::Drop { .. } => DROP_COST,
+                    _ => JUMP_COST,
+                };
+                nodes[a].links.push(NavLink {
+                    to: b,
+                    kind,
+                    cost: from.distance(to) + penalty,
+                });
+            }
+        }
+
+        Self { nodes }
+    }
+
+    /// All nodes in the graph
+    pub fn nodes(&self) -> &[NavNode] {
+        &self.nodes
+    }
+
+    /// Whether the graph has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Node of the surface under `point`, preferring the closest surface below it
+    pub fn nearest_node(&self, point: Vec2) -> Option<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.position.y <= point.y + NAV_WAYPOINT_HEIGHT)
+            .map(|(index, node)| {
+                let offset = point - node.position;
+                (index, offset.x.abs() + offset.y.abs() * 2.0)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Platform under `point`
+    pub fn platform_at(&self, point: Vec2) -> Option<usize> {
+        self.nearest_node(point)
+            .map(|node| self.nodes[node].platform)
+    }
+
+    /// Shortest path (A*) from the surface under `from` to the surface under `to`
+    ///
+    /// The first waypoint is the node nearest `from`; only links `ability`
+    /// allows are followed. Returns `None` when the target cannot be reached.
+    pub fn find_path(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        ability: &NavAbility,
+    ) -> Option<Vec<NavWaypoint>> {
+        let start = self.nearest_node(from)?;
+        let goal = self.nearest_node(to)?;
+        let goal_position = self.nodes[goal].position;
+
+        let mut cost = vec![f32::INFINITY; self.nodes.len()];
+        let mut came_from: Vec<Option<(usize, NavLinkKind)>> = vec![None; self.nodes.len()];
+        let mut open = BinaryHeap::new();
+        cost[start] = 0.0;
+        open.push(Open {
+            estimate: self.nodes[start].position.distance(goal_position),
+            node: start,
+        });
+
+        while let Some(Open { node, .. }) = open.pop() {
+            if node == goal {
+                let mut path = vec![NavWaypoint {
+                    position: self.nodes[goal].position,
+                    link: NavLinkKind::Walk,
+                }];
+                let mut current = goal;
+                while let Some((previous, link)) = came_from[current] {
+                    path.last_mut().unwrap().link = link;
+                    path.push(NavWaypoint {
+                        position: self.nodes[previous].position,
+                        link: NavLinkKind::Walk,
+                    });
+                    current = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for link in &self.nodes[node].links {
+                if !ability.allows(link.kind) {
+                    continue;
+                }
+                let next_cost = cost[node] + link.cost;
+                if next_cost < cost[link.to] {
+                    cost[link.to] = next_cost;
+                    came_from[link.to] = Some((node, link.kind));
+                    open.push(Open {
+                        estimate: next_cost + self.nodes[link.to].position.distance(goal_position),
+                        node: link.to,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Rebuild the navigation graph when the level geometry changes
+#[allow(clippy::type_complexity)]
+pub fn rebuild_nav_graph(
+    mut graph: ResMut<NavGraph>,
+    changed_query: Query<
+        (),
+        (
+            Or<(With<Ground>, With<Wall>)>,
+            Or<(Changed<Transform>, Changed<Collider>)>,
+        ),
+    >,
+    mut removed_ground: RemovedComponents<Ground>,
+    mut removed_walls: RemovedComponents<Wall>,
+    solid_query: Query<(&Transform, &Collider, Has<Wall>), Or<(With<Ground>, With<Wall>)>>,
+) {
+    let removed = removed_ground.read().count() + removed_walls.read().count() > 0;
+    if changed_query.is_empty() && !removed {
+        return;
+    }
+
+    let mut solids = Vec::new();
+    let mut walls = Vec::new();
+    for (transform, collider, is_wall) in solid_query.iter() {
+        let bounds = collider.aabb(transform.translation.truncate());
+        solids.push(bounds);
+        if is_wall {
+            walls.push(bounds);
+        }
+    }
+
+    *graph = NavGraph::build(&solids, &walls);
+    debug!(
+        "Rebuilt navigation graph: {} nodes from {} solids",
+        graph.nodes().len(),
+        solids.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Floor with its top at y=0, a platform 50px above it and a wall on the right
+    fn level() -> NavGraph {
+        let floor = Rect::new(-200.0, -32.0, 200.0, 0.0);
+        let platform = Rect::new(-40.0, 40.0, 40.0, 50.0);
+        let wall = Rect::new(150.0, 0.0, 170.0, 200.0);
+        NavGraph::build(&[floor, platform, wall], &[wall])
+    }
+
+    fn jumper(height: f32) -> NavAbility {
+        NavAbility {
+            jump: JumpReach {
+                height,
+                distance: 100.0,
+            },
+            max_drop: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_platforms() {
+        let graph = level();
+        let floor = graph.platform_at(Vec2::new(-100.0, 0.0)).unwrap();
+
+        assert_eq!(graph.platform_at(Vec2::new(100.0, 10.0)), Some(floor));
+        assert_ne!(graph.platform_at(Vec2::new(0.0, 50.0)), Some(floor));
+        // The wall cuts off the floor behind it
+        assert_ne!(graph.platform_at(Vec2::new(185.0, 0.0)), Some(floor));
+        // Under the platform is still the floor
+        assert_eq!(graph.platform_at(Vec2::new(0.0, 20.0)), Some(floor));
+    }
+
+    #[test]
+    fn test_find_path_jumps_up() {
+        let graph = level();
+        let from = Vec2::new(-150.0, 0.0);
+        let to = Vec2::new(0.0, 50.0);
+
+        let path = graph.find_path(from, to, &jumper(60.0)).unwrap();
+        assert!(path.first().unwrap().position.y == 0.0);
+        assert!(path.last().unwrap().position.y == 50.0);
+        assert!(
+            path.iter()
+                .any(|waypoint| matches!(waypoint.link, NavLinkKind::Jump { .. }))
+        );
+
+        // 50px is too high for a 30px jump
+        assert!(graph.find_path(from, to, &jumper(30.0)).is_none());
+        // The wall is too high for either
+        assert!(
+            graph
+                .find_path(from, Vec2::new(185.0, 0.0), &jumper(60.0))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_find_path_drops_down() {
+        let graph = level();
+        let from = Vec2::new(20.0, 50.0);
+        let to = Vec2::new(-150.0, 0.0);
+        let dropper = NavAbility {
+            jump: JumpReach::default(),
+            max_drop: 96.0,
+        };
+
+        let path = graph.find_path(from, to, &dropper).unwrap();
+        assert!(
+            path.iter()
+                .any(|waypoint| waypoint.link == NavLinkKind::Drop { height: 50.0 })
+        );
+        assert!(graph.find_path(from, to, &NavAbility::default()).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+//! Enemy perception
+//!
+//! Chasers spot players inside a view cone in front of them, and only when a
+//! ray from the enemy to the player is not blocked by `Ground` or `Wall`
+//! colliders.
+
+use bevy::prelude::*;
+use tropical_fox_combat::Health;
+use tropical_fox_common::{
+    Collider, Ground, Player, SpatialIndex, Wall, segment_rect_intersection,
+};
+
+/// Level geometry (`Ground` and `Wall`) for sight checks
+pub type SolidQuery<'w, 's> =
+    Query<'w, 's, (&'static Transform, &'static Collider), Or<(With<Ground>, With<Wall>)>>;
+
+/// Whether `target` is inside a view cone facing left or right
+///
+/// `view_angle` is the full cone width in degrees; 360 or more sees all around.
+pub fn in_view_cone(facing_right: bool, view_angle: f32, from: Vec2, target: Vec2) -> bool {
+    if view_angle >= 360.0 {
+        return true;
+    }
+    let to_target = target - from;
+    if to_target.length_squared() < f32::EPSILON {
+        return true;
+    }
+    let facing = if facing_right { Vec2::X } else { Vec2::NEG_X };
+    facing.angle_to(to_target).abs().to_degrees() <= view_angle / 2.0
+}
+
+/// Whether a straight line from `from` to `to` misses every solid
+pub fn line_of_sight(from: Vec2, to: Vec2, solids: &[Rect]) -> bool {
+    solids
+        .iter()
+        .all(|solid| segment_rect_intersection(from, to, *solid).is_none())
+}
+
+/// Bounds of the level geometry near the segment `from` -> `to`
+pub fn solids_along(
+    from: Vec2,
+    to: Vec2,
+    index: &SpatialIndex,
+    solid_query: &SolidQuery,
+) -> Vec<Rect> {
+    index
+        .solids
+        .query(Rect::from_corners(from, to))
+        .into_iter()
+        .filter_map(|solid| solid_query.get(solid).ok())
+        .map(|(transform, collider)| collider.aabb(transform.translation.truncate()))
+        .collect()
+}
+
+/// What an enemy can see of the players
+pub struct Sight {
+    /// Enemy position (eye)
+    pub position: Vec2,
+    /// Whether the enemy faces right
+    pub facing_right: bool,
+    /// Detection range (pixels)
+    pub range: f32,
+    /// Full view cone width (degrees)
+    pub view_angle: f32,
+    /// Whether walls and ground block sight
+    pub line_of_sight: bool,
+}
+
+impl Sight {
+    /// Position of the closest living player this enemy can see
+    pub fn visible_player(
+        &self,
+        player_query: &Query<(&Transform, &Health), With<Player>>,
+        index: &SpatialIndex,
+        solid_query: &SolidQuery,
+    ) -> Option<Vec2> {
+        let mut candidates: Vec<Vec2> = player_query
+            .iter()
+            .filter(|(_, health)| health.is_alive())
+            .map(|(transform, _)| transform.translation.truncate())
+            .filter(|position| position.distance(self.position) < self.range)
+            .filter(|position| {
+                in_view_cone(self.facing_right, self.view_angle, self.position, *position)
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            a.distance_squared(self.position)
+                .total_cmp(&b.distance_squared(self.position))
+        });
+
+        candidates.into_iter().find(|position| {
+            !self.line_of_sight
+                || line_of_sight(
+                    self.position,
+                    *position,
+                    &solids_along(self.position, *position, index, solid_query),
+                )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_cone() {
+        let eye = Vec2::ZERO;
+
+        // 120 degree cone facing right
+        assert!(in_view_cone(true, 120.0, eye, Vec2::new(100.0, 0.0)));
+        assert!(in_view_cone(true, 120.0, eye, Vec2::new(100.0, 150.0)));
+        assert!(!in_view_cone(true, 120.0, eye, Vec2::new(100.0, 200.0)));
+        assert!(!in_view_cone(true, 120.0, eye, Vec2::new(-100.0, 0.0)));
+        // Facing left flips the cone
+        assert!(in_view_cone(false, 120.0, eye, Vec2::new(-100.0, 0.0)));
+        // Full circle sees behind
+        assert!(in_view_cone(true, 360.0, eye, Vec2::new(-100.0, 0.0)));
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let wall = Rect::new(40.0, -100.0, 60.0, 100.0);
+        let floor = Rect::new(-200.0, -40.0, 200.0, -20.0);
+        let solids = [wall, floor];
+
+        assert!(line_of_sight(Vec2::ZERO, Vec2::new(30.0, 0.0), &solids));
+        assert!(!line_of_sight(Vec2::ZERO, Vec2::new(100.0, 0.0), &solids));
+        // Over the floor, not through it
+        assert!(line_of_sight(Vec2::ZERO, Vec2::new(-150.0, 0.0), &solids));
+        assert!(!line_of_sight(
+            Vec2::ZERO,
+            Vec2::new(-150.0, -80.0),
+            &solids
+        ));
+    }
+}
@@ -4,18 +4,35 @@
 
 use bevy::prelude::*;
 use tropical_fox_animation::{AnimationClip, AnimationController, AnimationState};
-use tropical_fox_combat::{DamageEvent, DeathEvent, EnemyHealth, Health, Invincibility};
+use tropical_fox_combat::{
+    DamageEvent, DamageResistances, DamageType, DeathEvent, EnemyHealth, Faction, FactionsConfig,
+    FxAssets, Health, Hitstun, Hurtbox, Invincibility, ProjectileBurst, ProjectileVolley,
+    StatusEffects, Stunned, fire_projectiles,
+};
 use tropical_fox_common::{
-    CharacterAssets, CharacterTextureAtlas, Collider, GameState, Gravity, Ground, Player, Velocity,
+    CharacterAssets, CharacterTextureAtlas, Collider, GameState, Gravity, Ground, InGameState,
+    PhysicsConfig, Player, SpatialIndex, Velocity, custom_physics, update_spatial_index,
 };
 
+use crate::boss::{
+    BossConfig, boss_arena_trigger_system, boss_controller_system, boss_fight_system,
+    end_boss_fight, spawn_boss_arenas, start_boss_fight,
+};
 use crate::components::{
-    ChaseAI, ContactDamage, Enemy, EnemyAI, EnemyProjectile, EnemyStats, FlyingAI, FlyingPattern,
-    PatrolAI, ProjectileShooter,
+    ChaseAI, ContactDamage, Enemy, EnemyAI, EnemyStats, FlyingAI, FlyingPattern, PatrolAI,
+    ProjectileShooter,
 };
 use crate::config::{
-    AIBehaviorType, EnemiesConfig, FlyingPatternConfig, load_enemies_config_optional,
+    AINode, EnemiesConfig, FlyingPatternConfig, load_bosses_config_optional,
+    load_enemies_config_optional,
 };
+use crate::melee::{MeleeAttacker, enemy_attack_lifetime_system, melee_attack_system};
+use crate::navigation::{
+    NAV_WAYPOINT_RADIUS, NavAbility, NavGraph, NavLinkKind, NavWaypoint, rebuild_nav_graph,
+};
+use crate::perception::{Sight, SolidQuery};
+use crate::probes::{EdgeAction, GroundMovement, GroundProbe, edge_action, ground_probe_system};
+use crate::state_machine::{AIStateMachine, ai_state_machine_system};
 
 /// Resource holding enemy configuration
 #[derive(Resource, Debug)]
@@ -97,7 +114,6 @@ fn load_enemy_assets(
     }
 
     // Load boss assets
-    use crate::config::load_bosses_config_optional;
     let bosses_config = load_bosses_config_optional("assets/config/bosses.ron");
 
     for (boss_id, boss_def) in &bosses_config.bosses {
@@ -145,6 +161,10 @@ impl Plugin for EnemyPlugin {
         let enemies_config = load_enemies_config_optional("assets/config/enemies.ron");
         app.insert_resource(EnemyConfig(enemies_config));
 
+        // Load boss configuration
+        let bosses_config = load_bosses_config_optional("assets/config/bosses.ron");
+        app.insert_resource(BossConfig(bosses_config));
+
         // Add score resource
         app.insert_resource(Score::default());
 
@@ -155,6 +175,35 @@ impl Plugin for EnemyPlugin {
         // Spawn test enemies when entering InGame state
         app.add_systems(OnEnter(GameState::InGame), spawn_test_enemies);
 
+        // Navigation graph follows the level geometry
+        app.init_resource::<NavGraph>();
+        app.add_systems(
+            Update,
+            rebuild_nav_graph
+                .before(chase_ai_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Ground probes feed the walking AI systems
+        app.add_systems(
+            Update,
+            ground_probe_system
+                .before(patrol_ai_system)
+                .before(chase_ai_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Data-driven state machines pick the movement node before the AI systems run
+        app.add_systems(
+            Update,
+            ai_state_machine_system
+                .after(ground_probe_system)
+                .before(patrol_ai_system)
+                .before(chase_ai_system)
+                .before(flying_ai_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
         // AI systems (run in Update)
         app.add_systems(
             Update,
@@ -167,8 +216,6 @@ impl Plugin for EnemyPlugin {
                 contact_damage_cooldown_system,
                 contact_damage_system,
                 projectile_shooter_system,
-                projectile_movement_system,
-                projectile_collision_system,
                 // Visual updates
                 enemy_animation_controller,
                 enemy_facing_system,
@@ -176,10 +223,44 @@ impl Plugin for EnemyPlugin {
                 .run_if(in_state(GameState::InGame)),
         );
 
-        // Enemy ground collision (run in FixedUpdate with physics)
+        // Melee attacks override the AI's movement while they run
+        app.add_systems(
+            Update,
+            (
+                melee_attack_system
+                    .after(patrol_ai_system)
+                    .after(chase_ai_system)
+                    .after(flying_ai_system)
+                    .before(enemy_facing_system),
+                enemy_attack_lifetime_system.after(melee_attack_system),
+            )
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Boss fights: arena triggers in stage play, the fight in the boss room
+        app.add_systems(OnEnter(GameState::InGame), spawn_boss_arenas);
+        app.add_systems(
+            Update,
+            boss_arena_trigger_system.run_if(in_state(InGameState::StagePlay)),
+        );
+        app.add_systems(OnEnter(InGameState::BossRoom), start_boss_fight);
+        app.add_systems(
+            Update,
+            (
+                boss_controller_system.before(contact_damage_system),
+                boss_fight_system.after(tropical_fox_combat::death_system),
+            )
+                .run_if(in_state(InGameState::BossRoom)),
+        );
+        app.add_systems(OnExit(InGameState::BossRoom), end_boss_fight);
+
+        // Enemy ground collision (run in FixedUpdate after physics rebuilds the spatial index)
         app.add_systems(
             FixedUpdate,
-            enemy_ground_collision.run_if(in_state(GameState::InGame)),
+            enemy_ground_collision
+                .after(update_spatial_index)
+                .run_if(custom_physics)
+                .run_if(in_state(GameState::InGame)),
         );
 
         // Enemy death handling (must run after combat systems)
@@ -264,7 +345,7 @@ pub fn spawn_enemy(
     position: Vec2,
     config: &crate::config::EnemyTypeConfig,
     character_assets: Option<&CharacterAssets>,
-) {
+) -> Entity {
     let collider_size = Vec2::new(config.collider.size.0, config.collider.size.1);
     // Use 2x scale for display
     let display_size = collider_size * 2.0;
@@ -281,21 +362,91 @@ pub fn spawn_enemy(
         },
         Health::new(config.stats.health),
         EnemyHealth,
+        Hurtbox,
+        config.faction,
         Velocity::default(),
         Collider::new(collider_size),
-        ContactDamage::new(config.stats.damage, config.stats.knockback_force, 0.5),
+        ContactDamage::new(config.stats.damage, config.stats.knockback_force, 0.5)
+            .with_impact(config.impact),
         Name::new(format!("Enemy_{}", config.name)),
     ));
 
-    // Try to use character assets for sprite
+    insert_character_sprite(
+        &mut entity,
+        enemy_type,
+        &config.animation_config_path,
+        display_size,
+        character_assets,
+    );
+
+    // Resistances and weaknesses to damage types
+    if !config.resistances.is_empty() {
+        entity.insert(DamageResistances::new(config.resistances.clone()));
+    }
+
+    // Status effect immunities and stacking rules
+    entity.insert(config.status_rules.clone());
+
+    // Knockback resistance, hitstun, i-frames and super-armor
+    entity.insert(config.hit_reaction);
+
+    // Add gravity if applicable; walking enemies probe the ground ahead
+    if config.has_gravity {
+        entity.insert((
+            Gravity::default(),
+            GroundMovement::from_config(&config.ai),
+            GroundProbe::default(),
+        ));
+    }
+
+    // Add AI component: the state machine's initial node, or the behavior type
+    if let Some(machine) = AIStateMachine::new(&config.ai, position) {
+        entity.insert((machine.build_node(), machine));
+    } else {
+        entity.insert(create_ai_node(
+            AINode::from(&config.ai.behavior),
+            &config.ai,
+            position,
+        ));
+    }
+
+    // Add melee attack if configured
+    if let Some(melee) = &config.melee {
+        entity.insert(MeleeAttacker::new(melee.clone(), config.impact));
+    }
+
+    // Add projectile shooter if enabled
+    if config.projectile.enabled {
+        entity.insert(ProjectileShooter::new(
+            config.projectile.projectile(config.impact),
+            config.projectile.emitter,
+            config.projectile.fire_rate,
+            config.projectile.range,
+        ));
+    }
+
+    entity.id()
+}
+
+/// Insert the sprite and animation of an enemy or boss character
+///
+/// Uses the character's texture atlas and animation config when they are
+/// loaded, otherwise a colored placeholder.
+pub(crate) fn insert_character_sprite(
+    entity: &mut EntityCommands,
+    character_id: &str,
+    animation_config_path: &str,
+    display_size: Vec2,
+    character_assets: Option<&CharacterAssets>,
+) {
     if let Some(assets) = character_assets {
-        if let Some(atlas) = assets.get(enemy_type) {
+        if let Some(atlas) = assets.get(character_id) {
             // Try to load animation config from file
             let (animation_controller, animation_state) = {
                 use tropical_fox_animation::load_animation_config_optional;
 
-                if !config.animation_config_path.is_empty() {
-                    let animation_config_path = format!("assets/{}", config.animation_config_path);
+                if !animation_config_path.is_empty() {
+                    let animation_config_path = format!("assets/{}", animation_config_path);
 
                     if let Some(anim_config) =
                         load_animation_config_optional(&animation_config_path)
@@ -304,7 +455,7 @@ pub fn spawn_enemy(
                             Ok(controller) => {
                                 info!(
                                     "Loaded enemy '{}' animations from {} ({} clips)",
-                                    enemy_type,
+                                    character_id,
                                     animation_config_path,
                                     controller.animations.len()
                                 );
@@ -313,7 +464,7 @@ pub fn spawn_enemy(
                             Err(e) => {
                                 warn!(
                                     "Failed to create animation controller for '{}' from {}: {}. Using fallback.",
-                                    enemy_type, animation_config_path, e
+                                    character_id, animation_config_path, e
                                 );
                                 create_fallback_enemy_animation()
                             }
@@ -321,7 +472,7 @@ pub fn spawn_enemy(
                     } else {
                         warn!(
                             "Could not load animation config for '{}' at {}. Using fallback.",
-                            enemy_type, animation_config_path
+                            character_id, animation_config_path
                         );
                         create_fallback_enemy_animation()
                     }
@@ -344,7 +495,7 @@ pub fn spawn_enemy(
         } else {
             // Fallback to colored placeholder
             entity.insert(Sprite {
-                color: get_enemy_color(enemy_type),
+                color: get_enemy_color(character_id),
                 custom_size: Some(display_size),
                 ..default()
             });
@@ -352,30 +503,11 @@ pub fn spawn_enemy(
     } else {
         // Fallback to colored placeholder
         entity.insert(Sprite {
-            color: get_enemy_color(enemy_type),
+            color: get_enemy_color(character_id),
             custom_size: Some(display_size),
             ..default()
         });
     }
-
-    // Add gravity if applicable
-    if config.has_gravity {
-        entity.insert(Gravity::default());
-    }
-
-    // Add AI component based on behavior type
-    let ai = create_ai_component(&config.ai, position);
-    entity.insert(ai);
-
-    // Add projectile shooter if enabled
-    if config.projectile.enabled {
-        entity.insert(ProjectileShooter::new(
-            config.projectile.damage,
-            config.projectile.speed,
-            config.projectile.fire_rate,
-            config.projectile.range,
-        ));
-    }
 }
 
 /// Create fallback animation controller for enemies without config
@@ -390,35 +522,48 @@ fn create_fallback_enemy_animation() -> (AnimationController, AnimationState) {
 }
 
 /// Get a placeholder color for an enemy type
-fn get_enemy_color(enemy_type: &str) -> Color {
+pub(crate) fn get_enemy_color(enemy_type: &str) -> Color {
     match enemy_type {
-        "ant" => Color::srgb(0.8, 0.3, 0.2),         // Reddish-brown
-        "bat" => Color::srgb(0.5, 0.2, 0.5),         // Purple
-        "bear" => Color::srgb(0.6, 0.4, 0.2),        // Brown
-        "piranha" => Color::srgb(0.2, 0.6, 0.3),     // Green-ish
-        "slime" => Color::srgb(0.2, 0.8, 0.3),       // Green
-        "goblin" => Color::srgb(0.6, 0.3, 0.1),      // Brown
-        "ghost" => Color::srgba(0.7, 0.7, 0.9, 0.7), // Translucent blue
-        "skeleton" => Color::srgb(0.9, 0.9, 0.85),   // Off-white
-        "wisp" => Color::srgb(0.9, 0.9, 0.3),        // Yellow
-        _ => Color::srgb(0.8, 0.2, 0.2),             // Default red
+        "ant" => Color::srgb(0.8, 0.3, 0.2),          // Reddish-brown
+        "bat" => Color::srgb(0.5, 0.2, 0.5),          // Purple
+        "bear" => Color::srgb(0.6, 0.4, 0.2),         // Brown
+        "piranha" => Color::srgb(0.2, 0.6, 0.3),      // Green-ish
+        "slime" => Color::srgb(0.2, 0.8, 0.3),        // Green
+        "goblin" => Color::srgb(0.6, 0.3, 0.1),       // Brown
+        "ghost" => Color::srgba(0.7, 0.7, 0.9, 0.7),  // Translucent blue
+        "skeleton" => Color::srgb(0.9, 0.9, 0.85),    // Off-white
+        "wisp" => Color::srgb(0.9, 0.9, 0.3),         // Yellow
+        "sunny_dragon" => Color::srgb(0.9, 0.6, 0.1), // Orange
+        _ => Color::srgb(0.8, 0.2, 0.2),              // Default red
     }
 }
 
-/// Create an AI component from configuration
-fn create_ai_component(ai_config: &crate::config::AIConfig, origin: Vec2) -> EnemyAI {
-    match ai_config.behavior {
-        AIBehaviorType::Idle => EnemyAI::Idle,
-        AIBehaviorType::Patrol => EnemyAI::Patrol(PatrolAI::horizontal(
+/// Create the built-in AI component for a movement node
+pub(crate) fn create_ai_node(
+    node: AINode,
+    ai_config: &crate::config::AIConfig,
+    origin: Vec2,
+) -> EnemyAI {
+    match node {
+        AINode::Idle => EnemyAI::Idle,
+        AINode::Patrol => EnemyAI::Patrol(PatrolAI::horizontal(
             ai_config.patrol_distance,
             ai_config.wait_time,
             origin,
         )),
-        AIBehaviorType::Chase => EnemyAI::Chase(ChaseAI::new(
-            ai_config.detection_range,
-            ai_config.attack_range,
+        AINode::MoveTo(x, y) => EnemyAI::Patrol(PatrolAI::new(
+            vec![Vec2::new(x, y)],
+            ai_config.wait_time,
+            origin,
         )),
-        AIBehaviorType::Flying => {
+        AINode::Chase => EnemyAI::Chase(
+            ChaseAI::new(ai_config.detection_range, ai_config.attack_range).with_perception(
+                ai_config.view_angle,
+                ai_config.line_of_sight,
+                ai_config.lose_aggro_time,
+            ),
+        ),
+        AINode::Flying => {
             let pattern = match &ai_config.flying_pattern {
                 Some(FlyingPatternConfig::SineWave {
                     amplitude,
@@ -453,89 +598,310 @@ fn create_ai_component(ai_config: &crate::config::AIConfig, origin: Vec2) -> Ene
     }
 }
 
+/// Movement speed after slowing status effects
+fn effective_move_speed(stats: &EnemyStats, status: Option<&StatusEffects>) -> f32 {
+    stats.move_speed * status.map_or(1.0, StatusEffects::speed_multiplier)
+}
+
 /// Patrol AI system - moves enemy between waypoints
+///
+/// Walking enemies treat a wall or ledge ahead according to their
+/// `EdgeBehavior`: turning heads for the next waypoint at once, stopping waits
+/// there like at a waypoint, and `JumpDown` walks off drops within reach.
+#[allow(clippy::type_complexity)]
 fn patrol_ai_system(
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut Velocity, &mut EnemyAI, &EnemyStats), With<Enemy>>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut EnemyAI,
+            &EnemyStats,
+            Option<&StatusEffects>,
+            Option<(&GroundMovement, &GroundProbe)>,
+        ),
+        (With<Enemy>, Without<Stunned>, Without<Hitstun>),
+    >,
 ) {
-    for (transform, mut velocity, mut ai, stats) in query.iter_mut() {
+    for (transform, mut velocity, mut ai, stats, status, ground) in query.iter_mut() {
         if let EnemyAI::Patrol(ref mut patrol) = *ai {
             let current_pos = transform.translation.truncate();
             let target_pos = patrol.current_target();
-            let distance = current_pos.distance(target_pos);
+            // Patrol points are horizontal; height changes after dropping down are ignored
+            let offset = target_pos.x - current_pos.x;
 
             const ARRIVAL_THRESHOLD: f32 = 5.0;
 
-            if distance < ARRIVAL_THRESHOLD {
-                // At waypoint - wait then advance
+            // Edges only matter while walking; airborne enemies keep their course
+            let action = ground.filter(|(_, probe)| probe.grounded).map_or(
+                EdgeAction::Continue,
+                |(movement, probe)| {
+                    edge_action(probe.towards(offset), movement.edge_behavior, false)
+                },
+            );
+
+            if offset.abs() < ARRIVAL_THRESHOLD || action == EdgeAction::Stop {
+                // At waypoint (or held at an edge) - wait then advance
                 velocity.x = 0.0;
                 patrol.wait_timer += time.delta_secs();
 
                 if patrol.wait_timer >= patrol.wait_time {
                     patrol.advance();
                 }
+            } else if action == EdgeAction::Turn {
+                velocity.x = 0.0;
+                patrol.advance();
             } else {
                 // Move toward target
-                let direction = (target_pos - current_pos).normalize();
-                velocity.x = direction.x * stats.move_speed;
+                velocity.x = offset.signum() * effective_move_speed(stats, status);
             }
         }
     }
 }
 
-/// Chase AI system - enemy chases player when in detection range
-fn chase_ai_system(
-    time: Res<Time>,
-    player_query: Query<&Transform, With<Player>>,
-    mut enemy_query: Query<(&Transform, &mut Velocity, &mut EnemyAI, &EnemyStats), With<Enemy>>,
+/// Position of the living player closest to `position`
+pub(crate) fn nearest_living_player(
+    position: Vec2,
+    player_query: &Query<(&Transform, &Health), With<Player>>,
+) -> Option<Vec2> {
+    player_query
+        .iter()
+        .filter(|(_, health)| health.is_alive())
+        .map(|(transform, _)| transform.translation.truncate())
+        .min_by(|a, b| {
+            a.distance_squared(position)
+                .total_cmp(&b.distance_squared(position))
+        })
+}
+
+/// Seconds between turns while searching the last known player position
+const SEARCH_TURN_SECS: f32 = 0.6;
+
+/// Walk horizontally towards `direction_x`, handling walls and ledges ahead
+///
+/// Walking chasers hold at walls and ledges instead of running into or off
+/// them, jump steps and gaps their `jump_force` clears, and with
+/// `EdgeBehavior::JumpDown` drop down after their target.
+fn chase_walk(
+    direction_x: f32,
+    velocity: &mut Velocity,
+    speed: f32,
+    ground: Option<(&GroundMovement, &GroundProbe)>,
 ) {
-    let Ok(player_transform) = player_query.single() else {
-        return;
-    };
+    velocity.x = direction_x.signum() * speed;
+
+    // Edges only matter while walking; jumps keep their momentum
+    if let Some((movement, probe)) = ground
+        && probe.grounded
+    {
+        match edge_action(
+            probe.towards(direction_x),
+            movement.edge_behavior,
+            movement.jump_force > 0.0,
+        ) {
+            EdgeAction::Continue => {}
+            EdgeAction::Jump => velocity.y = movement.jump_force,
+            EdgeAction::Turn | EdgeAction::Stop => velocity.x = 0.0,
+        }
+    }
+}
+
+/// Seconds between path plans while following the navigation graph
+const REPATH_SECS: f32 = 0.5;
+
+/// Next waypoint towards `target` when it is on another platform
+///
+/// Plans an A* path over the navigation graph every `REPATH_SECS` and drops
+/// waypoints as they are reached. Returns `None` when the target shares the
+/// enemy's platform or cannot be reached, leaving the enemy to walk straight
+/// at it.
+fn navigate(
+    chase: &mut ChaseAI,
+    graph: &NavGraph,
+    feet: Vec2,
+    target: Vec2,
+    ability: &NavAbility,
+    delta: f32,
+) -> Option<NavWaypoint> {
+    let here = graph.platform_at(feet);
+    let there = graph.platform_at(target);
+    if here.is_none() || there.is_none() || here == there {
+        chase.path.clear();
+        chase.repath_timer = 0.0;
+        return None;
+    }
+
+    chase.repath_timer -= delta;
+    if chase.repath_timer <= 0.0 {
+        chase.repath_timer = REPATH_SECS;
+        chase.path = graph.find_path(feet, target, ability).unwrap_or_default();
+    }
+    while chase
+        .path
+        .first()
+        .is_some_and(|waypoint| waypoint.reached(feet))
+    {
+        chase.path.remove(0);
+    }
+    chase.path.first().copied()
+}
 
-    let player_pos = player_transform.translation.truncate();
+/// Walk (and jump, for jump links) towards a waypoint
+///
+/// Ledges and walls are not probed: the link already says whether to walk
+/// on, drop off or jump.
+fn follow_waypoint(
+    waypoint: NavWaypoint,
+    feet: Vec2,
+    velocity: &mut Velocity,
+    speed: f32,
+    ground: (&GroundMovement, &GroundProbe),
+) {
+    let offset = waypoint.position.x - feet.x;
+    velocity.x = if offset.abs() > NAV_WAYPOINT_RADIUS {
+        offset.signum() * speed
+    } else {
+        0.0
+    };
 
-    for (enemy_transform, mut velocity, mut ai, stats) in enemy_query.iter_mut() {
-        if let EnemyAI::Chase(ref mut chase) = *ai {
-            let enemy_pos = enemy_transform.translation.truncate();
-            let distance = enemy_pos.distance(player_pos);
+    let (movement, probe) = ground;
+    if matches!(waypoint.link, NavLinkKind::Jump { .. }) && probe.grounded {
+        velocity.y = movement.jump_force;
+    }
+}
 
-            if distance < chase.detection_range {
-                // Player detected
-                chase.is_chasing = true;
-                chase.lost_sight_timer = 0.0;
+/// Chase AI system - enemy chases the nearest player it can see
+///
+/// Players are spotted within `detection_range` inside the view cone in front
+/// of the enemy, unless ground or walls block the line of sight. After losing
+/// sight the enemy walks to the last known position and looks around there
+/// until `lose_aggro_time` runs out. Walking chasers follow the navigation
+/// graph when their target is on another platform.
+#[allow(clippy::type_complexity)]
+fn chase_ai_system(
+    time: Res<Time>,
+    index: Res<SpatialIndex>,
+    physics_config: Res<PhysicsConfig>,
+    graph: Res<NavGraph>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    solid_query: SolidQuery,
+    mut enemy_query: Query<
+        (
+            &Transform,
+            &Collider,
+            &mut Velocity,
+            &mut EnemyAI,
+            &mut Enemy,
+            &EnemyStats,
+            Option<&StatusEffects>,
+            Option<(&GroundMovement, &GroundProbe)>,
+        ),
+        (Without<Stunned>, Without<Hitstun>),
+    >,
+) {
+    let delta = time.delta_secs();
+    for (enemy_transform, collider, mut velocity, mut ai, mut enemy, stats, status, ground) in
+        enemy_query.iter_mut()
+    {
+        let EnemyAI::Chase(ref mut chase) = *ai else {
+            continue;
+        };
+        let enemy_pos = enemy_transform.translation.truncate();
+        let feet = Vec2::new(enemy_pos.x, collider.aabb(enemy_pos).min.y);
+        let sight = Sight {
+            position: enemy_pos,
+            facing_right: enemy.facing_right,
+            range: chase.detection_range,
+            view_angle: chase.view_angle,
+            line_of_sight: chase.line_of_sight,
+        };
+        let speed = effective_move_speed(stats, status);
+        let ability = ground.map(|(movement, _)| {
+            NavAbility::new(
+                movement,
+                movement.jump_reach(physics_config.gravity, stats.move_speed),
+            )
+        });
+        let waypoint_towards = |chase: &mut ChaseAI, target: Vec2| {
+            ability.and_then(|ability| navigate(chase, &graph, feet, target, &ability, delta))
+        };
 
-                if distance > chase.attack_range {
-                    // Chase player
-                    let direction = (player_pos - enemy_pos).normalize();
-                    velocity.x = direction.x * stats.move_speed;
-                } else {
-                    // In attack range - stop
-                    velocity.x = 0.0;
-                }
-            } else if chase.is_chasing {
-                // Lost sight of player
-                chase.lost_sight_timer += time.delta_secs();
+        if let Some(player_pos) = sight.visible_player(&player_query, &index, &solid_query) {
+            // Player in sight
+            chase.spot(player_pos);
 
-                if chase.lost_sight_timer >= chase.lose_aggro_time {
-                    chase.is_chasing = false;
-                    velocity.x = 0.0;
+            if enemy_pos.distance(player_pos) > chase.attack_range {
+                match (waypoint_towards(chase, player_pos), ground) {
+                    (Some(waypoint), Some(ground)) => {
+                        follow_waypoint(waypoint, feet, &mut velocity, speed, ground)
+                    }
+                    _ => chase_walk(player_pos.x - enemy_pos.x, &mut velocity, speed, ground),
                 }
             } else {
+                // In attack range - stop
                 velocity.x = 0.0;
+                chase.path.clear();
             }
+        } else if chase.is_chasing && chase.lose_sight(delta) {
+            // Search where the player was last seen
+            let Some(last_known) = chase.last_known_position else {
+                velocity.x = 0.0;
+                continue;
+            };
+            const ARRIVAL_THRESHOLD: f32 = 5.0;
+            let offset = last_known.x - enemy_pos.x;
+
+            if let (Some(waypoint), Some(ground)) = (waypoint_towards(chase, last_known), ground) {
+                follow_waypoint(waypoint, feet, &mut velocity, speed, ground);
+                chase.search_turn_timer = SEARCH_TURN_SECS;
+            } else if offset.abs() > ARRIVAL_THRESHOLD {
+                chase_walk(offset, &mut velocity, speed, ground);
+                chase.search_turn_timer = SEARCH_TURN_SECS;
+            } else {
+                velocity.x = 0.0;
+            }
+            // Stopped (arrived or held at an edge) - look around
+            if velocity.x == 0.0 {
+                chase.search_turn_timer -= delta;
+                if chase.search_turn_timer <= 0.0 {
+                    chase.search_turn_timer = SEARCH_TURN_SECS;
+                    enemy.facing_right = !enemy.facing_right;
+                }
+            }
+        } else {
+            velocity.x = 0.0;
+            chase.path.clear();
         }
     }
 }
 
 /// Flying AI system - handles flying movement patterns
+///
+/// Slowing status effects slow down the pattern; stunned enemies hold still.
+/// Hitstunned enemies are carried by their knockback, moving the pattern with them.
+#[allow(clippy::type_complexity)]
 fn flying_ai_system(
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut Velocity, &mut EnemyAI), With<Enemy>>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut EnemyAI,
+            Option<&StatusEffects>,
+            Has<Hitstun>,
+        ),
+        (With<Enemy>, Without<Stunned>),
+    >,
 ) {
-    for (mut transform, mut velocity, mut ai) in query.iter_mut() {
+    for (mut transform, mut velocity, mut ai, status, hitstunned) in query.iter_mut() {
         if let EnemyAI::Flying(ref mut flying) = *ai {
-            flying.time += time.delta_secs();
+            if hitstunned {
+                flying.origin += Vec2::new(velocity.x, velocity.y) * time.delta_secs();
+                continue;
+            }
+
+            let speed_multiplier = status.map_or(1.0, StatusEffects::speed_multiplier);
+            flying.time += time.delta_secs() * speed_multiplier;
 
             match &flying.pattern {
                 FlyingPattern::SineWave {
@@ -592,33 +958,59 @@ fn contact_damage_cooldown_system(time: Res<Time>, mut query: Query<&mut Contact
     }
 }
 
-/// Contact damage system - deals damage when enemy touches player
+/// Contact damage system - deals damage when enemy touches a hostile hurtbox
+#[allow(clippy::type_complexity)]
 fn contact_damage_system(
-    player_query: Query<(Entity, &Transform, &Collider, Option<&Invincibility>), With<Player>>,
-    mut enemy_query: Query<(&Transform, &Collider, &mut ContactDamage, &Enemy)>,
+    factions: Res<FactionsConfig>,
+    index: Res<SpatialIndex>,
+    target_query: Query<
+        (&Transform, &Collider, Option<&Faction>),
+        (With<Hurtbox>, Without<Invincibility>),
+    >,
+    mut enemy_query: Query<(
+        Entity,
+        &Transform,
+        &Collider,
+        &mut ContactDamage,
+        &Enemy,
+        &Faction,
+    )>,
     mut damage_events: MessageWriter<DamageEvent>,
 ) {
-    let Ok((player_entity, player_transform, player_collider, invincibility)) =
-        player_query.single()
-    else {
-        return;
-    };
-
-    // Skip if player is invincible
-    if invincibility.is_some() {
-        return;
-    }
-
-    let player_pos = player_transform.translation.truncate();
-
-    for (enemy_transform, enemy_collider, mut contact, enemy) in enemy_query.iter_mut() {
+    for (enemy_entity, enemy_transform, enemy_collider, mut contact, enemy, faction) in
+        enemy_query.iter_mut()
+    {
         if !contact.can_damage() {
             continue;
         }
 
         let enemy_pos = enemy_transform.translation.truncate();
 
-        if check_collision(player_pos, player_collider, enemy_pos, enemy_collider) {
+        // Invincible targets are skipped by the query filter
+        let touched = index
+            .bodies
+            .query(enemy_collider.aabb(enemy_pos))
+            .into_iter()
+            .filter(|target_entity| *target_entity != enemy_entity)
+            .filter_map(|target_entity| {
+                target_query
+                    .get(target_entity)
+                    .ok()
+                    .map(|target| (target_entity, target))
+            })
+            .find(|(_, (target_transform, target_collider, target_faction))| {
+                factions.is_hostile(*faction, target_faction.copied().unwrap_or_default())
+                    && check_collision(
+                        target_transform.translation.truncate(),
+                        target_collider,
+                        enemy_pos,
+                        enemy_collider,
+                    )
+            });
+
+        if let Some((player_entity, (player_transform, _, _))) = touched {
+            let player_pos = player_transform.translation.truncate();
+
             // Calculate knockback direction (push player away from enemy)
             let direction = (player_pos - enemy_pos).normalize_or_zero();
             let knockback = direction * contact.knockback_force;
@@ -626,34 +1018,37 @@ fn contact_damage_system(
             damage_events.write(DamageEvent {
                 target: player_entity,
                 damage: contact.damage,
+                damage_type: DamageType::Physical,
                 knockback,
-                attacker: None,
+                attacker: Some(enemy_entity),
+                impact: contact.impact,
+                status: None,
+                critical: false,
             });
 
             contact.reset_cooldown();
 
             info!(
-                "{} dealt {} contact damage to player",
-                enemy.enemy_type, contact.damage
+                "{} dealt {} contact damage to {:?}",
+                enemy.enemy_type, contact.damage, player_entity
             );
         }
     }
 }
 
-/// Projectile shooter system - enemies fire projectiles at player
+/// Projectile shooter system - enemies fire projectiles at the nearest player
+#[allow(clippy::type_complexity)]
 fn projectile_shooter_system(
     mut commands: Commands,
     time: Res<Time>,
-    player_query: Query<&Transform, With<Player>>,
-    mut shooter_query: Query<(&Transform, &mut ProjectileShooter, &Enemy)>,
+    fx_assets: Res<FxAssets>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    mut shooter_query: Query<
+        (Entity, &Transform, &mut ProjectileShooter, &Enemy, &Faction),
+        (Without<Stunned>, Without<Hitstun>, Without<ProjectileBurst>),
+    >,
 ) {
-    let Ok(player_transform) = player_query.single() else {
-        return;
-    };
-
-    let player_pos = player_transform.translation.truncate();
-
-    for (enemy_transform, mut shooter, enemy) in shooter_query.iter_mut() {
+    for (entity, enemy_transform, mut shooter, enemy, faction) in shooter_query.iter_mut() {
         shooter.tick(time.delta_secs());
 
         if !shooter.can_fire() {
@@ -661,25 +1056,27 @@ fn projectile_shooter_system(
         }
 
         let enemy_pos = enemy_transform.translation.truncate();
+        let Some(player_pos) = nearest_living_player(enemy_pos, &player_query) else {
+            continue;
+        };
         let distance = enemy_pos.distance(player_pos);
 
         if distance <= shooter.range {
-            // Fire projectile toward player
-            let direction = (player_pos - enemy_pos).normalize();
-            let velocity = direction * shooter.projectile_speed;
-
-            commands.spawn((
-                Transform::from_translation(enemy_pos.extend(0.0)),
-                Visibility::default(),
-                Sprite {
-                    color: Color::srgb(1.0, 0.5, 0.0), // Orange projectile
-                    custom_size: Some(Vec2::new(8.0, 8.0)),
-                    ..default()
-                },
-                EnemyProjectile::new(shooter.damage, 100.0, velocity, 5.0),
-                Collider::new(Vec2::new(8.0, 8.0)),
-                Name::new(format!("Projectile_{}", enemy.enemy_type)),
-            ));
+            // Fire projectiles toward player
+            let volley = ProjectileVolley {
+                projectile: shooter.projectile.clone(),
+                emitter: shooter.emitter,
+                direction: player_pos - enemy_pos,
+                offset: Vec2::ZERO,
+                faction: *faction,
+            };
+            fire_projectiles(
+                &mut commands,
+                &fx_assets,
+                volley,
+                entity,
+                enemy_transform.translation,
+            );
 
             shooter.reset_timer();
 
@@ -688,83 +1085,32 @@ fn projectile_shooter_system(
     }
 }
 
-/// Projectile movement system
-fn projectile_movement_system(
-    time: Res<Time>,
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut Transform, &mut EnemyProjectile)>,
-) {
-    for (entity, mut transform, mut projectile) in query.iter_mut() {
-        // Update position
-        transform.translation.x += projectile.velocity.x * time.delta_secs();
-        transform.translation.y += projectile.velocity.y * time.delta_secs();
-
-        // Update lifetime
-        projectile.lifetime.tick(time.delta());
-
-        if projectile.lifetime.is_finished() {
-            commands.entity(entity).despawn();
-        }
-    }
-}
-
-/// Projectile collision system - check if projectiles hit player
-fn projectile_collision_system(
-    mut commands: Commands,
-    player_query: Query<(Entity, &Transform, &Collider, Option<&Invincibility>), With<Player>>,
-    projectile_query: Query<(Entity, &Transform, &Collider, &EnemyProjectile)>,
-    mut damage_events: MessageWriter<DamageEvent>,
-) {
-    let Ok((player_entity, player_transform, player_collider, invincibility)) =
-        player_query.single()
-    else {
-        return;
-    };
-
-    // Skip if player is invincible
-    if invincibility.is_some() {
-        return;
-    }
-
-    let player_pos = player_transform.translation.truncate();
-
-    for (proj_entity, proj_transform, proj_collider, projectile) in projectile_query.iter() {
-        let proj_pos = proj_transform.translation.truncate();
-
-        if check_collision(player_pos, player_collider, proj_pos, proj_collider) {
-            // Deal damage to player
-            let knockback = projectile.velocity.normalize() * projectile.knockback;
-
-            damage_events.write(DamageEvent {
-                target: player_entity,
-                damage: projectile.damage,
-                knockback,
-                attacker: Some(proj_entity),
-            });
-
-            // Destroy projectile
-            commands.entity(proj_entity).despawn();
-
-            info!("Projectile hit player for {} damage", projectile.damage);
-        }
-    }
-}
-
 /// Update enemy animations based on AI state and velocity
+#[allow(clippy::type_complexity)]
 fn enemy_animation_controller(
     mut query: Query<
         (
             &Enemy,
             &Velocity,
             &EnemyAI,
+            Option<&AIStateMachine>,
+            Option<&MeleeAttacker>,
             &mut AnimationController,
             &mut AnimationState,
         ),
         With<Enemy>,
     >,
 ) {
-    for (enemy, velocity, ai, mut controller, mut state) in query.iter_mut() {
-        let animation_name = match ai {
+    for (enemy, velocity, ai, machine, melee, mut controller, mut state) in query.iter_mut() {
+        // Melee attacks, then animations played by state machine actions take priority
+        let requested = [
+            melee.and_then(MeleeAttacker::animation),
+            machine.and_then(|machine| machine.animation.as_deref()),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|name| controller.animations.contains_key(*name));
+        let animation_name = requested.unwrap_or(match ai {
             EnemyAI::Idle => "idle",
             EnemyAI::Patrol(_) => {
                 if velocity.x.abs() > 0.1 {
@@ -788,7 +1134,7 @@ fn enemy_animation_controller(
                     "idle"
                 }
             }
-        };
+        });
 
         // Only change animation if different from current
         if controller.current_animation != animation_name
@@ -804,16 +1150,21 @@ fn enemy_animation_controller(
 }
 
 /// Update enemy facing direction based on velocity
-fn enemy_facing_system(mut query: Query<(&mut Enemy, &Velocity, &mut Sprite)>) {
+///
+/// Hitstunned enemies keep facing their attacker while knocked back.
+fn enemy_facing_system(mut query: Query<(&mut Enemy, &Velocity, &mut Sprite), Without<Hitstun>>) {
     for (mut enemy, velocity, mut sprite) in query.iter_mut() {
         if velocity.x.abs() > 0.1 {
             enemy.facing_right = velocity.x > 0.0;
-            // Handle sprites that face left by default
-            if enemy.sprite_faces_left {
-                sprite.flip_x = enemy.facing_right;
-            } else {
-                sprite.flip_x = !enemy.facing_right;
-            }
+        }
+        // Handle sprites that face left by default (AI may also turn standing enemies)
+        let flip_x = if enemy.sprite_faces_left {
+            enemy.facing_right
+        } else {
+            !enemy.facing_right
+        };
+        if sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
         }
     }
 }
@@ -843,6 +1194,7 @@ fn enemy_death_system(
 /// Ground collision system for enemies with gravity
 #[allow(clippy::type_complexity)]
 fn enemy_ground_collision(
+    index: Res<SpatialIndex>,
     mut enemy_query: Query<
         (&mut Transform, &mut Velocity, &Collider),
         (With<Enemy>, With<Gravity>),
@@ -852,7 +1204,10 @@ fn enemy_ground_collision(
     for (mut enemy_transform, mut velocity, enemy_collider) in enemy_query.iter_mut() {
         let enemy_pos = enemy_transform.translation.truncate();
 
-        for (ground_transform, ground_collider) in ground_query.iter() {
+        for ground in index.solids.query(enemy_collider.aabb(enemy_pos)) {
+            let Ok((ground_transform, ground_collider)) = ground_query.get(ground) else {
+                continue;
+            };
             let ground_pos = ground_transform.translation.truncate();
 
             // Check AABB collision
@@ -0,0 +1,400 @@
+//! Ground probes for walking enemies
+//!
+//! Each tick the probes look at the level geometry just ahead of a walking
+//! enemy on both sides: a wall at body height, a ledge under the leading foot,
+//! how far the drop past the ledge goes and whether a step or gap is small
+//! enough to jump. Patrol and chase AI read the result to turn, stop, drop
+//! down or jump instead of walking blindly off platforms and into walls.
+
+use bevy::prelude::*;
+use tropical_fox_common::{Collider, Ground, PhysicsConfig, SpatialIndex, Wall};
+
+use crate::components::{Enemy, EnemyStats};
+use crate::config::{AIConfig, EdgeBehavior};
+
+/// How far ahead of the body the probes look (pixels)
+pub const PROBE_DISTANCE: f32 = 4.0;
+
+/// Geometry this far below the feet still counts as ground (pixels)
+pub const GROUND_TOLERANCE: f32 = 2.0;
+
+/// Obstacles lower than this are walked over rather than treated as walls (pixels)
+pub const STEP_TOLERANCE: f32 = 2.0;
+
+/// Edge handling and jumping for an enemy that walks on the ground
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct GroundMovement {
+    /// What to do at a ledge that cannot be jumped
+    pub edge_behavior: EdgeBehavior,
+    /// Upward launch speed for jumps (0 = never jumps)
+    pub jump_force: f32,
+    /// Deepest drop `EdgeBehavior::JumpDown` walks off (pixels)
+    pub max_drop: f32,
+}
+
+impl GroundMovement {
+    /// Create ground movement settings from the AI configuration
+    pub fn from_config(config: &AIConfig) -> Self {
+        Self {
+            edge_behavior: config.edge_behavior,
+            jump_force: config.jump_force,
+            max_drop: config.max_drop,
+        }
+    }
+
+    /// Height and distance a jump covers with the given gravity and run speed
+    pub fn jump_reach(&self, gravity: f32, move_speed: f32) -> JumpReach {
+        let gravity = gravity.abs();
+        if self.jump_force <= 0.0 || gravity <= 0.0 {
+            return JumpReach::default();
+        }
+        let air_time = 2.0 * self.jump_force / gravity;
+        JumpReach {
+            height: self.jump_force * self.jump_force / (2.0 * gravity),
+            distance: move_speed * air_time,
+        }
+    }
+}
+
+/// How high and how far an enemy can jump (pixels)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct JumpReach {
+    /// Highest step that can be jumped onto
+    pub height: f32,
+    /// Widest gap that can be jumped across
+    pub distance: f32,
+}
+
+/// What the probes found on one side of the body
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EdgeProbe {
+    /// Solid geometry blocks the way at body height
+    pub wall: bool,
+    /// No ground under the leading foot
+    pub ledge: bool,
+    /// Distance down to the ground past the ledge, if within `max_drop`
+    pub drop: Option<f32>,
+    /// The wall is a step, or the ledge a gap, that a jump clears
+    pub jumpable: bool,
+}
+
+impl EdgeProbe {
+    /// Whether walking on would hit a wall or leave the ground
+    pub fn blocked(&self) -> bool {
+        self.wall || self.ledge
+    }
+}
+
+/// Probe results for both sides of a walking enemy
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct GroundProbe {
+    /// Standing on ground
+    pub grounded: bool,
+    /// Probe towards -x
+    pub left: EdgeProbe,
+    /// Probe towards +x
+    pub right: EdgeProbe,
+}
+
+impl GroundProbe {
+    /// Probe for the side the enemy wants to move towards
+    pub fn towards(&self, direction: f32) -> &EdgeProbe {
+        if direction < 0.0 {
+            &self.left
+        } else {
+            &self.right
+        }
+    }
+}
+
+/// What a walking enemy should do when moving towards a probed edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeAction {
+    /// Nothing in the way (or a safe drop with `JumpDown`)
+    Continue,
+    /// Turn around
+    Turn,
+    /// Stand still at the edge
+    Stop,
+    /// Jump the step or gap
+    Jump,
+}
+
+/// Decide how to handle the edge ahead
+///
+/// Only enemies allowed to jump (`can_jump`) take `EdgeAction::Jump`; walls
+/// that cannot be jumped always turn or stop the enemy.
+pub fn edge_action(probe: &EdgeProbe, behavior: EdgeBehavior, can_jump: bool) -> EdgeAction {
+    if !probe.blocked() {
+        return EdgeAction::Continue;
+    }
+    if can_jump && probe.jumpable {
+        return EdgeAction::Jump;
+    }
+    if !probe.wall && behavior == EdgeBehavior::JumpDown && probe.drop.is_some() {
+        return EdgeAction::Continue;
+    }
+    match behavior {
+        EdgeBehavior::Stop => EdgeAction::Stop,
+        EdgeBehavior::Turn | EdgeBehavior::JumpDown => EdgeAction::Turn,
+    }
+}
+
+/// Whether `sensor` overlaps any solid
+fn overlaps_any(sensor: Rect, solids: &[Rect]) -> bool {
+    solids
+        .iter()
+        .any(|solid| !solid.intersect(sensor).is_empty())
+}
+
+/// Probe one side (`direction` -1 or 1) of a body against level geometry
+pub fn probe_edge(
+    body: Rect,
+    direction: f32,
+    solids: &[Rect],
+    reach: JumpReach,
+    max_drop: f32,
+) -> EdgeProbe {
+    let feet = body.min.y;
+    let front = if direction < 0.0 {
+        body.min.x
+    } else {
+        body.max.x
+    };
+    // Horizontal span from the leading edge `distance` pixels ahead
+    let ahead = |distance: f32| {
+        let end = front + direction.signum() * distance;
+        (front.min(end), front.max(end))
+    };
+
+    // Wall: solid ahead between step height and the top of the body
+    let (min_x, max_x) = ahead(PROBE_DISTANCE);
+    let wall_sensor = Rect::new(min_x, feet + STEP_TOLERANCE, max_x, body.max.y);
+    let walls: Vec<&Rect> = solids
+        .iter()
+        .filter(|solid| !solid.intersect(wall_sensor).is_empty())
+        .collect();
+    let wall = !walls.is_empty();
+    let step_height = walls
+        .iter()
+        .map(|solid| solid.max.y - feet)
+        .fold(0.0, f32::max);
+
+    // Ledge: no ground under the point just past the leading foot
+    let ledge_sensor = Rect::new(min_x, feet - GROUND_TOLERANCE, max_x, feet);
+    let ledge = !overlaps_any(ledge_sensor, solids);
+
+    // Drop: highest ground below the ledge wide enough to land on
+    let (min_x, max_x) = ahead(body.width());
+    let drop = ledge
+        .then(|| {
+            let drop_sensor = Rect::new(min_x, feet - max_drop, max_x, feet);
+            solids
+                .iter()
+                .filter(|solid| !solid.intersect(drop_sensor).is_empty())
+                .map(|solid| feet - solid.max.y)
+                .fold(None, |nearest: Option<f32>, drop| {
+                    Some(nearest.map_or(drop, |nearest| nearest.min(drop)))
+                })
+        })
+        .flatten();
+
+    // Gap: ground at about the same height within jumping distance
+    let gap = ledge && reach.distance > 0.0 && {
+        let (min_x, max_x) = ahead(reach.distance);
+        let landing_sensor = Rect::new(min_x, feet - GROUND_TOLERANCE, max_x, feet + reach.height);
+        overlaps_any(landing_sensor, solids)
+    };
+
+    let step = wall && reach.height > 0.0 && step_height <= reach.height;
+    EdgeProbe {
+        wall,
+        ledge,
+        drop,
+        jumpable: step || gap,
+    }
+}
+
+/// Probe both sides of a body and check whether it stands on ground
+pub fn probe_ground(body: Rect, solids: &[Rect], reach: JumpReach, max_drop: f32) -> GroundProbe {
+    let feet_sensor = Rect::new(
+        body.min.x,
+        body.min.y - GROUND_TOLERANCE,
+        body.max.x,
+        body.min.y,
+    );
+    GroundProbe {
+        grounded: overlaps_any(feet_sensor, solids),
+        left: probe_edge(body, -1.0, solids, reach, max_drop),
+        right: probe_edge(body, 1.0, solids, reach, max_drop),
+    }
+}
+
+/// Update the ground probes of walking enemies from nearby level geometry
+#[allow(clippy::type_complexity)]
+pub fn ground_probe_system(
+    physics_config: Res<PhysicsConfig>,
+    index: Res<SpatialIndex>,
+    solid_query: Query<(&Transform, &Collider), Or<(With<Ground>, With<Wall>)>>,
+    mut enemy_query: Query<
+        (
+            &Transform,
+            &Collider,
+            &EnemyStats,
+            &GroundMovement,
+            &mut GroundProbe,
+        ),
+        With<Enemy>,
+    >,
+) {
+    for (transform, collider, stats, movement, mut probe) in enemy_query.iter_mut() {
+        let body = collider.aabb(transform.translation.truncate());
+        let reach = movement.jump_reach(physics_config.gravity, stats.move_speed);
+
+        // Everything the probes can touch: the body, the longest jump and the deepest drop
+        let search = Rect::new(
+            body.min.x - reach.distance.max(body.width()) - PROBE_DISTANCE,
+            body.min.y - movement.max_drop - GROUND_TOLERANCE,
+            body.max.x + reach.distance.max(body.width()) + PROBE_DISTANCE,
+            body.max.y.max(body.min.y + reach.height),
+        );
+        let solids: Vec<Rect> = index
+            .solids
+            .query(search)
+            .into_iter()
+            .filter_map(|solid| solid_query.get(solid).ok())
+            .map(|(solid_transform, solid_collider)| {
+                solid_collider.aabb(solid_transform.translation.truncate())
+            })
+            .collect();
+
+        *probe = probe_ground(body, &solids, reach, movement.max_drop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 20x30 body standing on a platform spanning x in [-100, 100] with its top at y=0
+    fn body_at(x: f32) -> Rect {
+        Rect::new(x - 10.0, 0.0, x + 10.0, 30.0)
+    }
+
+    const PLATFORM: Rect = Rect {
+        min: Vec2::new(-100.0, -32.0),
+        max: Vec2::new(100.0, 0.0),
+    };
+
+    #[test]
+    fn test_probe_finds_ledges_walls_and_drops() {
+        let wall = Rect::new(40.0, 0.0, 60.0, 100.0);
+        let lower_floor = Rect::new(-300.0, -80.0, -100.0, -48.0);
+        let solids = [PLATFORM, wall, lower_floor];
+
+        // Open floor
+        let probe = probe_ground(body_at(0.0), &solids, JumpReach::default(), 96.0);
+        assert!(probe.grounded);
+        assert!(!probe.left.blocked() && !probe.right.blocked());
+
+        // Wall on the right
+        let probe = probe_ground(body_at(28.0), &solids, JumpReach::default(), 96.0);
+        assert!(probe.right.wall && !probe.right.ledge);
+
+        // Ledge on the left with the lower floor 48px down
+        let probe = probe_ground(body_at(-92.0), &solids, JumpReach::default(), 96.0);
+        assert!(probe.left.ledge && !probe.left.wall);
+        assert_eq!(probe.left.drop, Some(48.0));
+        // Too deep for a shallow max drop
+        let probe = probe_ground(body_at(-92.0), &solids, JumpReach::default(), 32.0);
+        assert_eq!(probe.left.drop, None);
+    }
+
+    #[test]
+    fn test_probe_jumpable_steps_and_gaps() {
+        let step = Rect::new(20.0, 0.0, 80.0, 20.0);
+        let far_side = Rect::new(-200.0, -32.0, -130.0, 0.0);
+        let solids = [PLATFORM, step, far_side];
+        let reach = JumpReach {
+            height: 40.0,
+            distance: 60.0,
+        };
+
+        let probe = probe_ground(body_at(8.0), &solids, reach, 96.0);
+        assert!(probe.right.wall && probe.right.jumpable);
+        // A 20px step is too high for a 10px jump
+        let low = JumpReach {
+            height: 10.0,
+            distance: 60.0,
+        };
+        assert!(
+            !probe_ground(body_at(8.0), &solids, low, 96.0)
+                .right
+                .jumpable
+        );
+
+        // 30px gap on the left is within reach, not without a jump
+        let probe = probe_ground(body_at(-92.0), &solids, reach, 96.0);
+        assert!(probe.left.ledge && probe.left.jumpable);
+        let probe = probe_ground(body_at(-92.0), &solids, JumpReach::default(), 96.0);
+        assert!(!probe.left.jumpable);
+    }
+
+    #[test]
+    fn test_edge_action() {
+        let ledge = EdgeProbe {
+            ledge: true,
+            drop: Some(48.0),
+            ..default()
+        };
+        let wall = EdgeProbe {
+            wall: true,
+            jumpable: true,
+            ..default()
+        };
+
+        assert_eq!(
+            edge_action(&EdgeProbe::default(), EdgeBehavior::Stop, false),
+            EdgeAction::Continue
+        );
+        assert_eq!(
+            edge_action(&ledge, EdgeBehavior::Turn, false),
+            EdgeAction::Turn
+        );
+        assert_eq!(
+            edge_action(&ledge, EdgeBehavior::Stop, false),
+            EdgeAction::Stop
+        );
+        assert_eq!(
+            edge_action(&ledge, EdgeBehavior::JumpDown, false),
+            EdgeAction::Continue
+        );
+        // Walls are never walked off
+        assert_eq!(
+            edge_action(&wall, EdgeBehavior::JumpDown, false),
+            EdgeAction::Turn
+        );
+        assert_eq!(
+            edge_action(&wall, EdgeBehavior::Turn, true),
+            EdgeAction::Jump
+        );
+    }
+
+    #[test]
+    fn test_jump_reach() {
+        let movement = GroundMovement {
+            edge_behavior: EdgeBehavior::Turn,
+            jump_force: 300.0,
+            max_drop: 96.0,
+        };
+        let reach = movement.jump_reach(-900.0, 60.0);
+        assert!((reach.height - 50.0).abs() < 0.01);
+        assert!((reach.distance - 40.0).abs() < 0.01);
+
+        let grounded = GroundMovement {
+            jump_force: 0.0,
+            ..movement
+        };
+        assert_eq!(grounded.jump_reach(-900.0, 60.0), JumpReach::default());
+    }
+}
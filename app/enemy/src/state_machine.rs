@@ -0,0 +1,536 @@
+//! Runtime for data-driven AI state machines
+//!
+//! `AIStateMachine` tracks the current state of an enemy configured with
+//! `ai.state_machine` in `enemies.ron`. Every frame `ai_state_machine_system`
+//! checks the current state's transitions, swaps the `EnemyAI` movement node
+//! when the state changes and runs the state's actions. Nodes of states that
+//! were left are kept, so a patrol resumes where it stopped after a chase.
+
+use std::collections::HashMap;
+use std::mem::discriminant;
+
+use bevy::prelude::*;
+use tropical_fox_combat::{
+    Faction, FxAssets, Health, Hitstun, ProjectileVolley, Stunned, fire_projectiles,
+};
+use tropical_fox_common::{CharacterAssets, Player, SpatialIndex, Velocity};
+
+use crate::components::{Enemy, EnemyAI, Minion, ProjectileShooter};
+use crate::config::{
+    AIAction, AIConditionInput, AIConfig, AINode, AIStateConfig, AIStateMachineConfig,
+};
+use crate::perception::{Sight, SolidQuery};
+use crate::plugin::{EnemyConfig, create_ai_node, nearest_living_player, spawn_enemy};
+use crate::probes::GroundProbe;
+
+/// Horizontal distance from the summoner at which minions spawn (pixels)
+const MINION_SPAWN_OFFSET: f32 = 32.0;
+
+/// Current state of a data-driven enemy AI
+#[derive(Component, Debug, Clone)]
+pub struct AIStateMachine {
+    /// AI settings used to build movement nodes
+    pub ai: AIConfig,
+    /// States and transitions
+    pub config: AIStateMachineConfig,
+    /// Name of the current state
+    pub current: String,
+    /// Time spent in the current state (seconds)
+    pub time_in_state: f32,
+    /// Time until the state's repeated actions run again (seconds)
+    pub repeat_timer: f32,
+    /// Spawn position (movement nodes are relative to it)
+    pub origin: Vec2,
+    /// Movement nodes of states left earlier, restored when they are entered again
+    pub parked: HashMap<String, EnemyAI>,
+    /// Animation requested by `PlayAnimation`, until the state changes
+    pub animation: Option<String>,
+    /// The current state's `on_enter` actions have not run yet
+    pending_enter: bool,
+}
+
+/// Result of one state machine update
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AIStateUpdate {
+    /// A state was entered this update
+    pub entered: bool,
+    /// Actions to run
+    pub actions: Vec<AIAction>,
+}
+
+impl AIStateMachine {
+    /// Create a state machine in its initial state
+    ///
+    /// Returns `None` when `ai` has no state machine or its initial state is missing.
+    /// Transitions to missing states are dropped with a warning.
+    pub fn new(ai: &AIConfig, origin: Vec2) -> Option<Self> {
+        let mut config = ai.state_machine.clone()?;
+        if config.state(&config.initial).is_none() {
+            warn!(
+                "AI state machine initial state '{}' does not exist",
+                config.initial
+            );
+            return None;
+        }
+        drop_unknown_transitions(&mut config);
+        Some(Self {
+            ai: ai.clone(),
+            current: config.initial.clone(),
+            config,
+            time_in_state: 0.0,
+            repeat_timer: 0.0,
+            origin,
+            parked: HashMap::new(),
+            animation: None,
+            pending_enter: true,
+        })
+    }
+
+    /// Configuration of the current state
+    pub fn state(&self) -> Option<&AIStateConfig> {
+        self.config.state(&self.current)
+    }
+
+    /// Movement node of the current state
+    pub fn node(&self) -> AINode {
+        self.state().map(|state| state.node).unwrap_or_default()
+    }
+
+    /// Movement node for a fresh entry into the current state
+    pub fn build_node(&self) -> EnemyAI {
+        create_ai_node(self.node(), &self.ai, self.origin)
+    }
+
+    /// Switch to another state, parking the current movement node
+    ///
+    /// Entering the current state does nothing, so its node keeps running.
+    pub fn enter(&mut self, state: &str, ai: &mut EnemyAI) {
+        if state == self.current {
+            return;
+        }
+        let Some(node) = self.config.state(state).map(|state| state.node) else {
+            warn!("AI state machine has no state '{}'", state);
+            return;
+        };
+        let restored = self
+            .parked
+            .remove(state)
+            .unwrap_or_else(|| create_ai_node(node, &self.ai, self.origin));
+        let previous = std::mem::replace(ai, restored);
+        self.parked.insert(self.current.clone(), previous);
+
+        self.current = state.to_string();
+        self.time_in_state = 0.0;
+        self.animation = None;
+        self.pending_enter = true;
+    }
+
+    /// Advance time, take the first transition that holds and collect actions to run
+    pub fn update(
+        &mut self,
+        delta: f32,
+        input: AIConditionInput,
+        ai: &mut EnemyAI,
+    ) -> AIStateUpdate {
+        self.time_in_state += delta;
+        let input = AIConditionInput {
+            time_in_state: self.time_in_state,
+            ..input
+        };
+
+        let next = self.state().and_then(|state| {
+            state
+                .transitions
+                .iter()
+                .find(|transition| transition.when.evaluate(&input))
+                .map(|transition| transition.to.clone())
+        });
+        if let Some(next) = next {
+            self.enter(&next, ai);
+        }
+
+        let mut update = AIStateUpdate::default();
+        let Some(state) = self.config.state(&self.current) else {
+            return update;
+        };
+        if self.pending_enter {
+            self.pending_enter = false;
+            self.repeat_timer = state.repeat_secs;
+            update.entered = true;
+            update.actions.extend(state.on_enter.iter().cloned());
+        } else if !state.repeat.is_empty() {
+            self.repeat_timer -= delta;
+            if self.repeat_timer <= 0.0 {
+                self.repeat_timer = state.repeat_secs;
+                update.actions.extend(state.repeat.iter().cloned());
+            }
+        }
+        update
+    }
+
+    /// Apply reloaded AI settings, keeping the current state where possible
+    ///
+    /// Parked nodes of removed states, or of states whose node type changed,
+    /// are dropped. If the current state was removed the machine restarts in
+    /// the initial state; if its node type changed the node is rebuilt.
+    /// Returns false when the new settings have no usable state machine.
+    pub fn reload(&mut self, ai: &AIConfig, current_ai: &mut EnemyAI) -> bool {
+        let Some(mut config) = ai.state_machine.clone() else {
+            return false;
+        };
+        if config.state(&config.initial).is_none() {
+            return false;
+        }
+        drop_unknown_transitions(&mut config);
+
+        let old_node = self.node();
+        self.parked.retain(|name, _| {
+            let old = self
+                .config
+                .state(name)
+                .map(|state| discriminant(&state.node));
+            let new = config.state(name).map(|state| discriminant(&state.node));
+            new.is_some() && old == new
+        });
+        self.ai = ai.clone();
+        self.config = config;
+
+        match self.state().map(|state| state.node) {
+            None => {
+                let initial = self.config.initial.clone();
+                *current_ai = match self.parked.remove(&initial) {
+                    Some(parked) => parked,
+                    None => create_ai_node(
+                        self.config
+                            .state(&initial)
+                            .map(|state| state.node)
+                            .unwrap_or_default(),
+                        &self.ai,
+                        self.origin,
+                    ),
+                };
+                self.current = initial;
+                self.time_in_state = 0.0;
+                self.animation = None;
+                self.pending_enter = true;
+            }
+            Some(node) if discriminant(&node) != discriminant(&old_node) => {
+                *current_ai = self.build_node();
+            }
+            Some(_) => {}
+        }
+        true
+    }
+}
+
+/// Remove transitions whose target state does not exist
+fn drop_unknown_transitions(config: &mut AIStateMachineConfig) {
+    let names: Vec<String> = config.states.keys().cloned().collect();
+    for (name, state) in config.states.iter_mut() {
+        state.transitions.retain(|transition| {
+            let known = names.contains(&transition.to);
+            if !known {
+                warn!(
+                    "AI state '{}' has a transition to missing state '{}', ignoring it",
+                    name, transition.to
+                );
+            }
+            known
+        });
+    }
+}
+
+/// Run data-driven enemy AI: transitions, movement node swaps and actions
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn ai_state_machine_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    fx_assets: Res<FxAssets>,
+    enemy_config: Res<EnemyConfig>,
+    character_assets: Option<Res<CharacterAssets>>,
+    index: Res<SpatialIndex>,
+    player_query: Query<(&Transform, &Health), With<Player>>,
+    solid_query: SolidQuery,
+    minion_query: Query<&Minion>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Health,
+            &Faction,
+            &Enemy,
+            &mut AIStateMachine,
+            &mut EnemyAI,
+            &mut Velocity,
+            Option<&mut ProjectileShooter>,
+            Option<&GroundProbe>,
+        ),
+        (Without<Stunned>, Without<Hitstun>),
+    >,
+) {
+    for (
+        entity,
+        transform,
+        health,
+        faction,
+        enemy,
+        mut machine,
+        mut ai,
+        mut velocity,
+        mut shooter,
+        probe,
+    ) in enemy_query.iter_mut()
+    {
+        let position = transform.translation.truncate();
+        let player = nearest_living_player(position, &player_query);
+
+        // Sight needs raycasts; only look when a transition asks for it
+        let sight_range = machine.state().and_then(|state| {
+            state
+                .transitions
+                .iter()
+                .filter_map(|transition| transition.when.sight_range())
+                .reduce(f32::max)
+        });
+        let visible_player = sight_range.and_then(|range| {
+            let sight = Sight {
+                position,
+                facing_right: enemy.facing_right,
+                range,
+                view_angle: machine.ai.view_angle,
+                line_of_sight: machine.ai.line_of_sight,
+            };
+            sight.visible_player(&player_query, &index, &solid_query)
+        });
+
+        let input = AIConditionInput {
+            player_distance: player.map(|player| player.distance(position)),
+            visible_player_distance: visible_player.map(|player| player.distance(position)),
+            health_ratio: health.ratio(),
+            time_in_state: 0.0,
+        };
+        let update = machine.update(time.delta_secs(), input, &mut ai);
+
+        // New movement node starts from a standstill
+        if update.entered {
+            velocity.x = 0.0;
+        }
+
+        let mut minions = minion_query
+            .iter()
+            .filter(|minion| minion.summoner == entity)
+            .count() as u32;
+        for action in update.actions {
+            match action {
+                AIAction::Shoot => {
+                    let Some(shooter) = shooter.as_mut() else {
+                        continue;
+                    };
+                    let facing = if enemy.facing_right {
+                        Vec2::X
+                    } else {
+                        Vec2::NEG_X
+                    };
+                    let volley = ProjectileVolley {
+                        projectile: shooter.projectile.clone(),
+                        emitter: shooter.emitter,
+                        direction: player.map_or(facing, |player| player - position),
+                        offset: Vec2::ZERO,
+                        faction: *faction,
+                    };
+                    fire_projectiles(
+                        &mut commands,
+                        &fx_assets,
+                        volley,
+                        entity,
+                        transform.translation,
+                    );
+                    shooter.reset_timer();
+                }
+                AIAction::Jump(speed) => {
+                    if probe.is_some_and(|probe| probe.grounded) {
+                        velocity.y = speed;
+                    }
+                }
+                AIAction::PlayAnimation(name) => machine.animation = Some(name),
+                AIAction::SpawnMinion(minion_type) => {
+                    if minions >= machine.config.max_minions {
+                        continue;
+                    }
+                    let Some(minion_config) = enemy_config.0.get(&minion_type) else {
+                        warn!("Unknown minion enemy type '{}'", minion_type);
+                        continue;
+                    };
+                    let side = if enemy.facing_right { 1.0 } else { -1.0 };
+                    let minion = spawn_enemy(
+                        &mut commands,
+                        &minion_type,
+                        position + Vec2::X * side * MINION_SPAWN_OFFSET,
+                        minion_config,
+                        character_assets.as_deref(),
+                    );
+                    commands.entity(minion).insert(Minion { summoner: entity });
+                    minions += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AICondition, AIStateConfig, AITransition};
+
+    fn config() -> AIConfig {
+        let mut states = HashMap::new();
+        states.insert(
+            "patrol".to_string(),
+            AIStateConfig {
+                node: AINode::Patrol,
+                transitions: vec![AITransition {
+                    to: "chase".to_string(),
+                    when: AICondition::PlayerInRange(100.0),
+                }],
+                ..default()
+            },
+        );
+        states.insert(
+            "chase".to_string(),
+            AIStateConfig {
+                node: AINode::Chase,
+                on_enter: vec![AIAction::PlayAnimation("run".to_string())],
+                repeat: vec![AIAction::Jump(200.0)],
+                repeat_secs: 0.5,
+                transitions: vec![AITransition {
+                    to: "patrol".to_string(),
+                    when: AICondition::Not(Box::new(AICondition::PlayerInRange(150.0))),
+                }],
+            },
+        );
+        AIConfig {
+            state_machine: Some(AIStateMachineConfig {
+                initial: "patrol".to_string(),
+                states,
+                max_minions: 3,
+            }),
+            ..default()
+        }
+    }
+
+    fn player_at(distance: f32) -> AIConditionInput {
+        AIConditionInput {
+            player_distance: Some(distance),
+            health_ratio: 1.0,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_transitions_park_nodes() {
+        let mut machine = AIStateMachine::new(&config(), Vec2::ZERO).unwrap();
+        let mut ai = machine.build_node();
+        assert!(matches!(ai, EnemyAI::Patrol(_)));
+        if let EnemyAI::Patrol(ref mut patrol) = ai {
+            patrol.current_point_index = 1;
+        }
+
+        // Entering the initial state runs its (empty) enter actions once
+        let update = machine.update(0.1, player_at(300.0), &mut ai);
+        assert!(update.entered && update.actions.is_empty());
+
+        let update = machine.update(0.1, player_at(50.0), &mut ai);
+        assert_eq!(machine.current, "chase");
+        assert!(matches!(ai, EnemyAI::Chase(_)));
+        assert_eq!(
+            update.actions,
+            vec![AIAction::PlayAnimation("run".to_string())]
+        );
+
+        // Repeated actions every 0.5 seconds
+        assert!(
+            machine
+                .update(0.3, player_at(120.0), &mut ai)
+                .actions
+                .is_empty()
+        );
+        assert_eq!(
+            machine.update(0.3, player_at(120.0), &mut ai).actions,
+            vec![AIAction::Jump(200.0)]
+        );
+
+        // Back to the patrol where it left off
+        machine.update(0.1, player_at(300.0), &mut ai);
+        assert_eq!(machine.current, "patrol");
+        let EnemyAI::Patrol(patrol) = &ai else {
+            panic!("expected the patrol node");
+        };
+        assert_eq!(patrol.current_point_index, 1);
+    }
+
+    #[test]
+    fn test_reload_keeps_current_state() {
+        let mut machine = AIStateMachine::new(&config(), Vec2::ZERO).unwrap();
+        let mut ai = machine.build_node();
+        machine.update(0.1, player_at(50.0), &mut ai);
+        assert_eq!(machine.current, "chase");
+
+        // Same states: the chase and its runtime state survive
+        if let EnemyAI::Chase(ref mut chase) = ai {
+            chase.is_chasing = true;
+        }
+        assert!(machine.reload(&config(), &mut ai));
+        assert_eq!(machine.current, "chase");
+        assert!(matches!(ai, EnemyAI::Chase(ref chase) if chase.is_chasing));
+
+        // Chase state removed: restart in the initial state
+        let mut removed = config();
+        if let Some(state_machine) = removed.state_machine.as_mut() {
+            state_machine.states.remove("chase");
+        }
+        assert!(machine.reload(&removed, &mut ai));
+        assert_eq!(machine.current, "patrol");
+        assert!(matches!(ai, EnemyAI::Patrol(_)));
+
+        assert!(!machine.reload(&AIConfig::default(), &mut ai));
+    }
+
+    #[test]
+    fn test_unknown_and_self_transitions() {
+        let mut ai_config = config();
+        if let Some(state_machine) = ai_config.state_machine.as_mut() {
+            let patrol = state_machine.states.get_mut("patrol").unwrap();
+            patrol.transitions.insert(
+                0,
+                AITransition {
+                    to: "flee".to_string(),
+                    when: AICondition::PlayerInRange(100.0),
+                },
+            );
+            let chase = state_machine.states.get_mut("chase").unwrap();
+            chase.transitions.insert(
+                0,
+                AITransition {
+                    to: "chase".to_string(),
+                    when: AICondition::PlayerInRange(150.0),
+                },
+            );
+        }
+
+        // The transition to the missing state is dropped when the machine is built
+        let mut machine = AIStateMachine::new(&ai_config, Vec2::ZERO).unwrap();
+        assert_eq!(machine.state().unwrap().transitions.len(), 1);
+        let mut ai = machine.build_node();
+        machine.update(0.1, player_at(50.0), &mut ai);
+        assert_eq!(machine.current, "chase");
+
+        // Re-entering the current state keeps its running node
+        if let EnemyAI::Chase(ref mut chase) = ai {
+            chase.is_chasing = true;
+        }
+        let update = machine.update(0.1, player_at(50.0), &mut ai);
+        assert!(!update.entered);
+        assert!(matches!(ai, EnemyAI::Chase(ref chase) if chase.is_chasing));
+        assert!(machine.time_in_state > 0.0);
+    }
+}
@@ -0,0 +1,266 @@
+//! Player ability progression
+//!
+//! Movement abilities (double jump, wall jump, dash, ground pound) are gated
+//! behind unlocks so levels can be designed around locked abilities.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tropical_fox_common::{Collider, Player, SpatialIndex};
+
+/// Unlockable player abilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Ability {
+    /// Jump again while airborne
+    DoubleJump,
+    /// Slide down and jump off walls
+    WallJump,
+    /// Directional dash
+    Dash,
+    /// One additional air dash (can be unlocked multiple times)
+    ExtraAirDash,
+    /// Slam straight down while airborne
+    GroundPound,
+}
+
+/// Abilities unlocked by a player
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerAbilities {
+    /// Whether double jump is unlocked
+    #[serde(default)]
+    pub double_jump: bool,
+    /// Whether wall slide and wall jump are unlocked
+    #[serde(default)]
+    pub wall_jump: bool,
+    /// Whether dash is unlocked
+    #[serde(default)]
+    pub dash: bool,
+    /// Number of extra air dashes unlocked on top of `PlayerStats::max_air_dashes`
+    #[serde(default)]
+    pub extra_air_dashes: u32,
+    /// Whether ground pound is unlocked
+    #[serde(default)]
+    pub ground_pound: bool,
+}
+
+impl PlayerAbilities {
+    /// Create abilities with every ability in the list unlocked
+    pub fn from_unlocked(abilities: &[Ability]) -> Self {
+        let mut unlocked = Self::default();
+        for ability in abilities {
+            unlocked.unlock(*ability);
+        }
+        unlocked
+    }
+
+    /// Check whether an ability is unlocked
+    pub fn has(&self, ability: Ability) -> bool {
+        match ability {
+            Ability::DoubleJump => self.double_jump,
+            Ability::WallJump => self.wall_jump,
+            Ability::Dash => self.dash,
+            Ability::ExtraAirDash => self.extra_air_dashes > 0,
+            Ability::GroundPound => self.ground_pound,
+        }
+    }
+
+    /// Unlock an ability
+    ///
+    /// Returns true if the unlock changed anything. `ExtraAirDash` always stacks.
+    pub fn unlock(&mut self, ability: Ability) -> bool {
+        let flag = match ability {
+            Ability::DoubleJump => &mut self.double_jump,
+            Ability::WallJump => &mut self.wall_jump,
+            Ability::Dash => &mut self.dash,
+            Ability::GroundPound => &mut self.ground_pound,
+            Ability::ExtraAirDash => {
+                self.extra_air_dashes += 1;
+                return true;
+            }
+        };
+        let newly_unlocked = !*flag;
+        *flag = true;
+        newly_unlocked
+    }
+
+    /// Abilities unlocked by either set (extra air dashes take the larger count)
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            double_jump: self.double_jump || other.double_jump,
+            wall_jump: self.wall_jump || other.wall_jump,
+            dash: self.dash || other.dash,
+            extra_air_dashes: self.extra_air_dashes.max(other.extra_air_dashes),
+            ground_pound: self.ground_pound || other.ground_pound,
+        }
+    }
+
+    /// Number of air dashes available after touching ground
+    pub fn air_dashes(&self, base_air_dashes: u32) -> u32 {
+        if self.dash {
+            base_air_dashes + self.extra_air_dashes
+        } else {
+            0
+        }
+    }
+
+    /// Number of mid-air jumps available after touching ground
+    pub fn air_jumps(&self, max_air_jumps: u32) -> u32 {
+        if self.double_jump { max_air_jumps } else { 0 }
+    }
+}
+
+/// Event requesting that an ability be unlocked for a player
+#[derive(bevy::ecs::prelude::Message)]
+pub struct UnlockAbilityEvent {
+    /// The player receiving the ability
+    pub player: Entity,
+    /// The ability to unlock
+    pub ability: Ability,
+}
+
+/// Collectible item that unlocks an ability when touched by the player
+#[derive(Component, Debug, Clone)]
+pub struct AbilityPickup {
+    /// The ability granted by this pickup
+    pub ability: Ability,
+}
+
+/// Unlock abilities when the player touches an ability pickup
+pub fn ability_pickup_system(
+    mut commands: Commands,
+    index: Res<SpatialIndex>,
+    player_query: Query<(Entity, &Transform, &Collider), With<Player>>,
+    pickup_query: Query<(&Transform, &Collider, &AbilityPickup)>,
+    mut unlock_events: MessageWriter<UnlockAbilityEvent>,
+) {
+    // Co-op players touching the same pickup take it once
+    let mut taken = HashSet::new();
+
+    for (player_entity, player_transform, player_collider) in player_query.iter() {
+        let player_pos = player_transform.translation.truncate();
+
+        for pickup_entity in index.bodies.query(player_collider.aabb(player_pos)) {
+            let Ok((pickup_transform, pickup_collider, pickup)) = pickup_query.get(pickup_entity)
+            else {
+                continue;
+            };
+
+            if !taken.contains(&pickup_entity)
+                && crate::systems::check_aabb_collision(
+                    player_pos,
+                    player_collider,
+                    pickup_transform.translation.truncate(),
+                    pickup_collider,
+                )
+            {
+                taken.insert(pickup_entity);
+                unlock_events.write(UnlockAbilityEvent {
+                    player: player_entity,
+                    ability: pickup.ability,
+                });
+                commands.entity(pickup_entity).despawn();
+            }
+        }
+    }
+}
+
+/// Apply ability unlock events and persist the result to the save file
+///
+/// The save file keeps every ability unlocked by any player.
+pub fn apply_ability_unlocks(
+    mut commands: Commands,
+    mut unlock_events: MessageReader<UnlockAbilityEvent>,
+    mut player_query: Query<&mut PlayerAbilities, With<Player>>,
+    save_data: Option<ResMut<crate::save::SaveData>>,
+) {
+    let mut changed = false;
+
+    for event in unlock_events.read() {
+        if !player_query.contains(event.player) {
+            continue;
+        }
+
+        // Progression is shared, so co-op partners unlock the ability too
+        for mut abilities in player_query.iter_mut() {
+            if abilities.unlock(event.ability) {
+                info!("Unlocked ability: {:?}", event.ability);
+                changed = true;
+            }
+        }
+    }
+
+    if !changed {
+        return;
+    }
+
+    let abilities = player_query
+        .iter()
+        .fold(PlayerAbilities::default(), |all, abilities| {
+            all.union(abilities)
+        });
+
+    let save = match save_data {
+        Some(mut save) => {
+            save.abilities = save.abilities.union(&abilities);
+            save.clone()
+        }
+        None => {
+            let save = crate::save::SaveData { abilities };
+            commands.insert_resource(save.clone());
+            save
+        }
+    };
+
+    if let Err(e) = crate::save::write_save_data(crate::save::SAVE_FILE_PATH, &save) {
+        warn!("Failed to write save file: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abilities_default_locked() {
+        let abilities = PlayerAbilities::default();
+        assert!(!abilities.has(Ability::DoubleJump));
+        assert!(!abilities.has(Ability::Dash));
+        assert_eq!(abilities.air_dashes(1), 0);
+        assert_eq!(abilities.air_jumps(1), 0);
+    }
+
+    #[test]
+    fn test_ability_unlock() {
+        let mut abilities = PlayerAbilities::default();
+        assert!(abilities.unlock(Ability::Dash));
+        assert!(!abilities.unlock(Ability::Dash)); // Already unlocked
+        assert_eq!(abilities.air_dashes(1), 1);
+
+        // Extra air dashes stack
+        assert!(abilities.unlock(Ability::ExtraAirDash));
+        assert!(abilities.unlock(Ability::ExtraAirDash));
+        assert_eq!(abilities.air_dashes(1), 3);
+    }
+
+    #[test]
+    fn test_abilities_union() {
+        let mut first = PlayerAbilities::from_unlocked(&[Ability::Dash, Ability::ExtraAirDash]);
+        first.unlock(Ability::ExtraAirDash);
+        let second = PlayerAbilities::from_unlocked(&[Ability::WallJump, Ability::ExtraAirDash]);
+
+        let all = first.union(&second);
+        assert!(all.has(Ability::Dash));
+        assert!(all.has(Ability::WallJump));
+        assert!(!all.has(Ability::GroundPound));
+        assert_eq!(all.extra_air_dashes, 2);
+    }
+
+    #[test]
+    fn test_from_unlocked() {
+        let abilities = PlayerAbilities::from_unlocked(&[Ability::WallJump, Ability::DoubleJump]);
+        assert!(abilities.has(Ability::WallJump));
+        assert!(abilities.has(Ability::DoubleJump));
+        assert!(!abilities.has(Ability::GroundPound));
+        assert_eq!(abilities.air_jumps(1), 1);
+    }
+}
@@ -0,0 +1,100 @@
+//! Collectibles
+//!
+//! Items picked up by touching them. The total collected (shared by co-op
+//! players) is tracked in `CollectibleCount` and shown on the HUD.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use tropical_fox_common::{Collider, Player, SpatialIndex};
+
+/// Item that adds to the collectible count when touched by a player
+#[derive(Component, Debug, Clone)]
+pub struct Collectible {
+    /// Amount added to the count
+    pub value: u32,
+}
+
+/// Number of collectibles picked up
+#[derive(Resource, Debug, Default)]
+pub struct CollectibleCount {
+    /// Current count
+    pub count: u32,
+}
+
+impl CollectibleCount {
+    /// Add collected items to the count
+    pub fn add(&mut self, value: u32) {
+        self.count += value;
+    }
+}
+
+/// Pick up collectibles touched by a player
+pub fn collectible_pickup_system(
+    mut commands: Commands,
+    index: Res<SpatialIndex>,
+    player_query: Query<(&Transform, &Collider), With<Player>>,
+    collectible_query: Query<(&Transform, &Collider, &Collectible)>,
+    mut count: ResMut<CollectibleCount>,
+) {
+    // Co-op players touching the same item pick it up once
+    let mut collected = HashSet::new();
+
+    for (player_transform, player_collider) in player_query.iter() {
+        let player_pos = player_transform.translation.truncate();
+
+        for entity in index.bodies.query(player_collider.aabb(player_pos)) {
+            let Ok((transform, collider, collectible)) = collectible_query.get(entity) else {
+                continue;
+            };
+
+            if !collected.contains(&entity)
+                && crate::systems::check_aabb_collision(
+                    player_pos,
+                    player_collider,
+                    transform.translation.truncate(),
+                    collider,
+                )
+            {
+                collected.insert(entity);
+                count.add(collectible.value);
+                commands.entity(entity).despawn();
+                debug!("Collected {} (total: {})", collectible.value, count.count);
+            }
+        }
+    }
+}
+
+/// Spawn a row of test collectibles on the ground
+pub fn spawn_test_collectibles(mut commands: Commands) {
+    let size = Vec2::new(10.0, 10.0);
+
+    for i in 0..5 {
+        commands.spawn((
+            Transform::from_xyz(-200.0 + i as f32 * 30.0, -170.0, 0.0),
+            Visibility::default(),
+            Sprite {
+                color: Color::srgb(0.9, 0.3, 0.8), // Gem
+                custom_size: Some(size),
+                ..default()
+            },
+            Collectible { value: 1 },
+            Collider::new(size),
+            Name::new("Gem"),
+        ));
+    }
+
+    info!("Test collectibles spawned");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collectible_count() {
+        let mut count = CollectibleCount::default();
+        count.add(1);
+        count.add(5);
+        assert_eq!(count.count, 6);
+    }
+}
@@ -9,6 +9,9 @@ use std::fs;
 use std::path::Path;
 use thiserror::Error;
 
+use crate::abilities::Ability;
+use tropical_fox_combat::RangedAttackConfig;
+
 /// Player character definition with asset paths
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerTypeConfig {
@@ -21,6 +24,12 @@ pub struct PlayerTypeConfig {
     /// Description of the character
     #[serde(default)]
     pub description: String,
+    /// Abilities unlocked when starting without a save file
+    #[serde(default)]
+    pub starting_abilities: Vec<Ability>,
+    /// Ranged attack (None = character has no ranged attack)
+    #[serde(default)]
+    pub ranged_attack: Option<RangedAttackConfig>,
 }
 
 /// Players configuration file format
@@ -121,6 +130,8 @@ mod tests {
             name: "Fox".to_string(),
             animation_config_path: "graphics/characters/players/fox/fox_animations.ron".to_string(),
             description: "A clever fox character".to_string(),
+            starting_abilities: vec![Ability::Dash],
+            ranged_attack: None,
         };
 
         assert_eq!(player.id, "fox");
@@ -138,6 +149,8 @@ mod tests {
                 animation_config_path: "graphics/characters/players/fox/fox_animations.ron"
                     .to_string(),
                 description: "A clever fox".to_string(),
+                starting_abilities: Vec::new(),
+                ranged_attack: None,
             },
         );
 
@@ -0,0 +1,101 @@
+//! Local co-op settings
+//!
+//! Optional second player on a separate input device. Configured in the
+//! `coop` section of `assets/config/game_settings.ron`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use tropical_fox_common::InputDevice;
+
+/// How lives are tracked when several players are in the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LivesMode {
+    /// All players draw from one pool; game over when it runs out
+    #[default]
+    Shared,
+    /// Each player has their own lives; game over when everyone is out
+    PerPlayer,
+}
+
+/// Local co-op settings
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct CoopSettings {
+    /// Spawn a second player
+    #[serde(default)]
+    pub enabled: bool,
+    /// Lives per player (or the size of the shared pool)
+    #[serde(default = "default_lives")]
+    pub lives: u32,
+    /// How lives are tracked
+    #[serde(default)]
+    pub lives_mode: LivesMode,
+    /// Device controlling player one
+    #[serde(default)]
+    pub player_one_device: InputDevice,
+    /// Device controlling player two
+    #[serde(default = "default_player_two_device")]
+    pub player_two_device: InputDevice,
+}
+
+fn default_lives() -> u32 {
+    3
+}
+
+fn default_player_two_device() -> InputDevice {
+    InputDevice::Gamepad(0)
+}
+
+impl Default for CoopSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lives: default_lives(),
+            lives_mode: LivesMode::default(),
+            player_one_device: InputDevice::default(),
+            player_two_device: default_player_two_device(),
+        }
+    }
+}
+
+impl CoopSettings {
+    /// Input device of each player to spawn, in player order
+    pub fn player_devices(&self) -> Vec<InputDevice> {
+        if self.enabled {
+            vec![self.player_one_device, self.player_two_device]
+        } else {
+            vec![self.player_one_device]
+        }
+    }
+
+    /// Whether all players draw from a single lives pool
+    pub fn shared_lives(&self) -> bool {
+        self.enabled && self.lives_mode == LivesMode::Shared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coop_disabled_spawns_one_player() {
+        let settings = CoopSettings::default();
+        assert_eq!(settings.player_devices(), vec![InputDevice::Keyboard]);
+        assert!(!settings.shared_lives());
+    }
+
+    #[test]
+    fn test_coop_settings_parse() {
+        let settings: CoopSettings = ron::from_str(
+            "(enabled: true, lives_mode: PerPlayer, player_one_device: KeyboardLeft, player_two_device: KeyboardRight)",
+        )
+        .unwrap();
+
+        assert_eq!(settings.lives, 3);
+        assert_eq!(
+            settings.player_devices(),
+            vec![InputDevice::KeyboardLeft, InputDevice::KeyboardRight]
+        );
+        assert!(!settings.shared_lives());
+    }
+}
@@ -0,0 +1,312 @@
+//! Player input mapping
+//!
+//! Translates keyboard and gamepad state into each player's `PlayerInput`.
+
+use bevy::input::gamepad::{Gamepad, GamepadButton, GamepadConnectionEvent};
+use bevy::prelude::*;
+use tropical_fox_combat::{ChargingAttack, Stunned};
+use tropical_fox_common::{ActionState, InputDevice, PlayerInput};
+
+/// Keyboard bindings for one player
+#[derive(Debug)]
+pub struct KeyBindings {
+    /// Move left
+    pub left: &'static [KeyCode],
+    /// Move right
+    pub right: &'static [KeyCode],
+    /// Aim up (dash direction)
+    pub up: &'static [KeyCode],
+    /// Aim down (dash direction, aerial down-attack)
+    pub down: &'static [KeyCode],
+    /// Jump and wall jump
+    pub jump: &'static [KeyCode],
+    /// Dash
+    pub dash: &'static [KeyCode],
+    /// Melee attack (hold to charge)
+    pub attack: &'static [KeyCode],
+    /// Ground pound
+    pub ground_pound: &'static [KeyCode],
+    /// Ranged attack
+    pub ranged: &'static [KeyCode],
+    /// Block and parry
+    pub block: &'static [KeyCode],
+}
+
+/// Single-player layout using the whole keyboard
+pub const KEYBOARD_BINDINGS: KeyBindings = KeyBindings {
+    left: &[KeyCode::ArrowLeft, KeyCode::KeyA],
+    right: &[KeyCode::ArrowRight, KeyCode::KeyD],
+    up: &[KeyCode::ArrowUp, KeyCode::KeyW],
+    down: &[KeyCode::ArrowDown, KeyCode::KeyS],
+    jump: &[KeyCode::Space],
+    dash: &[KeyCode::ShiftLeft, KeyCode::ShiftRight],
+    attack: &[KeyCode::KeyX, KeyCode::KeyJ],
+    ground_pound: &[KeyCode::KeyC, KeyCode::KeyK],
+    ranged: &[KeyCode::KeyV, KeyCode::KeyL],
+    block: &[KeyCode::KeyZ, KeyCode::Semicolon],
+};
+
+/// Co-op layout for the left half of the keyboard
+pub const KEYBOARD_LEFT_BINDINGS: KeyBindings = KeyBindings {
+    left: &[KeyCode::KeyA],
+    right: &[KeyCode::KeyD],
+    up: &[KeyCode::KeyW],
+    down: &[KeyCode::KeyS],
+    jump: &[KeyCode::Space],
+    dash: &[KeyCode::ShiftLeft],
+    attack: &[KeyCode::KeyF],
+    ground_pound: &[KeyCode::KeyG],
+    ranged: &[KeyCode::KeyH],
+    block: &[KeyCode::KeyR],
+};
+
+/// Co-op layout for the arrow keys and numpad
+pub const KEYBOARD_RIGHT_BINDINGS: KeyBindings = KeyBindings {
+    left: &[KeyCode::ArrowLeft],
+    right: &[KeyCode::ArrowRight],
+    up: &[KeyCode::ArrowUp],
+    down: &[KeyCode::ArrowDown],
+    jump: &[KeyCode::Numpad0, KeyCode::Enter],
+    dash: &[KeyCode::ShiftRight],
+    attack: &[KeyCode::Numpad1],
+    ground_pound: &[KeyCode::Numpad2],
+    ranged: &[KeyCode::Numpad3],
+    block: &[KeyCode::Numpad4],
+};
+
+/// Gamepad bindings (shared by all gamepads)
+pub const GAMEPAD_JUMP: GamepadButton = GamepadButton::South;
+pub const GAMEPAD_DASH: GamepadButton = GamepadButton::RightTrigger;
+pub const GAMEPAD_ATTACK: GamepadButton = GamepadButton::West;
+pub const GAMEPAD_GROUND_POUND: GamepadButton = GamepadButton::East;
+pub const GAMEPAD_RANGED: GamepadButton = GamepadButton::North;
+pub const GAMEPAD_BLOCK: GamepadButton = GamepadButton::LeftTrigger;
+
+impl KeyBindings {
+    /// Keyboard bindings for a device (None for gamepads)
+    pub fn for_device(device: InputDevice) -> Option<&'static KeyBindings> {
+        match device {
+            InputDevice::Keyboard => Some(&KEYBOARD_BINDINGS),
+            InputDevice::KeyboardLeft => Some(&KEYBOARD_LEFT_BINDINGS),
+            InputDevice::KeyboardRight => Some(&KEYBOARD_RIGHT_BINDINGS),
+            InputDevice::Gamepad(_) => None,
+        }
+    }
+
+    /// Read the player input from the keyboard
+    pub fn read(&self, keyboard: &ButtonInput<KeyCode>, input: &mut PlayerInput) {
+        let axis = |negative: &[KeyCode], positive: &[KeyCode]| {
+            let mut value = 0.0;
+            if keyboard.any_pressed(negative.iter().copied()) {
+                value -= 1.0;
+            }
+            if keyboard.any_pressed(positive.iter().copied()) {
+                value += 1.0;
+            }
+            value
+        };
+
+        input.movement = Vec2::new(axis(self.left, self.right), axis(self.down, self.up));
+        input.jump = key_action(keyboard, self.jump);
+        input.dash = key_action(keyboard, self.dash);
+        input.attack = key_action(keyboard, self.attack);
+        input.ground_pound = key_action(keyboard, self.ground_pound);
+        input.ranged = key_action(keyboard, self.ranged);
+        input.block = key_action(keyboard, self.block);
+    }
+}
+
+fn key_action(keyboard: &ButtonInput<KeyCode>, keys: &[KeyCode]) -> ActionState {
+    ActionState {
+        pressed: keyboard.any_pressed(keys.iter().copied()),
+        just_pressed: keyboard.any_just_pressed(keys.iter().copied()),
+        just_released: keyboard.any_just_released(keys.iter().copied()),
+    }
+}
+
+fn gamepad_action(gamepad: &Gamepad, button: GamepadButton) -> ActionState {
+    ActionState {
+        pressed: gamepad.pressed(button),
+        just_pressed: gamepad.just_pressed(button),
+        just_released: gamepad.just_released(button),
+    }
+}
+
+/// Read the player input from a gamepad (left stick or d-pad to move)
+fn read_gamepad(gamepad: &Gamepad, input: &mut PlayerInput) {
+    input.movement = (gamepad.left_stick() + gamepad.dpad()).clamp(Vec2::NEG_ONE, Vec2::ONE);
+    input.jump = gamepad_action(gamepad, GAMEPAD_JUMP);
+    input.dash = gamepad_action(gamepad, GAMEPAD_DASH);
+    input.attack = gamepad_action(gamepad, GAMEPAD_ATTACK);
+    input.ground_pound = gamepad_action(gamepad, GAMEPAD_GROUND_POUND);
+    input.ranged = gamepad_action(gamepad, GAMEPAD_RANGED);
+    input.block = gamepad_action(gamepad, GAMEPAD_BLOCK);
+}
+
+/// Gamepads by `InputDevice::Gamepad` index, in connection order
+///
+/// A gamepad keeps its index while disconnected, so reconnecting it gives the
+/// same player control again. A newly seen gamepad takes the first index whose
+/// gamepad is disconnected, or a new one.
+#[derive(Resource, Debug, Default)]
+pub struct GamepadSlots {
+    /// Gamepad entity of each index and whether it is connected
+    slots: Vec<(Entity, bool)>,
+}
+
+impl GamepadSlots {
+    /// Assign a connected gamepad to an index, returning the index
+    pub fn connect(&mut self, gamepad: Entity) -> usize {
+        let index = self
+            .slots
+            .iter()
+            .position(|&(entity, _)| entity == gamepad)
+            .or_else(|| self.slots.iter().position(|&(_, connected)| !connected));
+
+        match index {
+            Some(index) => {
+                self.slots[index] = (gamepad, true);
+                index
+            }
+            None => {
+                self.slots.push((gamepad, true));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Mark a gamepad as disconnected (it keeps its index until replaced)
+    pub fn disconnect(&mut self, gamepad: Entity) {
+        if let Some(slot) = self.slots.iter_mut().find(|(entity, _)| *entity == gamepad) {
+            slot.1 = false;
+        }
+    }
+
+    /// Connected gamepad at an index
+    pub fn get(&self, index: usize) -> Option<Entity> {
+        self.slots
+            .get(index)
+            .and_then(|&(entity, connected)| connected.then_some(entity))
+    }
+}
+
+/// Assign gamepads to indices as they connect and disconnect
+pub fn update_gamepad_slots(
+    mut connection_events: MessageReader<GamepadConnectionEvent>,
+    mut slots: ResMut<GamepadSlots>,
+) {
+    for event in connection_events.read() {
+        if event.connected() {
+            let index = slots.connect(event.gamepad);
+            info!("Gamepad {} assigned to index {}", event.gamepad, index);
+        } else {
+            slots.disconnect(event.gamepad);
+        }
+    }
+}
+
+/// Update every player's input from their device
+///
+/// Stunned players and players whose gamepad is gone get a cleared input. A
+/// charge in progress is dropped then, so the cleared attack button doesn't
+/// release it.
+pub fn update_player_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    slots: Res<GamepadSlots>,
+    gamepad_query: Query<&Gamepad>,
+    mut query: Query<(Entity, &mut PlayerInput, Has<Stunned>, Has<ChargingAttack>)>,
+) {
+    for (entity, mut input, stunned, charging) in query.iter_mut() {
+        // Stunned players can't act
+        if stunned {
+            input.clear();
+            if charging {
+                commands.entity(entity).remove::<ChargingAttack>();
+            }
+            continue;
+        }
+
+        match input.device {
+            InputDevice::Gamepad(index) => {
+                let gamepad = slots
+                    .get(index)
+                    .and_then(|entity| gamepad_query.get(entity).ok());
+                match gamepad {
+                    Some(gamepad) => read_gamepad(gamepad, &mut input),
+                    None => {
+                        input.clear();
+                        if charging {
+                            commands.entity(entity).remove::<ChargingAttack>();
+                        }
+                    }
+                }
+            }
+            device => {
+                if let Some(bindings) = KeyBindings::for_device(device) {
+                    bindings.read(&keyboard, &mut input);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyboard_bindings_read() {
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        keyboard.press(KeyCode::KeyA);
+        keyboard.press(KeyCode::KeyX);
+
+        let mut input = PlayerInput::new(InputDevice::Keyboard);
+        KEYBOARD_BINDINGS.read(&keyboard, &mut input);
+        assert_eq!(input.horizontal(), -1.0);
+        assert!(input.attack.pressed && input.attack.just_pressed);
+        assert!(!input.jump.pressed);
+        assert!(!input.block.pressed);
+    }
+
+    #[test]
+    fn test_gamepad_slots_survive_reconnects() {
+        let first = Entity::from_raw_u32(1).unwrap();
+        let second = Entity::from_raw_u32(2).unwrap();
+        let third = Entity::from_raw_u32(3).unwrap();
+
+        let mut slots = GamepadSlots::default();
+        assert_eq!(slots.connect(second), 0);
+        assert_eq!(slots.connect(first), 1);
+
+        // Player one's gamepad drops out: player two keeps index 1
+        slots.disconnect(second);
+        assert_eq!(slots.get(0), None);
+        assert_eq!(slots.get(1), Some(first));
+
+        // Reconnecting gives the gamepad its index back
+        assert_eq!(slots.connect(second), 0);
+        assert_eq!(slots.get(0), Some(second));
+
+        // A new gamepad replaces a disconnected one
+        slots.disconnect(first);
+        assert_eq!(slots.connect(third), 1);
+    }
+
+    #[test]
+    fn test_split_keyboard_layouts_do_not_overlap() {
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        keyboard.press(KeyCode::ArrowRight);
+        keyboard.press(KeyCode::Numpad0);
+
+        let mut left = PlayerInput::new(InputDevice::KeyboardLeft);
+        KEYBOARD_LEFT_BINDINGS.read(&keyboard, &mut left);
+        assert_eq!(left.movement, Vec2::ZERO);
+        assert!(!left.jump.pressed);
+
+        let mut right = PlayerInput::new(InputDevice::KeyboardRight);
+        KEYBOARD_RIGHT_BINDINGS.read(&keyboard, &mut right);
+        assert_eq!(right.horizontal(), 1.0);
+        assert!(right.jump.just_pressed);
+    }
+}
@@ -2,10 +2,22 @@
 //!
 //! This crate handles player controls, movement, and physics.
 
+pub mod abilities;
+pub mod collectibles;
 pub mod config;
+pub mod coop;
+pub mod input;
 pub mod plugin;
+pub mod save;
+pub mod state_machine;
 pub mod systems;
 
 // Re-export commonly used types
+pub use abilities::*;
+pub use collectibles::*;
 pub use config::*;
+pub use coop::*;
+pub use input::*;
 pub use plugin::{PlayerPlugin, spawn_test_ground};
+pub use save::*;
+pub use state_machine::*;
@@ -4,16 +4,30 @@
 
 use bevy::prelude::*;
 use tropical_fox_animation::{AnimationClip, AnimationController, AnimationState};
-use tropical_fox_combat::{AttackCooldown, Health, Lives, PlayerHealth, PlayerSpawnPoint};
+use tropical_fox_combat::{
+    AttackCooldown, ComboState, Faction, Health, Hurtbox, Lives, PlayerHealth, PlayerSpawnPoint,
+    RangedAttack, SharedLives, Shield,
+};
 use tropical_fox_common::{
     CharacterAssets, CharacterTextureAtlas, Collider, GameState, Gravity, GroundDetection, Player,
-    PlayerStats, Velocity,
+    PlayerInput, PlayerSlot, PlayerState, PlayerStateChanged, PlayerStats, Velocity,
+    custom_physics, update_spatial_index,
 };
 
+use crate::abilities::{
+    Ability, AbilityPickup, PlayerAbilities, UnlockAbilityEvent, ability_pickup_system,
+    apply_ability_unlocks,
+};
+use crate::collectibles::{CollectibleCount, collectible_pickup_system, spawn_test_collectibles};
 use crate::config::SelectedCharacter;
+use crate::coop::CoopSettings;
+use crate::input::{GamepadSlots, update_gamepad_slots, update_player_input};
+use crate::save::{SAVE_FILE_PATH, SaveData, load_save_data_optional};
+use crate::state_machine::player_state_machine;
 use crate::systems::{
-    flip_sprite_by_facing, ground_collision, player_dash, player_horizontal_movement, player_jump,
-    update_dash, variable_jump_height, wall_collision, wall_jump, wall_slide,
+    aerial_attack_bounce, flip_sprite_by_facing, ground_collision, ground_pound, player_dash,
+    player_horizontal_movement, player_jump, update_dash, update_ground_state,
+    variable_jump_height, wall_collision, wall_jump, wall_slide,
 };
 use tropical_fox_animation::systems::player_animation_controller;
 
@@ -88,12 +102,44 @@ impl Plugin for PlayerPlugin {
         // Add spawn point resource
         app.insert_resource(PlayerSpawnPoint::default());
 
+        // Co-op settings (the game inserts these from game_settings.ron)
+        app.init_resource::<CoopSettings>();
+
+        // Collectible count (shown on the HUD)
+        app.init_resource::<CollectibleCount>();
+
+        // Load save file (abilities fall back to the character's starting abilities)
+        if let Some(save) = load_save_data_optional(SAVE_FILE_PATH) {
+            app.insert_resource(save);
+        }
+
+        // Events (Messages in Bevy 0.17)
+        app.add_message::<UnlockAbilityEvent>();
+        app.add_message::<PlayerStateChanged>();
+
         // Load player character assets in PostStartup
         // This ensures AnimationPlugin's Startup system has created CharacterAssets resource
         app.add_systems(PostStartup, load_player_assets);
 
         // Spawn player and test level when entering InGame state
-        app.add_systems(OnEnter(GameState::InGame), (spawn_player, spawn_test_walls));
+        app.add_systems(
+            OnEnter(GameState::InGame),
+            (
+                spawn_player,
+                spawn_test_walls,
+                spawn_test_ability_pickups,
+                spawn_test_collectibles,
+            ),
+        );
+
+        // Read each player's device before gameplay systems run
+        app.init_resource::<GamepadSlots>();
+        app.add_systems(
+            PreUpdate,
+            (update_gamepad_slots, update_player_input)
+                .chain()
+                .after(bevy::input::InputSystems),
+        );
 
         // Player input and movement systems (run in Update)
         app.add_systems(
@@ -106,21 +152,50 @@ impl Plugin for PlayerPlugin {
                 player_dash,
                 update_dash,
                 wall_slide,
+                ground_pound,
             )
                 .run_if(in_state(GameState::InGame)),
         );
 
-        // Collision and sprite systems (run in FixedUpdate after physics)
-        // Animation controller must run after ground_collision to use updated is_grounded
+        // Pogo bounce must read aerial hits before the attack hitbox despawns
+        app.add_systems(
+            Update,
+            aerial_attack_bounce
+                .after(tropical_fox_combat::attack_collision_system)
+                .before(tropical_fox_combat::attack_lifetime_system)
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Ability unlock systems
+        app.add_systems(
+            Update,
+            (ability_pickup_system, apply_ability_unlocks)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        // Collectible pickups
+        app.add_systems(
+            Update,
+            collectible_pickup_system.run_if(in_state(GameState::InGame)),
+        );
+
+        // Collision and sprite systems (run in FixedUpdate after physics rebuilds the spatial index)
+        // State machine must run after ground_collision to use updated is_grounded,
+        // and the animation controller after the state machine.
+        // The AABB collision is skipped when another physics backend resolves contacts.
         app.add_systems(
             FixedUpdate,
             (
-                ground_collision,
-                wall_collision,
+                ground_collision.run_if(custom_physics),
+                wall_collision.run_if(custom_physics),
+                update_ground_state,
+                player_state_machine,
                 flip_sprite_by_facing,
                 player_animation_controller,
             )
                 .chain()
+                .after(update_spatial_index)
                 .run_if(in_state(GameState::InGame)),
         );
     }
@@ -147,12 +222,16 @@ fn create_fallback_animation_controller() -> (AnimationController, AnimationStat
     controller.current_animation = "idle".to_string();
     controller.with_initial_state(true)
 }
-/// Spawn the player entity
+/// Horizontal distance between co-op players at spawn
+const PLAYER_SPAWN_SPACING: f32 = 48.0;
+
+/// Spawn the player entities (one per co-op device)
 fn spawn_player(
     mut commands: Commands,
-    _asset_server: Res<AssetServer>,
     character_assets: Option<Res<CharacterAssets>>,
     selected_character: Option<Res<SelectedCharacter>>,
+    save_data: Option<Res<SaveData>>,
+    coop_settings: Option<Res<CoopSettings>>,
 ) {
     use tropical_fox_animation::load_animation_config_optional;
 
@@ -167,11 +246,26 @@ fn spawn_player(
 
     info!("Spawning player with character: {}", character_id);
 
+    let players_config = crate::config::load_players_config_optional("assets/config/players.ron");
+
+    // Abilities come from the save file, or the character's starting abilities for a new game
+    let abilities = match save_data {
+        Some(save) => save.abilities.clone(),
+        None => players_config
+            .as_ref()
+            .and_then(|config| config.get_player(character_id).ok())
+            .map(|player_def| PlayerAbilities::from_unlocked(&player_def.starting_abilities))
+            .unwrap_or_default(),
+    };
+
+    // Ranged attack is configured per character
+    let ranged_attack = players_config
+        .as_ref()
+        .and_then(|config| config.get_player(character_id).ok())
+        .and_then(|player_def| player_def.ranged_attack.clone());
+
     // Try to load animation config from file, fallback to hardcoded if failed
     let (animation_controller, animation_state) = {
-        let players_config =
-            crate::config::load_players_config_optional("assets/config/players.ron");
-
         if let Some(config) = players_config {
             if let Ok(player_def) = config.get_player(character_id) {
                 let animation_config_path = format!("assets/{}", player_def.animation_config_path);
@@ -214,114 +308,100 @@ fn spawn_player(
         }
     };
 
-    // Check if we have character assets loaded
-    if let Some(assets) = character_assets {
-        // Get assets for the selected character, falling back to default if not found
-        let character_atlas = assets.get(character_id).or_else(|| {
+    // Use the character's texture atlas if available, otherwise a colored square
+    let atlas = character_assets.as_ref().and_then(|assets| {
+        assets.get(character_id).or_else(|| {
             warn!(
                 "Assets for '{}' not found, using default (fox)",
                 character_id
             );
             assets.get_default()
-        });
-
-        if let Some(atlas) = character_atlas {
-            // Spawn with texture atlas
-            let mut entity = commands.spawn(Transform::from_xyz(0.0, 100.0, 0.0));
-
-            entity.insert(Visibility::default());
-            entity.insert(Sprite {
-                image: atlas.texture.clone(),
-                texture_atlas: Some(TextureAtlas {
-                    layout: atlas.layout.clone(),
-                    index: 0,
-                }),
-                custom_size: Some(player_size),
-                ..default()
-            });
-            entity.insert(Name::new("Player"));
-
-            entity.insert(Player::default());
-            entity.insert(PlayerStats::default());
-            entity.insert(GroundDetection::default());
-
-            entity.insert(Velocity::zero());
-            entity.insert(Gravity::default());
-            entity.insert(Collider::new(player_size));
-
-            entity.insert(animation_controller);
-            entity.insert(animation_state);
-
-            // Combat components
-            entity.insert(Health::new(100.0));
-            entity.insert(PlayerHealth);
-            entity.insert(Lives::new(3));
-            entity.insert(AttackCooldown::default());
+        })
+    });
 
-            info!(
-                "Player spawned at position (0, 100) with character '{}' texture atlas",
-                character_id
-            );
-        } else {
-            // Fallback to colored square if no assets are available
+    let sprite = match atlas {
+        Some(atlas) => Sprite {
+            image: atlas.texture.clone(),
+            texture_atlas: Some(TextureAtlas {
+                layout: atlas.layout.clone(),
+                index: 0,
+            }),
+            custom_size: Some(player_size),
+            ..default()
+        },
+        None => {
             warn!(
                 "No character assets available for '{}', spawning with colored square",
                 character_id
             );
-            spawn_player_with_placeholder(
-                &mut commands,
-                player_size,
-                animation_controller,
-                animation_state,
-            );
+            Sprite {
+                color: Color::srgb(0.2, 0.7, 0.3), // Green placeholder
+                custom_size: Some(player_size),
+                ..default()
+            }
         }
+    };
+
+    // Lives are either pooled or tracked per player
+    let coop = coop_settings.map(|c| c.clone()).unwrap_or_default();
+    if coop.shared_lives() {
+        commands.insert_resource(SharedLives(Lives::new(coop.lives)));
     } else {
-        // Fallback to colored square if assets aren't loaded yet
-        warn!("Character assets not loaded yet, spawning player with colored square");
-        spawn_player_with_placeholder(
-            &mut commands,
-            player_size,
-            animation_controller,
-            animation_state,
-        );
+        commands.remove_resource::<SharedLives>();
     }
-}
-
-/// Spawn player with a colored placeholder square
-fn spawn_player_with_placeholder(
-    commands: &mut Commands,
-    player_size: Vec2,
-    animation_controller: AnimationController,
-    animation_state: AnimationState,
-) {
-    let mut entity = commands.spawn(Transform::from_xyz(0.0, 100.0, 0.0));
 
-    entity.insert(Visibility::default());
-    entity.insert(Sprite {
-        color: Color::srgb(0.2, 0.7, 0.3), // Green placeholder
-        custom_size: Some(player_size),
-        ..default()
-    });
-    entity.insert(Name::new("Player"));
-
-    entity.insert(Player::default());
-    entity.insert(PlayerStats::default());
-    entity.insert(GroundDetection::default());
-
-    entity.insert(Velocity::zero());
-    entity.insert(Gravity::default());
-    entity.insert(Collider::new(player_size));
-
-    entity.insert(animation_controller);
-    entity.insert(animation_state);
+    for (slot, device) in coop.player_devices().into_iter().enumerate() {
+        let position = Vec3::new(slot as f32 * PLAYER_SPAWN_SPACING, 100.0, 0.0);
+        let name = if slot == 0 {
+            Name::new("Player")
+        } else {
+            Name::new(format!("Player {}", slot + 1))
+        };
 
-    // Combat components
-    entity.insert(Health::new(100.0));
-    entity.insert(PlayerHealth);
-    entity.insert(Lives::new(3));
-    entity.insert(AttackCooldown::default());
+        let mut entity = commands.spawn(Transform::from_translation(position));
+
+        entity.insert(Visibility::default());
+        entity.insert(sprite.clone());
+        entity.insert(name);
+
+        entity.insert(Player::default());
+        entity.insert(PlayerSlot(slot));
+        entity.insert(PlayerInput::new(device));
+        entity.insert(PlayerStats::default());
+        entity.insert(PlayerState::default());
+        entity.insert(abilities.clone());
+        entity.insert(GroundDetection::default());
+
+        entity.insert(Velocity::zero());
+        entity.insert(Gravity::default());
+        entity.insert(Collider::new(player_size));
+
+        entity.insert(animation_controller.clone());
+        entity.insert(animation_state.clone());
+
+        // Combat components
+        entity.insert(Health::new(100.0));
+        entity.insert(PlayerHealth);
+        entity.insert(Hurtbox);
+        entity.insert(Faction::Player);
+        if !coop.shared_lives() {
+            entity.insert(Lives::new(coop.lives));
+        }
+        entity.insert(AttackCooldown::default());
+        entity.insert(ComboState::default());
+        entity.insert(Shield::default());
+        if let Some(config) = ranged_attack.clone() {
+            entity.insert(RangedAttack::new(config));
+        }
 
-    info!("Player spawned at position (0, 100) with placeholder sprite");
+        info!(
+            "Player {} spawned at {:?} with character '{}' ({:?})",
+            slot + 1,
+            position,
+            character_id,
+            device
+        );
+    }
 }
 
 /// Spawn a ground platform for testing
@@ -396,3 +476,51 @@ pub fn spawn_test_walls(mut commands: Commands) {
 
     info!("Test walls spawned at positions (-350, 50) and (350, 50)");
 }
+
+/// Spawn ability pickups for testing progression
+///
+/// Abilities already unlocked in the save file get no pickup.
+pub fn spawn_test_ability_pickups(mut commands: Commands, save_data: Option<Res<SaveData>>) {
+    let pickup_size = Vec2::new(16.0, 16.0);
+
+    let pickups = [
+        // Double jump pickup above the middle platform
+        (
+            Ability::DoubleJump,
+            Vec3::new(0.0, -10.0, 0.0),
+            Color::srgb(0.3, 0.6, 1.0), // Blue pickup
+            "Double Jump Pickup",
+        ),
+        // Ground pound pickup near the right wall
+        (
+            Ability::GroundPound,
+            Vec3::new(250.0, -160.0, 0.0),
+            Color::srgb(1.0, 0.6, 0.2), // Orange pickup
+            "Ground Pound Pickup",
+        ),
+    ];
+
+    for (ability, position, color, name) in pickups {
+        if save_data
+            .as_ref()
+            .is_some_and(|save| save.abilities.has(ability))
+        {
+            continue;
+        }
+
+        commands.spawn((
+            Transform::from_translation(position),
+            Visibility::default(),
+            Sprite {
+                color,
+                custom_size: Some(pickup_size),
+                ..default()
+            },
+            AbilityPickup { ability },
+            Collider::new(pickup_size),
+            Name::new(name),
+        ));
+    }
+
+    info!("Test ability pickups spawned");
+}
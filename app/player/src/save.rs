@@ -0,0 +1,99 @@
+//! Save file persistence
+//!
+//! Stores player progression (unlocked abilities) in a RON save file.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::abilities::PlayerAbilities;
+
+/// Default save file location (relative to the working directory)
+pub const SAVE_FILE_PATH: &str = "saves/save.ron";
+
+/// Persistent player progression
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+pub struct SaveData {
+    /// Abilities unlocked so far
+    #[serde(default)]
+    pub abilities: PlayerAbilities,
+}
+
+/// Save file errors
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("Failed to access save file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON save file: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+
+    #[error("Failed to serialize save data: {0}")]
+    SerializeError(#[from] ron::Error),
+}
+
+/// Load save data from a RON file
+pub fn load_save_data<P: AsRef<Path>>(path: P) -> Result<SaveData, SaveError> {
+    let content = fs::read_to_string(path)?;
+    let save: SaveData = ron::from_str(&content)?;
+    Ok(save)
+}
+
+/// Load save data from a RON file, or return None if there is no usable save
+pub fn load_save_data_optional<P: AsRef<Path>>(path: P) -> Option<SaveData> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return None;
+    }
+
+    match load_save_data(path) {
+        Ok(save) => {
+            info!("Loaded save file from {}", path.display());
+            Some(save)
+        }
+        Err(e) => {
+            warn!("Failed to load save file: {}", e);
+            None
+        }
+    }
+}
+
+/// Write save data to a RON file, creating parent directories as needed
+pub fn write_save_data<P: AsRef<Path>>(path: P, save: &SaveData) -> Result<(), SaveError> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::Ability;
+
+    #[test]
+    fn test_save_roundtrip() {
+        let save = SaveData {
+            abilities: PlayerAbilities::from_unlocked(&[Ability::Dash, Ability::ExtraAirDash]),
+        };
+
+        let path =
+            std::env::temp_dir().join(format!("tropical_fox_save_test_{}.ron", std::process::id()));
+        write_save_data(&path, &save).unwrap();
+        let loaded = load_save_data(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.abilities, save.abilities);
+    }
+
+    #[test]
+    fn test_missing_save_is_none() {
+        assert!(load_save_data_optional("does/not/exist.ron").is_none());
+    }
+}
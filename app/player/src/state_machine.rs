@@ -0,0 +1,238 @@
+//! Player state machine
+//!
+//! Derives a `PlayerState` from movement and combat components each tick,
+//! validates it against the transition table, runs enter/exit hooks and
+//! fires `PlayerStateChanged` so animation, audio and VFX can react.
+
+use bevy::prelude::*;
+use tropical_fox_combat::{Attacking, ChargingAttack, Health, InKnockback};
+use tropical_fox_common::{GroundDetection, Player, PlayerState, PlayerStateChanged, Velocity};
+
+use crate::abilities::PlayerAbilities;
+
+/// Snapshot of the conditions that decide the player state
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlayerStateContext {
+    /// Health has reached zero
+    pub dead: bool,
+    /// Currently in knockback
+    pub hurt: bool,
+    /// Dash timer is running
+    pub dashing: bool,
+    /// An attack is in progress
+    pub attacking: bool,
+    /// Sliding down a wall
+    pub wall_sliding: bool,
+    /// Standing on the ground
+    pub grounded: bool,
+    /// Current velocity
+    pub velocity: Vec2,
+}
+
+impl PlayerStateContext {
+    /// State the player should be in, ignoring the transition table
+    pub fn desired_state(&self) -> PlayerState {
+        if self.dead {
+            PlayerState::Dead
+        } else if self.hurt {
+            PlayerState::Hurt
+        } else if self.dashing {
+            PlayerState::Dash
+        } else if self.attacking {
+            PlayerState::Attack
+        } else if self.wall_sliding {
+            PlayerState::WallSlide
+        } else {
+            self.movement_state()
+        }
+    }
+
+    /// Plain movement state (Idle, Run, Jump or Fall)
+    pub fn movement_state(&self) -> PlayerState {
+        if self.grounded {
+            if self.velocity.x.abs() > 10.0 {
+                PlayerState::Run
+            } else {
+                PlayerState::Idle
+            }
+        } else if self.velocity.y > 50.0 {
+            PlayerState::Jump
+        } else {
+            PlayerState::Fall
+        }
+    }
+}
+
+/// Resolve the next state from the current one
+///
+/// Returns None if the player should stay in the current state. Transitions
+/// not allowed by the table fall back to the plain movement state.
+pub fn next_player_state(
+    current: PlayerState,
+    context: &PlayerStateContext,
+) -> Option<PlayerState> {
+    [context.desired_state(), context.movement_state()]
+        .into_iter()
+        .find(|&state| current.can_enter(state))
+        .filter(|&state| state != current)
+}
+
+/// Hook run when a state is entered
+fn on_enter_state(
+    state: PlayerState,
+    entity: Entity,
+    player: &mut Player,
+    velocity: &mut Velocity,
+    commands: &mut Commands,
+) {
+    match state {
+        PlayerState::Hurt | PlayerState::Dead => {
+            // Taking damage interrupts dashes, ground pounds and charging
+            player.dash_timer = 0.0;
+            player.ground_pounding = false;
+            commands.entity(entity).remove::<ChargingAttack>();
+
+            if state == PlayerState::Dead {
+                velocity.x = 0.0;
+                velocity.y = 0.0;
+            }
+        }
+        PlayerState::WallSlide => {
+            // Sliding down a wall ends a ground pound
+            player.ground_pounding = false;
+        }
+        _ => {}
+    }
+}
+
+/// Hook run when a state is exited
+fn on_exit_state(state: PlayerState, player: &mut Player) {
+    if state == PlayerState::Dash {
+        // Make sure an interrupted dash does not resume
+        player.dash_timer = 0.0;
+    }
+}
+
+/// Update the player state machine and fire transition events
+#[allow(clippy::type_complexity)]
+pub fn player_state_machine(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut PlayerState,
+        &mut Player,
+        &mut Velocity,
+        &PlayerAbilities,
+        &GroundDetection,
+        Option<&Health>,
+        Has<InKnockback>,
+        Has<Attacking>,
+    )>,
+    mut state_events: MessageWriter<PlayerStateChanged>,
+) {
+    for (entity, mut state, mut player, mut velocity, abilities, ground, health, hurt, attacking) in
+        &mut query
+    {
+        let context = PlayerStateContext {
+            dead: health.is_some_and(|h| !h.is_alive()),
+            hurt,
+            dashing: player.dash_timer > 0.0,
+            attacking,
+            // Mirrors the conditions used by the wall_slide system
+            wall_sliding: abilities.wall_jump
+                && player.wall_contact != 0
+                && !ground.is_grounded
+                && !player.ground_pounding
+                && velocity.y < 0.0,
+            grounded: ground.is_grounded,
+            velocity: Vec2::new(velocity.x, velocity.y),
+        };
+
+        let Some(next) = next_player_state(*state, &context) else {
+            continue;
+        };
+
+        let previous = *state;
+        on_exit_state(previous, &mut player);
+        on_enter_state(next, entity, &mut player, &mut velocity, &mut commands);
+        *state = next;
+
+        debug!("Player state: {:?} -> {:?}", previous, next);
+        state_events.write(PlayerStateChanged {
+            entity,
+            from: previous,
+            to: next,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desired_state_priority() {
+        let context = PlayerStateContext {
+            hurt: true,
+            dashing: true,
+            attacking: true,
+            grounded: true,
+            ..default()
+        };
+        assert_eq!(context.desired_state(), PlayerState::Hurt);
+
+        let context = PlayerStateContext {
+            dashing: true,
+            attacking: true,
+            ..default()
+        };
+        assert_eq!(context.desired_state(), PlayerState::Dash);
+    }
+
+    #[test]
+    fn test_movement_states() {
+        let mut context = PlayerStateContext {
+            grounded: true,
+            ..default()
+        };
+        assert_eq!(context.desired_state(), PlayerState::Idle);
+
+        context.velocity.x = 100.0;
+        assert_eq!(context.desired_state(), PlayerState::Run);
+
+        context.grounded = false;
+        context.velocity.y = 200.0;
+        assert_eq!(context.desired_state(), PlayerState::Jump);
+
+        context.velocity.y = -200.0;
+        assert_eq!(context.desired_state(), PlayerState::Fall);
+    }
+
+    #[test]
+    fn test_disallowed_transition_falls_back_to_movement() {
+        // Hurt cannot go straight into an attack
+        let context = PlayerStateContext {
+            attacking: true,
+            grounded: true,
+            ..default()
+        };
+        assert_eq!(
+            next_player_state(PlayerState::Hurt, &context),
+            Some(PlayerState::Idle)
+        );
+
+        // Staying in the same state is not a transition
+        assert_eq!(next_player_state(PlayerState::Attack, &context), None);
+    }
+
+    #[test]
+    fn test_knockback_blocks_actions() {
+        assert!(!PlayerState::Hurt.can_enter(PlayerState::Attack));
+        assert!(!PlayerState::Hurt.can_enter(PlayerState::Dash));
+        assert!(!PlayerState::Dead.can_enter(PlayerState::Attack));
+
+        // Combo follow-ups start while already attacking
+        assert!(PlayerState::Attack.can_enter(PlayerState::Attack));
+        assert!(PlayerState::Run.can_enter(PlayerState::Dash));
+    }
+}
@@ -3,27 +3,68 @@
 //! Handles player input, movement, jumping, and sprite updates.
 
 use bevy::prelude::*;
-use tropical_fox_common::{Collider, Ground, GroundDetection, Player, PlayerStats, Velocity, Wall};
+use tropical_fox_combat::{
+    AerialAttack, ChargingAttack, CombatConfig, DamageEvent, Shield, StatusEffects,
+};
+use tropical_fox_common::{
+    Collider, Ground, GroundDetection, Player, PlayerInput, PlayerState, PlayerStats, SpatialIndex,
+    Velocity, Wall,
+};
 
-/// Handle horizontal player movement based on keyboard input
+use crate::abilities::PlayerAbilities;
+
+/// Handle horizontal player movement based on player input
+#[allow(clippy::type_complexity)]
 pub fn player_horizontal_movement(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Velocity, &mut Player, &PlayerStats, &GroundDetection)>,
+    mut query: Query<(
+        &mut Velocity,
+        &mut Player,
+        &PlayerInput,
+        &PlayerStats,
+        &GroundDetection,
+        Has<ChargingAttack>,
+        Option<&StatusEffects>,
+        Option<&Shield>,
+    )>,
+    combat_config: Res<CombatConfig>,
     time: Res<Time>,
 ) {
-    for (mut velocity, mut player, stats, ground) in &mut query {
-        let mut input = 0.0;
+    for (mut velocity, mut player, player_input, stats, ground, charging, status, shield) in
+        &mut query
+    {
+        // Ground pound locks horizontal movement until landing
+        if player.ground_pounding {
+            continue;
+        }
+
+        let input = player_input.horizontal();
+        let shielding = shield.is_some_and(|shield| shield.raised);
 
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-            input -= 1.0;
-            player.facing_right = false;
+        // A raised shield keeps facing forward while backing off
+        if !shielding {
+            if input < 0.0 {
+                player.facing_right = false;
+            } else if input > 0.0 {
+                player.facing_right = true;
+            }
         }
-        if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-            input += 1.0;
-            player.facing_right = true;
+
+        let mut target_velocity = input * stats.move_speed;
+
+        // Charging an attack slows the player down
+        if charging {
+            target_velocity *= stats.charge_move_speed_multiplier;
         }
 
-        let target_velocity = input * stats.move_speed;
+        // Shielding slows the player down
+        if shielding {
+            target_velocity *= combat_config.shield.move_speed_multiplier;
+        }
+
+        // Slowing status effects (e.g. freeze)
+        if let Some(status) = status {
+            target_velocity *= status.speed_multiplier();
+        }
 
         // Choose deceleration based on whether player is grounded
         let deceleration = if ground.is_grounded {
@@ -43,15 +84,21 @@ pub fn player_horizontal_movement(
     }
 }
 
-/// Handle player jump input with coyote time and jump buffering
+/// Handle player jump input with coyote time, jump buffering, and double jump
 pub fn player_jump(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Velocity, &mut Player, &GroundDetection, &PlayerStats)>,
+    mut query: Query<(
+        &mut Velocity,
+        &mut Player,
+        &PlayerInput,
+        &GroundDetection,
+        &PlayerStats,
+        &PlayerAbilities,
+    )>,
     time: Res<Time>,
 ) {
-    for (mut velocity, mut player, ground, stats) in &mut query {
+    for (mut velocity, mut player, input, ground, stats, abilities) in &mut query {
         // Update jump buffer timer if jump was pressed
-        if keyboard.just_pressed(KeyCode::Space) {
+        if input.jump.just_pressed {
             player.jump_buffer_timer = stats.jump_buffer_time;
         }
 
@@ -68,13 +115,22 @@ pub fn player_jump(
             velocity.y = stats.jump_force;
             player.jump_buffer_timer = 0.0; // Consume the buffered jump
             player.coyote_timer = 0.0; // Consume coyote time
+        } else if input.jump.just_pressed
+            && player.air_jumps_remaining > 0
+            && !player.ground_pounding
+            && !(abilities.wall_jump && player.wall_contact != 0)
+        {
+            // Double jump (wall jump takes priority when touching a wall)
+            velocity.y = stats.double_jump_force;
+            player.air_jumps_remaining -= 1;
+            player.jump_buffer_timer = 0.0;
         }
     }
 }
 
 /// Check AABB collision between two colliders
 #[inline]
-fn check_aabb_collision(
+pub(crate) fn check_aabb_collision(
     pos_a: Vec2,
     collider_a: &Collider,
     pos_b: Vec2,
@@ -96,28 +152,23 @@ pub fn ground_collision(
             &mut Transform,
             &mut Velocity,
             &mut GroundDetection,
-            &mut Player,
-            &PlayerStats,
             &Collider,
         ),
         With<Player>,
     >,
     ground_query: Query<(&Transform, &Collider), (With<Ground>, Without<Player>)>,
-    time: Res<Time>,
+    index: Res<SpatialIndex>,
 ) {
-    for (
-        mut player_transform,
-        mut velocity,
-        mut ground_detection,
-        mut player,
-        stats,
-        player_collider,
-    ) in &mut player_query
+    for (mut player_transform, mut velocity, mut ground_detection, player_collider) in
+        &mut player_query
     {
-        let was_grounded = ground_detection.is_grounded;
         ground_detection.is_grounded = false;
 
-        for (ground_transform, ground_collider) in &ground_query {
+        let bounds = player_collider.aabb(player_transform.translation.truncate());
+        for ground in index.solids.query(bounds) {
+            let Ok((ground_transform, ground_collider)) = ground_query.get(ground) else {
+                continue;
+            };
             let player_pos = player_transform.translation.truncate();
             let ground_pos = ground_transform.translation.truncate();
 
@@ -145,13 +196,33 @@ pub fn ground_collision(
                 }
             }
         }
+    }
+}
+
+/// Manage coyote time and the dash/air jump reset from the ground state
+///
+/// Runs after whichever physics backend updated `GroundDetection` this tick.
+pub fn update_ground_state(
+    mut player_query: Query<(
+        &mut GroundDetection,
+        &mut Player,
+        &PlayerStats,
+        &PlayerAbilities,
+    )>,
+    time: Res<Time>,
+) {
+    for (mut ground_detection, mut player, stats, abilities) in &mut player_query {
+        let was_grounded = ground_detection.was_grounded;
+        ground_detection.was_grounded = ground_detection.is_grounded;
 
-        // Manage coyote time and dash reset
         if ground_detection.is_grounded {
             // Reset coyote timer when on ground
             player.coyote_timer = stats.coyote_time;
-            // Reset dash count when on ground
-            player.dashes_remaining = stats.max_air_dashes;
+            // Reset dash and air jump counts when on ground
+            player.dashes_remaining = abilities.air_dashes(stats.max_air_dashes);
+            player.air_jumps_remaining = abilities.air_jumps(stats.max_air_jumps);
+            // Landing ends a ground pound
+            player.ground_pounding = false;
         } else if was_grounded {
             // Just left ground - only start coyote time if it wasn't consumed by a jump
             // (If player jumped, coyote_timer is already 0)
@@ -167,42 +238,35 @@ pub fn ground_collision(
 
 /// Handle variable jump height (cut jump short when button is released)
 pub fn variable_jump_height(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Velocity, &PlayerStats), With<Player>>,
+    mut query: Query<(&mut Velocity, &PlayerInput, &PlayerStats), With<Player>>,
 ) {
-    for (mut velocity, stats) in &mut query {
+    for (mut velocity, input, stats) in &mut query {
         // If player just released jump button while still moving upward, cut the jump short
-        if keyboard.just_released(KeyCode::Space) && velocity.y > 0.0 {
+        if input.jump.just_released && velocity.y > 0.0 {
             velocity.y *= stats.jump_cut_multiplier;
         }
     }
 }
 
 /// Handle player dash input
+///
+/// Dashes only start from states that can transition to `PlayerState::Dash`.
 pub fn player_dash(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Player, &PlayerStats)>,
+    mut query: Query<(
+        &mut Player,
+        &PlayerInput,
+        &PlayerStats,
+        &PlayerAbilities,
+        &PlayerState,
+    )>,
 ) {
-    for (mut player, stats) in &mut query {
-        // Check if dash button is pressed (Shift key)
-        if keyboard.just_pressed(KeyCode::ShiftLeft) || keyboard.just_pressed(KeyCode::ShiftRight) {
-            // Can only dash if we have dashes remaining
-            if player.dashes_remaining > 0 {
-                // Get dash direction from arrow keys or WASD
-                let mut dash_dir = Vec2::ZERO;
-
-                if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-                    dash_dir.x -= 1.0;
-                }
-                if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-                    dash_dir.x += 1.0;
-                }
-                if keyboard.pressed(KeyCode::ArrowUp) || keyboard.pressed(KeyCode::KeyW) {
-                    dash_dir.y += 1.0;
-                }
-                if keyboard.pressed(KeyCode::ArrowDown) || keyboard.pressed(KeyCode::KeyS) {
-                    dash_dir.y -= 1.0;
-                }
+    for (mut player, input, stats, abilities, state) in &mut query {
+        // Check if dash button is pressed (knockback and death block dashing)
+        if input.dash.just_pressed && state.can_enter(PlayerState::Dash) {
+            // Can only dash if unlocked, not ground pounding, and we have dashes remaining
+            if abilities.dash && !player.ground_pounding && player.dashes_remaining > 0 {
+                // Get dash direction from movement input
+                let mut dash_dir = Vec2::new(input.horizontal(), input.vertical());
 
                 // If no direction input, dash in facing direction
                 if dash_dir.length_squared() == 0.0 {
@@ -254,6 +318,7 @@ pub fn wall_collision(
         With<Player>,
     >,
     wall_query: Query<(&Transform, &Collider), (With<Wall>, Without<Player>)>,
+    index: Res<SpatialIndex>,
 ) {
     for (mut player_transform, player_collider, mut player, mut velocity, ground) in
         &mut player_query
@@ -262,7 +327,11 @@ pub fn wall_collision(
 
         // Only detect wall contact if player is in air (not grounded)
         if !ground.is_grounded {
-            for (wall_transform, wall_collider) in &wall_query {
+            let bounds = player_collider.aabb(player_transform.translation.truncate());
+            for wall in index.solids.query(bounds) {
+                let Ok((wall_transform, wall_collider)) = wall_query.get(wall) else {
+                    continue;
+                };
                 let player_pos = player_transform.translation.truncate();
                 let wall_pos = wall_transform.translation.truncate();
 
@@ -312,10 +381,23 @@ pub fn wall_collision(
 }
 
 /// Apply wall sliding physics
-pub fn wall_slide(mut query: Query<(&mut Velocity, &Player, &PlayerStats, &GroundDetection)>) {
-    for (mut velocity, player, stats, ground) in &mut query {
-        // Only slide if touching a wall and not grounded
-        if player.wall_contact != 0 && !ground.is_grounded && velocity.y < 0.0 {
+pub fn wall_slide(
+    mut query: Query<(
+        &mut Velocity,
+        &Player,
+        &PlayerStats,
+        &PlayerAbilities,
+        &GroundDetection,
+    )>,
+) {
+    for (mut velocity, player, stats, abilities, ground) in &mut query {
+        // Only slide if unlocked, touching a wall and not grounded
+        if abilities.wall_jump
+            && player.wall_contact != 0
+            && !ground.is_grounded
+            && !player.ground_pounding
+            && velocity.y < 0.0
+        {
             // Cap downward velocity to wall slide speed
             if velocity.y < stats.wall_slide_speed {
                 velocity.y = stats.wall_slide_speed;
@@ -326,12 +408,21 @@ pub fn wall_slide(mut query: Query<(&mut Velocity, &Player, &PlayerStats, &Groun
 
 /// Handle wall jump input
 pub fn wall_jump(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Velocity, &mut Player, &PlayerStats, &GroundDetection)>,
+    mut query: Query<(
+        &mut Velocity,
+        &mut Player,
+        &PlayerInput,
+        &PlayerStats,
+        &PlayerAbilities,
+        &GroundDetection,
+    )>,
 ) {
-    for (mut velocity, mut player, stats, ground) in &mut query {
-        // Can only wall jump if touching a wall and not grounded
-        if player.wall_contact != 0 && !ground.is_grounded && keyboard.just_pressed(KeyCode::Space)
+    for (mut velocity, mut player, input, stats, abilities, ground) in &mut query {
+        // Can only wall jump if unlocked, touching a wall and not grounded
+        if abilities.wall_jump
+            && player.wall_contact != 0
+            && !ground.is_grounded
+            && input.jump.just_pressed
         {
             // Jump away from the wall
             let jump_dir_x = -player.wall_contact as f32; // Opposite direction of wall
@@ -342,8 +433,63 @@ pub fn wall_jump(
             player.wall_contact = 0;
 
             // Reset dash count on wall jump (like Celeste)
-            player.dashes_remaining = stats.max_air_dashes;
+            player.dashes_remaining = abilities.air_dashes(stats.max_air_dashes);
+        }
+    }
+}
+
+/// Handle ground pound input (slam straight down while airborne)
+pub fn ground_pound(
+    mut query: Query<(
+        &mut Velocity,
+        &mut Player,
+        &PlayerInput,
+        &PlayerStats,
+        &PlayerAbilities,
+        &GroundDetection,
+    )>,
+) {
+    for (mut velocity, mut player, input, stats, abilities, ground) in &mut query {
+        if !abilities.ground_pound || ground.is_grounded {
+            continue;
+        }
+
+        // Ground pound button starts a ground pound (not during a dash)
+        if !player.ground_pounding && player.dash_timer <= 0.0 && input.ground_pound.just_pressed {
+            player.ground_pounding = true;
         }
+
+        if player.ground_pounding {
+            velocity.x = 0.0;
+            velocity.y = -stats.ground_pound_speed;
+        }
+    }
+}
+
+/// Bounce the player upward when an aerial down-attack hits (pogo)
+///
+/// Landing a down-attack also refreshes air dashes and cancels a ground pound.
+pub fn aerial_attack_bounce(
+    mut damage_events: MessageReader<DamageEvent>,
+    attack_query: Query<&AerialAttack>,
+    mut player_query: Query<(&mut Velocity, &mut Player, &PlayerStats, &PlayerAbilities)>,
+) {
+    for event in damage_events.read() {
+        let Some(attack) = event
+            .attacker
+            .and_then(|attacker| attack_query.get(attacker).ok())
+        else {
+            continue;
+        };
+
+        let Ok((mut velocity, mut player, stats, abilities)) = player_query.get_mut(attack.owner)
+        else {
+            continue;
+        };
+
+        velocity.y = attack.bounce_force;
+        player.ground_pounding = false;
+        player.dashes_remaining = abilities.air_dashes(stats.max_air_dashes);
     }
 }
 
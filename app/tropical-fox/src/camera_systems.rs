@@ -0,0 +1,135 @@
+//! Camera systems
+//!
+//! Keeps every living player in frame, zooming out when co-op players
+//! move apart.
+
+use bevy::prelude::*;
+use tropical_fox_combat::{Health, ScreenShake};
+use tropical_fox_common::Player;
+
+/// Camera that follows and frames the players
+#[derive(Component, Debug)]
+pub struct CameraFollow {
+    /// Follow responsiveness (higher = snappier)
+    pub smoothing: f32,
+    /// Space kept around the players (pixels)
+    pub margin: Vec2,
+    /// Closest zoom (orthographic scale)
+    pub min_scale: f32,
+    /// Farthest zoom (orthographic scale)
+    pub max_scale: f32,
+    /// Smoothed camera position before screen shake
+    pub focus: Vec2,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            smoothing: 5.0,
+            margin: Vec2::new(200.0, 150.0),
+            min_scale: 1.0,
+            max_scale: 2.0,
+            focus: Vec2::ZERO,
+        }
+    }
+}
+
+impl CameraFollow {
+    /// Camera center and scale that frame all the given positions
+    ///
+    /// Returns None if there are no positions.
+    pub fn framing(
+        &self,
+        positions: impl IntoIterator<Item = Vec2>,
+        viewport: Vec2,
+    ) -> Option<(Vec2, f32)> {
+        let (min, max) =
+            positions
+                .into_iter()
+                .fold(None, |bounds: Option<(Vec2, Vec2)>, pos| {
+                    Some(bounds.map_or((pos, pos), |(min, max)| (min.min(pos), max.max(pos))))
+                })?;
+
+        let extent = max - min + self.margin * 2.0;
+        let scale = (extent / viewport.max(Vec2::ONE))
+            .max_element()
+            .clamp(self.min_scale, self.max_scale);
+
+        Some(((min + max) / 2.0, scale))
+    }
+}
+
+/// Move and zoom the camera to frame all living players
+///
+/// Runs before the screen shake so the shake is applied around the
+/// followed position.
+#[allow(clippy::type_complexity)]
+pub fn camera_follow_system(
+    time: Res<Time>,
+    mut shake: ResMut<ScreenShake>,
+    player_query: Query<(&Transform, &Health), (With<Player>, Without<CameraFollow>)>,
+    mut camera_query: Query<(&mut Transform, &mut Projection, &mut CameraFollow, &Camera)>,
+) {
+    let positions: Vec<Vec2> = player_query
+        .iter()
+        .filter(|(_, health)| health.is_alive())
+        .map(|(transform, _)| transform.translation.truncate())
+        .collect();
+
+    for (mut transform, mut projection, mut follow, camera) in camera_query.iter_mut() {
+        let viewport = camera
+            .logical_viewport_size()
+            .unwrap_or(Vec2::new(1280.0, 720.0));
+
+        let Projection::Orthographic(ortho) = projection.as_mut() else {
+            continue;
+        };
+
+        if let Some((target, target_scale)) = follow.framing(positions.iter().copied(), viewport) {
+            // Frame-rate independent exponential smoothing
+            let t = 1.0 - (-follow.smoothing * time.delta_secs()).exp();
+            follow.focus = follow.focus.lerp(target, t);
+            ortho.scale = ortho.scale.lerp(target_scale, t);
+        }
+
+        transform.translation.x = follow.focus.x;
+        transform.translation.y = follow.focus.y;
+        shake.original_pos = transform.translation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framing_single_player() {
+        let follow = CameraFollow::default();
+        let (center, scale) = follow
+            .framing([Vec2::new(100.0, 50.0)], Vec2::new(1280.0, 720.0))
+            .unwrap();
+
+        assert_eq!(center, Vec2::new(100.0, 50.0));
+        assert_eq!(scale, follow.min_scale);
+    }
+
+    #[test]
+    fn test_framing_zooms_out_for_distant_players() {
+        let follow = CameraFollow::default();
+        let (center, scale) = follow
+            .framing(
+                [Vec2::new(-800.0, 0.0), Vec2::new(800.0, 0.0)],
+                Vec2::new(1280.0, 720.0),
+            )
+            .unwrap();
+
+        assert_eq!(center, Vec2::ZERO);
+        assert!(scale > 1.0 && scale <= follow.max_scale);
+    }
+
+    #[test]
+    fn test_framing_no_players() {
+        let follow = CameraFollow::default();
+        assert!(follow.framing([], Vec2::new(1280.0, 720.0)).is_none());
+    }
+}
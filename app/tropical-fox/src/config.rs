@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
+use tropical_fox_combat::HitFeedbackSettings;
+use tropical_fox_common::DEFAULT_CELL_SIZE;
+use tropical_fox_player::CoopSettings;
 
 /// Physics settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +18,13 @@ pub struct PhysicsSettings {
     pub gravity: f32,
     /// Terminal velocity (maximum falling speed)
     pub terminal_velocity: f32,
+    /// Cell size of the collision broadphase grid (pixels)
+    #[serde(default = "default_broadphase_cell_size")]
+    pub broadphase_cell_size: f32,
+}
+
+fn default_broadphase_cell_size() -> f32 {
+    DEFAULT_CELL_SIZE
 }
 
 impl Default for PhysicsSettings {
@@ -22,6 +32,7 @@ impl Default for PhysicsSettings {
         Self {
             gravity: -980.0,
             terminal_velocity: -500.0,
+            broadphase_cell_size: DEFAULT_CELL_SIZE,
         }
     }
 }
@@ -57,6 +68,14 @@ pub struct PlayerSettings {
     pub wall_jump_force_y: f32,
     /// Wall slide speed
     pub wall_slide_speed: f32,
+    /// Number of mid-air jumps granted by double jump
+    pub max_air_jumps: u32,
+    /// Double jump force
+    pub double_jump_force: f32,
+    /// Ground pound slam speed
+    pub ground_pound_speed: f32,
+    /// Movement speed multiplier while charging an attack
+    pub charge_move_speed_multiplier: f32,
 }
 
 impl Default for PlayerSettings {
@@ -76,6 +95,10 @@ impl Default for PlayerSettings {
             wall_jump_force_x: 300.0,
             wall_jump_force_y: 400.0,
             wall_slide_speed: -100.0,
+            max_air_jumps: 1,
+            double_jump_force: 350.0,
+            ground_pound_speed: 500.0,
+            charge_move_speed_multiplier: 0.4,
         }
     }
 }
@@ -113,6 +136,12 @@ pub struct GameSettings {
     pub window: WindowSettings,
     /// Player configuration
     pub player: PlayerSettings,
+    /// Local co-op configuration
+    #[serde(default)]
+    pub coop: CoopSettings,
+    /// Damage numbers and hit sparks
+    #[serde(default)]
+    pub hit_feedback: HitFeedbackSettings,
 }
 
 /// Configuration loading errors
@@ -3,9 +3,12 @@
 //! Handles basic game initialization and setup.
 
 use bevy::prelude::*;
-use tropical_fox_common::{GameState, InGameState, PhysicsConfig};
+use tropical_fox_common::{
+    GameState, InGameState, PhysicsConfig, SpatialIndex, custom_physics, update_spatial_index,
+};
 use tropical_fox_player::spawn_test_ground;
 
+use crate::camera_systems::{CameraFollow, camera_follow_system};
 use crate::config;
 use crate::physics_systems::{apply_gravity, update_position};
 
@@ -27,6 +30,7 @@ impl Plugin for CorePlugin {
         };
 
         app.insert_resource(physics_config);
+        app.insert_resource(SpatialIndex::new(settings.physics.broadphase_cell_size));
 
         // Register startup systems
         app.add_systems(Startup, (setup_camera, setup_initial_state));
@@ -35,13 +39,31 @@ impl Plugin for CorePlugin {
         app.add_systems(OnEnter(GameState::InGame), spawn_test_ground);
 
         // Register physics systems (run in FixedUpdate for consistent physics)
-        app.add_systems(FixedUpdate, (apply_gravity, update_position).chain());
+        // The spatial index is rebuilt after movement for this tick's collision queries.
+        // With the rapier backend, bodies are moved by rapier between these systems.
+        app.add_systems(
+            FixedUpdate,
+            (
+                apply_gravity,
+                update_position.run_if(custom_physics),
+                update_spatial_index,
+            )
+                .chain(),
+        );
+
+        // Camera follows the players (screen shake is applied on top)
+        app.add_systems(
+            Update,
+            camera_follow_system
+                .before(tropical_fox_combat::screen_shake_system)
+                .run_if(in_state(GameState::InGame)),
+        );
     }
 }
 
 /// Set up the main 2D camera
 fn setup_camera(mut commands: Commands) {
-    commands.spawn((Camera2d, Name::new("Main Camera")));
+    commands.spawn((Camera2d, CameraFollow::default(), Name::new("Main Camera")));
 }
 
 /// Set initial game state to InGame for development
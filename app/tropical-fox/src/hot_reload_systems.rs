@@ -3,22 +3,28 @@
 //! These systems react to configuration file changes and update game state accordingly.
 
 use bevy::prelude::*;
-use tropical_fox_common::{PhysicsConfig, Player, PlayerStats};
+use tropical_fox_combat::{AttacksConfig, CombatConfig, HitFeedbackSettings};
+use tropical_fox_common::{PhysicsConfig, Player, PlayerStats, SpatialIndex};
 use tropical_fox_enemy::{
-    BossesConfig, EnemiesConfig, Enemy, EnemyAI, EnemyStats, ProjectileShooter,
+    AINode, AIStateMachine, BossConfig, BossesConfig, EnemiesConfig, Enemy, EnemyAI, EnemyStats,
+    GroundMovement, MeleeAttacker, ProjectileShooter,
 };
 use tropical_fox_hot_asset::{AssetReloaded, HotAssetHandle};
 use tropical_fox_player::PlayersConfig;
 
 use crate::config::GameSettings;
+use crate::hud::HudConfig;
 
 /// System that applies reloaded game settings to the game state
+#[allow(clippy::too_many_arguments)]
 pub fn apply_game_settings_reload(
     mut events: MessageReader<AssetReloaded<GameSettings>>,
     handle: Res<HotAssetHandle<GameSettings>>,
     assets: Res<Assets<GameSettings>>,
     mut settings_resource: ResMut<GameSettings>,
     mut physics_config: ResMut<PhysicsConfig>,
+    mut spatial_index: ResMut<SpatialIndex>,
+    mut hit_feedback: ResMut<HitFeedbackSettings>,
     mut player_query: Query<&mut PlayerStats, With<Player>>,
 ) {
     for event in events.read() {
@@ -36,6 +42,9 @@ pub fn apply_game_settings_reload(
                 "  Updated physics: gravity={}, terminal_velocity={}",
                 new_settings.physics.gravity, new_settings.physics.terminal_velocity
             );
+            if spatial_index.bodies.cell_size() != new_settings.physics.broadphase_cell_size {
+                spatial_index.set_cell_size(new_settings.physics.broadphase_cell_size);
+            }
 
             // Update all player entities
             let mut player_count = 0;
@@ -54,6 +63,11 @@ pub fn apply_game_settings_reload(
                 stats.wall_jump_force_x = new_settings.player.wall_jump_force_x;
                 stats.wall_jump_force_y = new_settings.player.wall_jump_force_y;
                 stats.wall_slide_speed = new_settings.player.wall_slide_speed;
+                stats.max_air_jumps = new_settings.player.max_air_jumps;
+                stats.double_jump_force = new_settings.player.double_jump_force;
+                stats.ground_pound_speed = new_settings.player.ground_pound_speed;
+                stats.charge_move_speed_multiplier =
+                    new_settings.player.charge_move_speed_multiplier;
                 player_count += 1;
             }
 
@@ -61,6 +75,9 @@ pub fn apply_game_settings_reload(
                 info!("  Updated {} player entities", player_count);
             }
 
+            // Update damage numbers and hit sparks
+            *hit_feedback = new_settings.hit_feedback.clone();
+
             // Update the GameSettings resource
             *settings_resource = new_settings.clone();
 
@@ -70,6 +87,7 @@ pub fn apply_game_settings_reload(
 }
 
 /// System that applies reloaded enemy configuration to the game state
+#[allow(clippy::type_complexity)]
 pub fn apply_enemies_config_reload(
     mut events: MessageReader<AssetReloaded<EnemiesConfig>>,
     handle: Res<HotAssetHandle<EnemiesConfig>>,
@@ -79,6 +97,9 @@ pub fn apply_enemies_config_reload(
         &mut EnemyStats,
         &mut EnemyAI,
         Option<&mut ProjectileShooter>,
+        Option<&mut GroundMovement>,
+        Option<&mut AIStateMachine>,
+        Option<&mut MeleeAttacker>,
     )>,
 ) {
     for event in events.read() {
@@ -91,7 +112,9 @@ pub fn apply_enemies_config_reload(
 
             let mut updated_count = 0;
 
-            for (enemy, mut stats, mut ai, shooter) in enemy_query.iter_mut() {
+            for (enemy, mut stats, mut ai, shooter, ground_movement, machine, melee) in
+                enemy_query.iter_mut()
+            {
                 if let Some(config) = new_config.enemies.get(&enemy.enemy_type) {
                     // Update stats
                     stats.move_speed = config.stats.move_speed;
@@ -100,18 +123,76 @@ pub fn apply_enemies_config_reload(
                     stats.score_value = config.stats.score_value;
 
                     // Update AI while preserving runtime state
-                    update_ai_preserving_state(&mut ai, &config.ai);
+                    match machine {
+                        Some(mut machine) => {
+                            if machine.reload(&config.ai, &mut ai) {
+                                let node = machine.node();
+                                update_ai_preserving_state(&mut ai, &config.ai, node);
+                                // Left states keep their runtime state too
+                                let AIStateMachine {
+                                    parked,
+                                    config: machine_config,
+                                    ..
+                                } = &mut *machine;
+                                for (state, parked_ai) in parked.iter_mut() {
+                                    if let Some(state) = machine_config.state(state) {
+                                        update_ai_preserving_state(
+                                            parked_ai, &config.ai, state.node,
+                                        );
+                                    }
+                                }
+                            } else {
+                                warn!(
+                                    "  State machine removed from '{}' - full update requires respawn",
+                                    enemy.enemy_type
+                                );
+                            }
+                        }
+                        None if config.ai.state_machine.is_some() => {
+                            warn!(
+                                "  State machine added to '{}' - full update requires respawn",
+                                enemy.enemy_type
+                            );
+                        }
+                        None => {
+                            update_ai_preserving_state(
+                                &mut ai,
+                                &config.ai,
+                                AINode::from(&config.ai.behavior),
+                            );
+                        }
+                    }
+                    if let Some(mut ground_movement) = ground_movement {
+                        *ground_movement = GroundMovement::from_config(&config.ai);
+                    }
 
                     // Update projectile shooter if present
                     if config.projectile.enabled
                         && let Some(mut shooter) = shooter
                     {
-                        shooter.damage = config.projectile.damage;
-                        shooter.projectile_speed = config.projectile.speed;
+                        shooter.projectile = config.projectile.projectile(config.impact);
+                        shooter.emitter = config.projectile.emitter;
                         shooter.fire_rate = config.projectile.fire_rate;
                         shooter.range = config.projectile.range;
                     }
 
+                    // Update melee attack timings and hitbox (takes effect on the next attack)
+                    match (&config.melee, melee) {
+                        (Some(melee_config), Some(mut melee)) => {
+                            melee.config = melee_config.clone();
+                            melee.impact = config.impact;
+                        }
+                        (Some(_), None) => warn!(
+                            "  Melee attack added to '{}' - full update requires respawn",
+                            enemy.enemy_type
+                        ),
+                        (None, Some(_)) => warn!(
+                            "  Melee attack removed from '{}' - full update requires respawn",
+                            enemy.enemy_type
+                        ),
+                        (None, None) => {}
+                    }
+
                     updated_count += 1;
                 }
             }
@@ -126,52 +207,67 @@ pub fn apply_enemies_config_reload(
 }
 
 /// Updates AI configuration while preserving runtime state
-fn update_ai_preserving_state(current_ai: &mut EnemyAI, new_config: &tropical_fox_enemy::AIConfig) {
-    use tropical_fox_enemy::AIBehaviorType;
-
+///
+/// `node` is the movement node the AI should be running: the behavior type,
+/// or the state machine state the AI belongs to.
+fn update_ai_preserving_state(
+    current_ai: &mut EnemyAI,
+    new_config: &tropical_fox_enemy::AIConfig,
+    node: AINode,
+) {
     match current_ai {
-        EnemyAI::Patrol(patrol) => {
-            if matches!(new_config.behavior, AIBehaviorType::Patrol) {
+        EnemyAI::Patrol(patrol) => match node {
+            AINode::Patrol => {
                 patrol.wait_time = new_config.wait_time;
                 info!("  Updated Patrol AI: wait_time={}", patrol.wait_time);
-            } else {
+            }
+            AINode::MoveTo(x, y) => {
+                patrol.wait_time = new_config.wait_time;
+                patrol.patrol_points = vec![Vec2::new(x, y)];
+                patrol.current_point_index = 0;
+                info!("  Updated MoveTo AI: target=({}, {})", x, y);
+            }
+            _ => {
                 warn!(
                     "  AI behavior changed from Patrol to {:?} - full update requires respawn",
-                    new_config.behavior
+                    node
                 );
             }
-        }
+        },
         EnemyAI::Chase(chase) => {
-            if matches!(new_config.behavior, AIBehaviorType::Chase) {
+            if node == AINode::Chase {
                 chase.detection_range = new_config.detection_range;
                 chase.attack_range = new_config.attack_range;
+                chase.view_angle = new_config.view_angle;
+                chase.line_of_sight = new_config.line_of_sight;
+                chase.lose_aggro_time = new_config.lose_aggro_time;
 
                 info!(
-                    "  Updated Chase AI: detection={}, attack_range={}",
-                    chase.detection_range, chase.attack_range
+                    "  Updated Chase AI: detection={}, attack_range={}, view_angle={}",
+                    chase.detection_range, chase.attack_range, chase.view_angle
                 );
             } else {
                 warn!(
                     "  AI behavior changed from Chase to {:?} - full update requires respawn",
-                    new_config.behavior
+                    node
                 );
             }
         }
         EnemyAI::Flying(_flying) => {
-            if matches!(new_config.behavior, AIBehaviorType::Flying) {
+            if node == AINode::Flying {
                 info!("  Flying AI detected - pattern updates require respawn");
             } else {
                 warn!(
                     "  AI behavior changed from Flying to {:?} - full update requires respawn",
-                    new_config.behavior
+                    node
                 );
             }
         }
         EnemyAI::Idle => {
-            if !matches!(new_config.behavior, AIBehaviorType::Idle) {
+            if node != AINode::Idle {
                 warn!(
                     "  AI behavior changed from Idle to {:?} - full update requires respawn",
-                    new_config.behavior
+                    node
                 );
             }
         }
@@ -204,6 +300,7 @@ pub fn apply_bosses_config_reload(
     mut events: MessageReader<AssetReloaded<BossesConfig>>,
     handle: Res<HotAssetHandle<BossesConfig>>,
     assets: Res<Assets<BossesConfig>>,
+    mut boss_config: ResMut<BossConfig>,
 ) {
     for event in events.read() {
         if !event.success {
@@ -214,7 +311,80 @@ pub fn apply_bosses_config_reload(
             info!("🐉 Applying reloaded bosses.ron...");
             info!("  Total boss types: {}", new_config.bosses.len());
 
+            // Bosses read phases, patterns and arenas from the resource every frame
+            boss_config.0 = new_config.clone();
+
             info!("✅ Successfully applied reloaded bosses config");
         }
     }
 }
+
+/// System that applies reloaded attack definitions
+///
+/// Attacks already in progress keep their hitboxes; the new values apply from
+/// the next attack.
+pub fn apply_attacks_config_reload(
+    mut events: MessageReader<AssetReloaded<AttacksConfig>>,
+    handle: Res<HotAssetHandle<AttacksConfig>>,
+    assets: Res<Assets<AttacksConfig>>,
+    mut attacks: ResMut<AttacksConfig>,
+) {
+    for event in events.read() {
+        if !event.success {
+            continue;
+        }
+
+        if let Some(new_config) = assets.get(&handle.0) {
+            info!("⚔️ Applying reloaded attacks.ron...");
+            info!("  Total attacks: {}", new_config.attacks.len());
+
+            *attacks = new_config.clone();
+
+            info!("✅ Successfully applied reloaded attacks config");
+        }
+    }
+}
+
+/// System that applies reloaded combat feel configuration
+pub fn apply_combat_config_reload(
+    mut events: MessageReader<AssetReloaded<CombatConfig>>,
+    handle: Res<HotAssetHandle<CombatConfig>>,
+    assets: Res<Assets<CombatConfig>>,
+    mut combat_config: ResMut<CombatConfig>,
+) {
+    for event in events.read() {
+        if !event.success {
+            continue;
+        }
+
+        if let Some(new_config) = assets.get(&handle.0) {
+            info!("💥 Applying reloaded combat.ron...");
+
+            *combat_config = new_config.clone();
+
+            info!("✅ Successfully applied reloaded combat config");
+        }
+    }
+}
+
+/// System that applies reloaded HUD configuration (the HUD is rebuilt on change)
+pub fn apply_hud_config_reload(
+    mut events: MessageReader<AssetReloaded<HudConfig>>,
+    handle: Res<HotAssetHandle<HudConfig>>,
+    assets: Res<Assets<HudConfig>>,
+    mut hud_config: ResMut<HudConfig>,
+) {
+    for event in events.read() {
+        if !event.success {
+            continue;
+        }
+
+        if let Some(new_config) = assets.get(&handle.0) {
+            info!("🖥️ Applying reloaded hud.ron...");
+
+            *hud_config = new_config.clone();
+
+            info!("✅ Successfully applied reloaded HUD config");
+        }
+    }
+}
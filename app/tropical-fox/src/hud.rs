@@ -0,0 +1,832 @@
+//! In-game HUD
+//!
+//! Bevy UI overlay showing each player's health, lives, score, collectibles
+//! and the boss health bar in boss rooms. Layout, colors and scale are
+//! configured in `assets/config/hud.ron` (hot-reloaded in debug builds).
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+use tropical_fox_combat::{Health, Lives, SharedLives};
+use tropical_fox_common::{GameState, InGameState, Player};
+use tropical_fox_enemy::{ActiveBossFight, Boss, Score};
+use tropical_fox_player::CollectibleCount;
+
+/// Minimum score roll-up speed (points per second) so the display always catches up
+const MIN_ROLL_UP_SPEED: f32 = 30.0;
+
+/// Screen corner or edge an element is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HudAnchor {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl HudAnchor {
+    /// All anchors, in spawn order
+    const ALL: [HudAnchor; 6] = [
+        HudAnchor::TopLeft,
+        HudAnchor::TopCenter,
+        HudAnchor::TopRight,
+        HudAnchor::BottomLeft,
+        HudAnchor::BottomCenter,
+        HudAnchor::BottomRight,
+    ];
+
+    /// Absolutely positioned column container for this anchor
+    fn container(&self, margin: f32) -> Node {
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(margin / 2.0),
+            ..default()
+        };
+
+        match self {
+            HudAnchor::TopLeft | HudAnchor::TopCenter | HudAnchor::TopRight => {
+                node.top = Val::Px(margin);
+            }
+            HudAnchor::BottomLeft | HudAnchor::BottomCenter | HudAnchor::BottomRight => {
+                node.bottom = Val::Px(margin);
+            }
+        }
+
+        match self {
+            HudAnchor::TopLeft | HudAnchor::BottomLeft => {
+                node.left = Val::Px(margin);
+                node.align_items = AlignItems::FlexStart;
+            }
+            HudAnchor::TopRight | HudAnchor::BottomRight => {
+                node.right = Val::Px(margin);
+                node.align_items = AlignItems::FlexEnd;
+            }
+            HudAnchor::TopCenter | HudAnchor::BottomCenter => {
+                node.left = Val::Px(0.0);
+                node.right = Val::Px(0.0);
+                node.align_items = AlignItems::Center;
+            }
+        }
+
+        node
+    }
+}
+
+/// How health is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HealthStyle {
+    /// Hearts that empty gradually (partial hearts are drawn partly filled)
+    Hearts { count: u32 },
+    /// Bar split into segments that are either full or empty
+    Segments { count: u32 },
+}
+
+impl HealthStyle {
+    /// Number of hearts or segments
+    pub fn count(&self) -> u32 {
+        match *self {
+            HealthStyle::Hearts { count } | HealthStyle::Segments { count } => count.max(1),
+        }
+    }
+
+    /// Fill amount (0.0 to 1.0) of each heart or segment for a health ratio
+    pub fn fills(&self, ratio: f32) -> Vec<f32> {
+        let count = self.count();
+        let filled = ratio.clamp(0.0, 1.0) * count as f32;
+
+        (0..count)
+            .map(|i| match self {
+                HealthStyle::Hearts { .. } => (filled - i as f32).clamp(0.0, 1.0),
+                // Any remaining health in a segment keeps it lit
+                HealthStyle::Segments { .. } => {
+                    if filled > i as f32 {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Player health display (one row per player)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthHudConfig {
+    /// Screen anchor
+    #[serde(default)]
+    pub anchor: HudAnchor,
+    /// Hearts or segments
+    pub style: HealthStyle,
+    /// Size of a heart or segment (pixels, before scaling)
+    pub cell_size: (f32, f32),
+    /// Gap between hearts or segments (pixels, before scaling)
+    #[serde(default)]
+    pub spacing: f32,
+    /// Color of filled health (RGB)
+    pub fill_color: (f32, f32, f32),
+    /// Color of missing health (RGB)
+    pub empty_color: (f32, f32, f32),
+}
+
+impl Default for HealthHudConfig {
+    fn default() -> Self {
+        Self {
+            anchor: HudAnchor::TopLeft,
+            style: HealthStyle::Hearts { count: 5 },
+            cell_size: (20.0, 20.0),
+            spacing: 4.0,
+            fill_color: (0.9, 0.2, 0.25),
+            empty_color: (0.25, 0.1, 0.12),
+        }
+    }
+}
+
+/// A labelled counter (lives, score, collectibles)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounterHudConfig {
+    /// Whether the counter is shown
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Screen anchor
+    #[serde(default)]
+    pub anchor: HudAnchor,
+    /// Text shown before the value
+    #[serde(default)]
+    pub label: String,
+    /// Text color (RGB)
+    #[serde(default = "default_text_color")]
+    pub color: (f32, f32, f32),
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_text_color() -> (f32, f32, f32) {
+    (1.0, 1.0, 1.0)
+}
+
+impl CounterHudConfig {
+    fn new(anchor: HudAnchor, label: &str) -> Self {
+        Self {
+            enabled: true,
+            anchor,
+            label: label.to_string(),
+            color: default_text_color(),
+        }
+    }
+}
+
+/// Boss health bar (shown in boss rooms)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossBarHudConfig {
+    /// Screen anchor
+    #[serde(default = "default_boss_bar_anchor")]
+    pub anchor: HudAnchor,
+    /// Bar size (pixels, before scaling)
+    pub size: (f32, f32),
+    /// Color of remaining health (RGB)
+    pub fill_color: (f32, f32, f32),
+    /// Bar background color (RGB)
+    pub back_color: (f32, f32, f32),
+    /// Show the boss name above the bar
+    #[serde(default = "default_enabled")]
+    pub show_name: bool,
+    /// Banner shown during the victory sequence
+    #[serde(default = "default_victory_text")]
+    pub victory_text: String,
+}
+
+fn default_victory_text() -> String {
+    "VICTORY!".to_string()
+}
+
+fn default_boss_bar_anchor() -> HudAnchor {
+    HudAnchor::BottomCenter
+}
+
+impl Default for BossBarHudConfig {
+    fn default() -> Self {
+        Self {
+            anchor: default_boss_bar_anchor(),
+            size: (480.0, 16.0),
+            fill_color: (0.85, 0.15, 0.2),
+            back_color: (0.15, 0.1, 0.1),
+            show_name: true,
+            victory_text: default_victory_text(),
+        }
+    }
+}
+
+/// HUD layout and style
+#[derive(Debug, Clone, Serialize, Deserialize, Resource, Asset, TypePath)]
+pub struct HudConfig {
+    /// Scale applied to all sizes, margins and fonts
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// Distance from the screen edges (pixels, before scaling)
+    #[serde(default = "default_margin")]
+    pub margin: f32,
+    /// Font size of counters (before scaling)
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    /// Player health
+    #[serde(default)]
+    pub health: HealthHudConfig,
+    /// Lives counter (shared pool, or each player's lives)
+    #[serde(default = "default_lives")]
+    pub lives: CounterHudConfig,
+    /// Score counter
+    #[serde(default = "default_score")]
+    pub score: CounterHudConfig,
+    /// Time for the score display to roll up to a new value (seconds, 0 = instant)
+    #[serde(default = "default_score_roll_up_secs")]
+    pub score_roll_up_secs: f32,
+    /// Collectible counter
+    #[serde(default = "default_collectibles")]
+    pub collectibles: CounterHudConfig,
+    /// Boss health bar
+    #[serde(default)]
+    pub boss_bar: BossBarHudConfig,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+fn default_margin() -> f32 {
+    16.0
+}
+
+fn default_font_size() -> f32 {
+    20.0
+}
+
+fn default_lives() -> CounterHudConfig {
+    CounterHudConfig::new(HudAnchor::TopLeft, "x")
+}
+
+fn default_score() -> CounterHudConfig {
+    CounterHudConfig::new(HudAnchor::TopRight, "SCORE ")
+}
+
+fn default_score_roll_up_secs() -> f32 {
+    0.5
+}
+
+fn default_collectibles() -> CounterHudConfig {
+    CounterHudConfig::new(HudAnchor::TopRight, "GEMS ")
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            scale: default_scale(),
+            margin: default_margin(),
+            font_size: default_font_size(),
+            health: HealthHudConfig::default(),
+            lives: default_lives(),
+            score: default_score(),
+            score_roll_up_secs: default_score_roll_up_secs(),
+            collectibles: default_collectibles(),
+            boss_bar: BossBarHudConfig::default(),
+        }
+    }
+}
+
+impl HudConfig {
+    /// Scaled pixel value
+    fn px(&self, value: f32) -> Val {
+        Val::Px(value * self.scale)
+    }
+
+    /// Scaled text font
+    fn font(&self) -> TextFont {
+        TextFont {
+            font_size: self.font_size * self.scale,
+            ..default()
+        }
+    }
+}
+
+/// HUD configuration loading errors
+#[derive(Debug, Error)]
+pub enum HudConfigError {
+    #[error("Failed to read HUD config file: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse RON HUD config: {0}")]
+    RonError(#[from] ron::error::SpannedError),
+}
+
+/// Load the HUD configuration from a RON file
+pub fn load_hud_config<P: AsRef<Path>>(path: P) -> Result<HudConfig, HudConfigError> {
+    let content = fs::read_to_string(path)?;
+    let config: HudConfig = ron::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load the HUD configuration from a RON file, or return default if loading/parsing fails
+pub fn load_hud_config_optional<P: AsRef<Path>>(path: P) -> HudConfig {
+    match load_hud_config(path) {
+        Ok(config) => {
+            info!("Loaded HUD config");
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load HUD config: {}. Using default.", e);
+            HudConfig::default()
+        }
+    }
+}
+
+fn rgb((r, g, b): (f32, f32, f32)) -> Color {
+    Color::srgb(r, g, b)
+}
+
+/// Root node of the HUD
+#[derive(Component, Debug)]
+pub struct HudRoot;
+
+/// Container of the player health rows
+#[derive(Component, Debug)]
+pub struct HudHealthContainer;
+
+/// Health row of a player
+#[derive(Component, Debug)]
+pub struct HudHealthRow {
+    /// The player shown by this row
+    pub player: Entity,
+}
+
+/// Filled part of a heart or segment
+#[derive(Component, Debug)]
+pub struct HudHealthFill {
+    /// The player whose health is shown
+    pub player: Entity,
+    /// Index of the heart or segment
+    pub index: usize,
+}
+
+/// Lives counter text
+#[derive(Component, Debug)]
+pub struct HudLivesText;
+
+/// Score counter text
+#[derive(Component, Debug, Default)]
+pub struct HudScoreText {
+    /// Score currently displayed (rolls up toward the actual score)
+    pub shown: f32,
+}
+
+/// Collectible counter text
+#[derive(Component, Debug)]
+pub struct HudCollectiblesText;
+
+/// Boss health bar container
+#[derive(Component, Debug)]
+pub struct HudBossBar;
+
+/// Filled part of the boss health bar
+#[derive(Component, Debug)]
+pub struct HudBossBarFill;
+
+/// Boss name text
+#[derive(Component, Debug)]
+pub struct HudBossName;
+
+/// Victory banner shown after a boss is defeated
+#[derive(Component, Debug)]
+pub struct HudBossVictory;
+
+/// Move the displayed score toward the actual score
+///
+/// Rolls up in about `roll_up_secs`, at least `MIN_ROLL_UP_SPEED` points per second.
+pub fn roll_up(shown: f32, target: f32, delta_secs: f32, roll_up_secs: f32) -> f32 {
+    let diff = target - shown;
+    if roll_up_secs <= 0.0 || diff.abs() < 1.0 {
+        return target;
+    }
+
+    let speed = (diff.abs() / roll_up_secs).max(MIN_ROLL_UP_SPEED);
+    shown + diff.signum() * (speed * delta_secs).min(diff.abs())
+}
+
+/// HUD plugin
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        let config = load_hud_config_optional("assets/config/hud.ron");
+        app.insert_resource(config);
+
+        // Rebuild the layout when entering the game or when the config changes
+        app.add_systems(
+            Update,
+            (
+                rebuild_hud
+                    .run_if(resource_changed::<HudConfig>.or(not(any_with_component::<HudRoot>))),
+                sync_health_rows,
+                update_health_hud,
+                update_lives_hud,
+                update_score_hud,
+                update_collectibles_hud,
+                update_boss_bar_hud,
+            )
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        );
+
+        app.add_systems(OnExit(GameState::InGame), despawn_hud);
+    }
+}
+
+/// Spawn the HUD layout, replacing any existing one
+pub fn rebuild_hud(
+    mut commands: Commands,
+    config: Res<HudConfig>,
+    root_query: Query<Entity, With<HudRoot>>,
+) {
+    for root in root_query.iter() {
+        commands.entity(root).despawn();
+    }
+
+    let margin = config.margin * config.scale;
+    let font = config.font();
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            HudRoot,
+            Name::new("HUD"),
+        ))
+        .with_children(|root| {
+            for anchor in HudAnchor::ALL {
+                root.spawn(anchor.container(margin))
+                    .with_children(|column| {
+                        if config.health.anchor == anchor {
+                            column.spawn((
+                                Node {
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: config.px(config.health.spacing),
+                                    ..default()
+                                },
+                                HudHealthContainer,
+                            ));
+                        }
+
+                        if config.lives.enabled && config.lives.anchor == anchor {
+                            column.spawn((
+                                Text::default(),
+                                font.clone(),
+                                TextColor(rgb(config.lives.color)),
+                                HudLivesText,
+                            ));
+                        }
+
+                        if config.score.enabled && config.score.anchor == anchor {
+                            column.spawn((
+                                Text::default(),
+                                font.clone(),
+                                TextColor(rgb(config.score.color)),
+                                HudScoreText::default(),
+                            ));
+                        }
+
+                        if config.collectibles.enabled && config.collectibles.anchor == anchor {
+                            column.spawn((
+                                Text::default(),
+                                font.clone(),
+                                TextColor(rgb(config.collectibles.color)),
+                                HudCollectiblesText,
+                            ));
+                        }
+
+                        if config.boss_bar.anchor == anchor {
+                            spawn_boss_bar(column, &config);
+                        }
+                    });
+            }
+        });
+}
+
+/// Spawn the (initially hidden) boss health bar and victory banner
+fn spawn_boss_bar(parent: &mut ChildSpawnerCommands, config: &HudConfig) {
+    let bar = &config.boss_bar;
+
+    parent.spawn((
+        Text::new(bar.victory_text.clone()),
+        TextFont {
+            font_size: config.font_size * config.scale * 2.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Visibility::Hidden,
+        HudBossVictory,
+    ));
+
+    parent
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: config.px(4.0),
+                ..default()
+            },
+            Visibility::Hidden,
+            HudBossBar,
+        ))
+        .with_children(|boss_bar| {
+            if bar.show_name {
+                boss_bar.spawn((
+                    Text::default(),
+                    config.font(),
+                    TextColor(Color::WHITE),
+                    HudBossName,
+                ));
+            }
+
+            boss_bar
+                .spawn((
+                    Node {
+                        width: config.px(bar.size.0),
+                        height: config.px(bar.size.1),
+                        ..default()
+                    },
+                    BackgroundColor(rgb(bar.back_color)),
+                ))
+                .with_children(|back| {
+                    back.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(rgb(bar.fill_color)),
+                        HudBossBarFill,
+                    ));
+                });
+        });
+}
+
+/// Keep one health row per player (rebuilt when players join or leave)
+pub fn sync_health_rows(
+    mut commands: Commands,
+    config: Res<HudConfig>,
+    player_query: Query<Entity, With<Player>>,
+    container_query: Query<Entity, With<HudHealthContainer>>,
+    row_query: Query<(Entity, &HudHealthRow)>,
+) {
+    let Ok(container) = container_query.single() else {
+        return;
+    };
+
+    let mut players: Vec<Entity> = player_query.iter().collect();
+    players.sort();
+    let mut shown: Vec<Entity> = row_query.iter().map(|(_, row)| row.player).collect();
+    shown.sort();
+    if players == shown {
+        return;
+    }
+
+    for (row, _) in row_query.iter() {
+        commands.entity(row).despawn();
+    }
+
+    let health = &config.health;
+    commands.entity(container).with_children(|container| {
+        for &player in &players {
+            container
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: config.px(health.spacing),
+                        ..default()
+                    },
+                    HudHealthRow { player },
+                ))
+                .with_children(|row| {
+                    for index in 0..health.style.count() as usize {
+                        row.spawn((
+                            Node {
+                                width: config.px(health.cell_size.0),
+                                height: config.px(health.cell_size.1),
+                                ..default()
+                            },
+                            BackgroundColor(rgb(health.empty_color)),
+                        ))
+                        .with_children(|cell| {
+                            cell.spawn((
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                BackgroundColor(rgb(health.fill_color)),
+                                HudHealthFill { player, index },
+                            ));
+                        });
+                    }
+                });
+        }
+    });
+}
+
+/// Fill hearts or segments from each player's `Health::ratio`
+pub fn update_health_hud(
+    config: Res<HudConfig>,
+    player_query: Query<&Health, With<Player>>,
+    mut fill_query: Query<(&HudHealthFill, &mut Node)>,
+) {
+    for (fill, mut node) in fill_query.iter_mut() {
+        let ratio = player_query.get(fill.player).map_or(0.0, Health::ratio);
+        let amount = config
+            .health
+            .style
+            .fills(ratio)
+            .get(fill.index)
+            .copied()
+            .unwrap_or(0.0);
+        node.width = Val::Percent(amount * 100.0);
+    }
+}
+
+/// Show the shared lives pool, or each player's lives
+pub fn update_lives_hud(
+    config: Res<HudConfig>,
+    shared_lives: Option<Res<SharedLives>>,
+    player_query: Query<(Entity, &Lives), With<Player>>,
+    mut text_query: Query<&mut Text, With<HudLivesText>>,
+) {
+    let value = match shared_lives {
+        Some(shared) => shared.0.current.to_string(),
+        None => {
+            let mut lives: Vec<(Entity, u32)> = player_query
+                .iter()
+                .map(|(entity, lives)| (entity, lives.current))
+                .collect();
+            lives.sort();
+            lives
+                .iter()
+                .map(|(_, current)| current.to_string())
+                .collect::<Vec<_>>()
+                .join(" / ")
+        }
+    };
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("{}{}", config.lives.label, value);
+    }
+}
+
+/// Roll the score display up toward the current score
+pub fn update_score_hud(
+    time: Res<Time>,
+    config: Res<HudConfig>,
+    score: Option<Res<Score>>,
+    mut text_query: Query<(&mut Text, &mut HudScoreText)>,
+) {
+    let target = score.map_or(0, |score| score.value) as f32;
+
+    for (mut text, mut display) in text_query.iter_mut() {
+        display.shown = roll_up(
+            display.shown,
+            target,
+            time.delta_secs(),
+            config.score_roll_up_secs,
+        );
+        text.0 = format!("{}{}", config.score.label, display.shown.round() as u32);
+    }
+}
+
+/// Show the collectible count
+pub fn update_collectibles_hud(
+    config: Res<HudConfig>,
+    count: Option<Res<CollectibleCount>>,
+    mut text_query: Query<&mut Text, With<HudCollectiblesText>>,
+) {
+    let value = count.map_or(0, |count| count.count);
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("{}{}", config.collectibles.label, value);
+    }
+}
+
+/// Show the boss health bar while a boss is alive in a boss room, and the
+/// victory banner once it is defeated
+#[allow(clippy::type_complexity)]
+pub fn update_boss_bar_hud(
+    in_game_state: Option<Res<State<InGameState>>>,
+    fight: Option<Res<ActiveBossFight>>,
+    boss_query: Query<(&Health, Option<&Name>), With<Boss>>,
+    mut bar_query: Query<&mut Visibility, (With<HudBossBar>, Without<HudBossVictory>)>,
+    mut victory_query: Query<&mut Visibility, With<HudBossVictory>>,
+    mut fill_query: Query<&mut Node, With<HudBossBarFill>>,
+    mut name_query: Query<&mut Text, With<HudBossName>>,
+) {
+    let in_boss_room = in_game_state.is_some_and(|state| *state.get() == InGameState::BossRoom);
+
+    // Several bosses share one bar
+    let (current, max) = boss_query
+        .iter()
+        .filter(|(health, _)| health.is_alive())
+        .fold((0.0, 0.0), |(current, max), (health, _)| {
+            (current + health.current, max + health.max)
+        });
+    let show = in_boss_room && max > 0.0;
+    let won = in_boss_room && fight.is_some_and(|fight| fight.is_won());
+
+    for mut visibility in victory_query.iter_mut() {
+        *visibility = if won {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for mut visibility in bar_query.iter_mut() {
+        *visibility = if show {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    if !show {
+        return;
+    }
+
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(current / max * 100.0);
+    }
+
+    let name = boss_query
+        .iter()
+        .find(|(health, _)| health.is_alive())
+        .and_then(|(_, name)| name)
+        .map_or(String::new(), |name| name.to_string());
+    for mut text in name_query.iter_mut() {
+        text.0 = name.clone();
+    }
+}
+
+/// Remove the HUD when leaving the game
+pub fn despawn_hud(mut commands: Commands, root_query: Query<Entity, With<HudRoot>>) {
+    for root in root_query.iter() {
+        commands.entity(root).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heart_fills() {
+        let style = HealthStyle::Hearts { count: 4 };
+        assert_eq!(style.fills(1.0), vec![1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(style.fills(0.625), vec![1.0, 1.0, 0.5, 0.0]);
+        assert_eq!(style.fills(0.0), vec![0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_segment_fills() {
+        let style = HealthStyle::Segments { count: 4 };
+        assert_eq!(style.fills(0.3), vec![1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(style.fills(0.25), vec![1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_score_roll_up() {
+        // Moves part of the way, then snaps when close
+        let shown = roll_up(0.0, 100.0, 0.1, 0.5);
+        assert!(shown > 0.0 && shown < 100.0);
+        assert_eq!(roll_up(99.5, 100.0, 0.1, 0.5), 100.0);
+        assert_eq!(roll_up(0.0, 100.0, 0.1, 0.0), 100.0);
+    }
+
+    #[test]
+    fn test_hud_config_parse() {
+        let config: HudConfig = ron::from_str(
+            "(scale: 2.0, health: (anchor: BottomLeft, style: Segments(count: 10), cell_size: (8.0, 16.0), fill_color: (0.2, 0.9, 0.3), empty_color: (0.1, 0.2, 0.1)), collectibles: (enabled: false))",
+        )
+        .unwrap();
+
+        assert_eq!(config.scale, 2.0);
+        assert_eq!(config.health.style.count(), 10);
+        assert!(!config.collectibles.enabled);
+        assert_eq!(config.score.anchor, HudAnchor::TopRight);
+        assert_eq!(config.boss_bar.anchor, HudAnchor::BottomCenter);
+    }
+}
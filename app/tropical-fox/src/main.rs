@@ -4,10 +4,14 @@
 
 use bevy::prelude::*;
 
+mod camera_systems;
 mod config;
 mod core_plugin;
 mod debug;
+mod hud;
 mod physics_systems;
+#[cfg(feature = "rapier")]
+mod rapier_physics;
 
 // Import from workspace crates
 use tropical_fox_animation::AnimationPlugin;
@@ -17,6 +21,7 @@ use tropical_fox_player::{PlayerPlugin, SelectedCharacter};
 
 use config::load_settings_or_default;
 use core_plugin::CorePlugin;
+use hud::HudPlugin;
 
 #[cfg(debug_assertions)]
 use tropical_fox_hot_asset::HotReloadPlugin;
@@ -27,10 +32,15 @@ mod hot_reload_systems;
 use config::GameSettings;
 #[cfg(debug_assertions)]
 use hot_reload_systems::{
-    apply_bosses_config_reload, apply_enemies_config_reload, apply_game_settings_reload,
+    apply_attacks_config_reload, apply_bosses_config_reload, apply_combat_config_reload,
+    apply_enemies_config_reload, apply_game_settings_reload, apply_hud_config_reload,
     apply_players_config_reload,
 };
 #[cfg(debug_assertions)]
+use hud::HudConfig;
+#[cfg(debug_assertions)]
+use tropical_fox_combat::{AttacksConfig, CombatConfig};
+#[cfg(debug_assertions)]
 use tropical_fox_enemy::{BossesConfig, EnemiesConfig};
 #[cfg(debug_assertions)]
 use tropical_fox_player::PlayersConfig;
@@ -107,6 +117,8 @@ fn main() {
                 }
             }),
     )
+    .insert_resource(settings.coop.clone())
+    .insert_resource(settings.hit_feedback.clone())
     .insert_resource(settings)
     .insert_resource(selected_character)
     .add_plugins((
@@ -115,8 +127,12 @@ fn main() {
         PlayerPlugin,
         CombatPlugin,
         EnemyPlugin,
+        HudPlugin,
     ));
 
+    #[cfg(feature = "rapier")]
+    app.add_plugins(rapier_physics::RapierBackendPlugin);
+
     #[cfg(debug_assertions)]
     {
         app.add_plugins(
@@ -137,6 +153,16 @@ fn main() {
             HotReloadPlugin::<BossesConfig>::new("config/bosses.ron").with_verbose(true),
         );
 
+        app.add_plugins(
+            HotReloadPlugin::<AttacksConfig>::new("config/attacks.ron").with_verbose(true),
+        );
+
+        app.add_plugins(
+            HotReloadPlugin::<CombatConfig>::new("config/combat.ron").with_verbose(true),
+        );
+
+        app.add_plugins(HotReloadPlugin::<HudConfig>::new("config/hud.ron").with_verbose(true));
+
         app.add_systems(
             Update,
             (
@@ -144,6 +170,9 @@ fn main() {
                 apply_enemies_config_reload,
                 apply_players_config_reload,
                 apply_bosses_config_reload,
+                apply_attacks_config_reload,
+                apply_combat_config_reload,
+                apply_hud_config_reload,
             ),
         );
     }
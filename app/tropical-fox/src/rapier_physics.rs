@@ -0,0 +1,504 @@
+//! Rapier physics backend (`rapier` feature)
+//!
+//! Swaps the built-in velocity integration and AABB collision for
+//! `bevy_rapier2d`. Gameplay code keeps writing `Velocity` and reading
+//! `GroundDetection`: each fixed tick the velocity becomes a kinematic
+//! character controller move, and the controller's result is written back
+//! (grounded state, blocked velocity, wall contact).
+//!
+//! Level geometry becomes fixed rapier colliders. A few dynamic props (crates,
+//! a hanging chain and enemy ragdolls) exercise the rest of the simulation.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{
+    Collider as RapierCollider, CollisionGroups, Group, ImpulseJoint, KinematicCharacterController,
+    KinematicCharacterControllerOutput, NoUserData, PhysicsSet, RapierConfiguration,
+    RapierPhysicsPlugin, RevoluteJointBuilder, RigidBody, TimestepMode, Velocity as RapierVelocity,
+};
+use tropical_fox_combat::DeathEvent;
+use tropical_fox_common::{
+    Collider, GameState, Gravity, Ground, GroundDetection, PhysicsBackend, PhysicsConfig, Player,
+    Velocity, Wall, update_spatial_index,
+};
+use tropical_fox_enemy::Enemy;
+use tropical_fox_player::systems::update_ground_state;
+
+use crate::physics_systems::apply_gravity;
+
+/// Pixels per rapier length unit
+const PIXELS_PER_METER: f32 = 32.0;
+
+/// Seconds an enemy ragdoll stays in the level
+const RAGDOLL_LIFETIME_SECS: f32 = 4.0;
+
+/// Walls and ground
+const SOLID_GROUP: Group = Group::GROUP_1;
+/// Players and enemies moved by character controllers
+const CHARACTER_GROUP: Group = Group::GROUP_2;
+/// Dynamic props the characters can push
+const PROP_GROUP: Group = Group::GROUP_3;
+/// Ragdolls (collide with the level and props, never with characters)
+const DEBRIS_GROUP: Group = Group::GROUP_4;
+
+/// Plugin that runs the game's physics on rapier
+pub struct RapierBackendPlugin;
+
+impl Plugin for RapierBackendPlugin {
+    fn build(&self, app: &mut App) {
+        // Step rapier once per fixed tick, in FixedUpdate like the built-in physics
+        let timestep = app
+            .world()
+            .get_resource::<Time<Fixed>>()
+            .map(|time| time.timestep().as_secs_f32())
+            .unwrap_or(1.0 / 64.0);
+        app.insert_resource(TimestepMode::Fixed {
+            dt: timestep,
+            substeps: 1,
+        });
+        app.insert_resource(PhysicsBackend::Rapier);
+        app.add_plugins(
+            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(PIXELS_PER_METER)
+                .in_fixed_schedule(),
+        );
+
+        // Gravity -> controller moves -> rapier step -> results -> spatial index
+        app.add_systems(
+            FixedUpdate,
+            (
+                (attach_rapier_bodies, sync_rapier_gravity, move_free_bodies),
+                drive_character_controllers,
+            )
+                .chain()
+                .after(apply_gravity)
+                .before(PhysicsSet::SyncBackend),
+        );
+        app.add_systems(
+            FixedUpdate,
+            apply_character_controller_output
+                .after(PhysicsSet::Writeback)
+                .before(update_spatial_index)
+                .before(update_ground_state),
+        );
+
+        app.add_systems(OnEnter(GameState::InGame), spawn_test_props);
+        app.add_systems(
+            Update,
+            (
+                spawn_enemy_ragdolls.before(tropical_fox_combat::death_system),
+                ragdoll_lifetime_system,
+            )
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+/// Enemy ragdoll parts, despawned together when the timer runs out
+#[derive(Component, Debug)]
+pub struct Ragdoll {
+    /// Time left before the ragdoll is removed
+    pub timer: Timer,
+    /// Other bodies jointed to this one
+    pub parts: Vec<Entity>,
+}
+
+/// Rapier collider matching a gameplay collider (including its offset)
+fn rapier_collider(collider: &Collider) -> RapierCollider {
+    let half_size = collider.size / 2.0;
+    if collider.offset == Vec2::ZERO {
+        RapierCollider::cuboid(half_size.x, half_size.y)
+    } else {
+        RapierCollider::compound(vec![(
+            collider.offset,
+            0.0,
+            RapierCollider::cuboid(half_size.x, half_size.y),
+        )])
+    }
+}
+
+/// Give level geometry and moving bodies their rapier counterparts
+///
+/// Ground and walls become fixed colliders. Bodies with `Gravity` and
+/// `Velocity` get a kinematic character controller; everything else
+/// (hitboxes, hurtboxes, flying enemies) stays out of the simulation.
+#[allow(clippy::type_complexity)]
+fn attach_rapier_bodies(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &Collider,
+            Has<Ground>,
+            Has<Wall>,
+            Has<Gravity>,
+            Has<Velocity>,
+        ),
+        (Added<Collider>, Without<RapierCollider>),
+    >,
+) {
+    for (entity, collider, is_ground, is_wall, has_gravity, has_velocity) in query.iter() {
+        if is_ground || is_wall {
+            commands.entity(entity).insert((
+                RigidBody::Fixed,
+                rapier_collider(collider),
+                CollisionGroups::new(SOLID_GROUP, Group::ALL),
+            ));
+        } else if has_gravity && has_velocity {
+            commands.entity(entity).insert((
+                RigidBody::KinematicPositionBased,
+                rapier_collider(collider),
+                CollisionGroups::new(CHARACTER_GROUP, SOLID_GROUP | PROP_GROUP),
+                KinematicCharacterController {
+                    filter_groups: Some(CollisionGroups::new(
+                        CHARACTER_GROUP,
+                        SOLID_GROUP | PROP_GROUP,
+                    )),
+                    apply_impulse_to_dynamic_bodies: true,
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+/// Keep rapier gravity (used by dynamic props) in step with `PhysicsConfig`
+fn sync_rapier_gravity(
+    physics_config: Res<PhysicsConfig>,
+    mut configurations: Query<&mut RapierConfiguration>,
+) {
+    let gravity = Vec2::new(0.0, physics_config.gravity);
+    for mut configuration in configurations.iter_mut() {
+        if configuration.gravity != gravity {
+            configuration.gravity = gravity;
+        }
+    }
+}
+
+/// Integrate velocity for bodies without a character controller
+fn move_free_bodies(
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &Velocity), Without<KinematicCharacterController>>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut transform, velocity) in query.iter_mut() {
+        transform.translation.x += velocity.x * delta;
+        transform.translation.y += velocity.y * delta;
+    }
+}
+
+/// Turn this tick's velocity into the character controller's desired move
+fn drive_character_controllers(
+    time: Res<Time>,
+    mut query: Query<(&mut KinematicCharacterController, &Velocity)>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut controller, velocity) in query.iter_mut() {
+        controller.translation = Some(Vec2::new(velocity.x, velocity.y) * delta);
+    }
+}
+
+/// Write the character controller result back into the gameplay components
+///
+/// Velocity into a surface is cancelled, `GroundDetection` follows the
+/// controller's grounded flag and airborne players touching a `Wall` get
+/// their wall contact side for wall slides and wall jumps.
+fn apply_character_controller_output(
+    mut query: Query<(
+        &KinematicCharacterControllerOutput,
+        &mut Velocity,
+        Option<&mut GroundDetection>,
+        Option<&mut Player>,
+    )>,
+    wall_query: Query<(), With<Wall>>,
+) {
+    for (output, mut velocity, ground, player) in query.iter_mut() {
+        if output.grounded && velocity.y < 0.0 {
+            velocity.y = 0.0;
+        }
+
+        let mut wall_contact = 0;
+        for collision in &output.collisions {
+            let Some(details) = collision.hit.details else {
+                continue;
+            };
+            // Surface normal of the obstacle, pointing towards the character
+            let normal = details.normal1;
+            if normal.y < -0.5 && velocity.y > 0.0 {
+                // Bumped into a ceiling
+                velocity.y = 0.0;
+            }
+            if normal.x.abs() > 0.5 {
+                if velocity.x * normal.x < 0.0 {
+                    velocity.x = 0.0;
+                }
+                if wall_query.contains(collision.entity) {
+                    wall_contact = -normal.x.signum() as i32;
+                }
+            }
+        }
+
+        if let Some(mut ground) = ground {
+            ground.is_grounded = output.grounded;
+        }
+        if let Some(mut player) = player {
+            player.wall_contact = if output.grounded { 0 } else { wall_contact };
+        }
+    }
+}
+
+/// Spawn a stack of crates and a hanging chain next to the test ground
+fn spawn_test_props(mut commands: Commands) {
+    let crate_size = Vec2::splat(24.0);
+    // Two crates on the test ground with a third on top
+    for (column, row) in [(-0.6, 0.0), (0.6, 0.0), (0.0, 1.0)] {
+        commands.spawn((
+            Transform::from_xyz(
+                160.0 + column * crate_size.x,
+                -172.0 + row * crate_size.y,
+                0.0,
+            ),
+            Visibility::default(),
+            Sprite {
+                color: Color::srgb(0.6, 0.45, 0.25),
+                custom_size: Some(crate_size),
+                ..default()
+            },
+            RigidBody::Dynamic,
+            RapierCollider::cuboid(crate_size.x / 2.0, crate_size.y / 2.0),
+            CollisionGroups::new(PROP_GROUP, Group::ALL),
+            Name::new("Crate"),
+        ));
+    }
+
+    // Chain of links hanging from a fixed anchor
+    let link_size = Vec2::new(6.0, 16.0);
+    let anchor_position = Vec2::new(-150.0, 150.0);
+    let mut previous = commands
+        .spawn((
+            Transform::from_translation(anchor_position.extend(0.0)),
+            Visibility::default(),
+            Sprite {
+                color: Color::srgb(0.3, 0.3, 0.35),
+                custom_size: Some(Vec2::splat(10.0)),
+                ..default()
+            },
+            RigidBody::Fixed,
+            Name::new("Chain Anchor"),
+        ))
+        .id();
+    let mut previous_anchor = Vec2::ZERO;
+    for link in 0..8 {
+        let position = anchor_position - Vec2::new(0.0, (link as f32 + 0.5) * link_size.y);
+        let joint = RevoluteJointBuilder::new()
+            .local_anchor1(previous_anchor)
+            .local_anchor2(Vec2::new(0.0, link_size.y / 2.0));
+        previous = commands
+            .spawn((
+                Transform::from_translation(position.extend(0.0)),
+                Visibility::default(),
+                Sprite {
+                    color: Color::srgb(0.55, 0.55, 0.6),
+                    custom_size: Some(link_size),
+                    ..default()
+                },
+                RigidBody::Dynamic,
+                RapierCollider::cuboid(link_size.x / 2.0, link_size.y / 2.0),
+                CollisionGroups::new(PROP_GROUP, Group::ALL),
+                ImpulseJoint::new(previous, joint),
+                Name::new("Chain Link"),
+            ))
+            .id();
+        previous_anchor = Vec2::new(0.0, -link_size.y / 2.0);
+    }
+
+    info!("Physics props spawned (rapier backend)");
+}
+
+/// Replace dying enemies with a tumbling two-part ragdoll
+fn spawn_enemy_ragdolls(
+    mut commands: Commands,
+    mut death_events: MessageReader<DeathEvent>,
+    enemy_query: Query<(&Transform, &Collider, &Velocity, Option<&Sprite>), With<Enemy>>,
+) {
+    for event in death_events.read() {
+        let Ok((transform, collider, velocity, sprite)) = enemy_query.get(event.entity) else {
+            continue;
+        };
+
+        let position = transform.translation.truncate() + collider.offset;
+        let body_size = collider.size * Vec2::new(1.0, 0.7);
+        let head_size = Vec2::splat(collider.size.x * 0.5);
+        let launch = Vec2::new(velocity.x, velocity.y.max(0.0) + 150.0);
+        let spin = if velocity.x >= 0.0 { -6.0 } else { 6.0 };
+        let groups = CollisionGroups::new(DEBRIS_GROUP, SOLID_GROUP | PROP_GROUP | DEBRIS_GROUP);
+
+        let head = commands
+            .spawn((
+                Transform::from_translation(
+                    (position + Vec2::new(0.0, (body_size.y + head_size.y) / 2.0))
+                        .extend(transform.translation.z),
+                ),
+                Visibility::default(),
+                Sprite {
+                    color: Color::srgb(0.35, 0.3, 0.3),
+                    custom_size: Some(head_size),
+                    ..default()
+                },
+                RigidBody::Dynamic,
+                RapierCollider::cuboid(head_size.x / 2.0, head_size.y / 2.0),
+                groups,
+                RapierVelocity::linear(launch),
+                Name::new("Ragdoll Head"),
+            ))
+            .id();
+
+        let mut body_sprite = sprite.cloned().unwrap_or_default();
+        body_sprite.custom_size = Some(collider.size);
+        let neck = RevoluteJointBuilder::new()
+            .local_anchor1(Vec2::new(0.0, head_size.y / -2.0))
+            .local_anchor2(Vec2::new(0.0, body_size.y / 2.0));
+        commands.spawn((
+            Transform::from_translation(position.extend(transform.translation.z)),
+            Visibility::default(),
+            body_sprite,
+            RigidBody::Dynamic,
+            RapierCollider::cuboid(body_size.x / 2.0, body_size.y / 2.0),
+            groups,
+            RapierVelocity {
+                linvel: launch,
+                angvel: spin,
+            },
+            ImpulseJoint::new(head, neck),
+            Ragdoll {
+                timer: Timer::from_seconds(RAGDOLL_LIFETIME_SECS, TimerMode::Once),
+                parts: vec![head],
+            },
+            Name::new("Ragdoll"),
+        ));
+    }
+}
+
+/// Remove ragdolls once they have settled for a while
+fn ragdoll_lifetime_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Ragdoll)>,
+) {
+    for (entity, mut ragdoll) in query.iter_mut() {
+        ragdoll.timer.tick(time.delta());
+        if ragdoll.timer.is_finished() {
+            for part in ragdoll.parts.drain(..) {
+                commands.entity(part).despawn();
+            }
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics_systems::update_position;
+    use std::time::Duration;
+    use tropical_fox_common::{SpatialIndex, custom_physics};
+
+    /// Headless app with the fixed-tick physics chain and the rapier backend
+    fn physics_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            bevy::state::app::StatesPlugin,
+            bevy::asset::AssetPlugin::default(),
+            bevy::scene::ScenePlugin,
+        ));
+        app.init_state::<GameState>();
+        app.init_resource::<PhysicsConfig>();
+        app.init_resource::<SpatialIndex>();
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_secs_f32(1.0 / 64.0),
+        ));
+        app.add_systems(
+            FixedUpdate,
+            (
+                apply_gravity,
+                update_position.run_if(custom_physics),
+                update_spatial_index,
+                update_ground_state,
+            )
+                .chain(),
+        );
+        app.add_plugins(RapierBackendPlugin);
+        app
+    }
+
+    #[test]
+    fn test_character_lands_on_ground() {
+        let mut app = physics_app();
+        app.world_mut().spawn((
+            Transform::from_xyz(0.0, -200.0, 0.0),
+            Collider::new(Vec2::new(800.0, 32.0)),
+            Ground,
+        ));
+        let body = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, -100.0, 0.0),
+                Collider::new(Vec2::new(20.0, 30.0)),
+                Gravity::default(),
+                Velocity::default(),
+                GroundDetection::default(),
+            ))
+            .id();
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        // Resting on the ground top (-184) within the controller's skin offset
+        let y = app.world().get::<Transform>(body).unwrap().translation.y;
+        assert!((y - -169.0).abs() < 1.0, "body at y={y}");
+        assert!(
+            app.world()
+                .get::<GroundDetection>(body)
+                .unwrap()
+                .is_grounded
+        );
+        assert_eq!(app.world().get::<Velocity>(body).unwrap().y, 0.0);
+    }
+
+    #[test]
+    fn test_airborne_player_touches_wall() {
+        let mut app = physics_app();
+        app.world_mut().spawn((
+            Transform::from_xyz(40.0, 0.0, 0.0),
+            Collider::new(Vec2::new(32.0, 500.0)),
+            Wall,
+        ));
+        let player = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 0.0),
+                Collider::new(Vec2::new(20.0, 30.0)),
+                Gravity { scale: 0.0 },
+                Velocity::new(100.0, 0.0),
+                GroundDetection::default(),
+                Player::default(),
+            ))
+            .id();
+
+        let mut touched = false;
+        for _ in 0..30 {
+            // Keep pushing into the wall like held movement input
+            app.world_mut().get_mut::<Velocity>(player).unwrap().x = 100.0;
+            app.update();
+            touched |= app.world().get::<Player>(player).unwrap().wall_contact == 1;
+        }
+
+        assert!(touched);
+        // Stopped at the wall's left face (x=24) minus half the body width
+        let x = app.world().get::<Transform>(player).unwrap().translation.x;
+        assert!(x < 14.5, "player at x={x}");
+    }
+}